@@ -0,0 +1,55 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// Files of `dir` with one of `extensions`, sorted by name.
+fn embedded_files(dir: &Path, extensions: &[&str]) -> Vec<(String, String)> {
+    println!("cargo:rerun-if-changed={}", dir.display());
+    let mut files: Vec<(String, String)> = fs::read_dir(dir)
+        .unwrap_or_else(|_| panic!("{} directory has to exist", dir.display()))
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if !extensions.contains(&path.extension()?.to_str()?) {
+                return None;
+            }
+            println!("cargo:rerun-if-changed={}", path.display());
+            let name = path.file_name()?.to_str()?.to_string();
+            Some((name, path.display().to_string()))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+/// Embeds every `locales/<code>.ftl` so that a language is added by adding its file, and every
+/// stylesheet and script of `static/assets` so that the binary serves them itself.
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is set by cargo");
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR is set by cargo");
+
+    let entries: String = embedded_files(&Path::new(&manifest_dir).join("locales"), &["ftl"])
+        .iter()
+        .map(|(name, path)| {
+            let code = name.trim_end_matches(".ftl");
+            format!("    ({:?}, include_str!({:?})),\n", code, path)
+        })
+        .collect();
+    let source = format!(
+        "/// Language code and Fluent source of every file in `locales`\npub static LOCALE_SOURCES: &[(&str, &str)] = &[\n{}];\n",
+        entries
+    );
+    fs::write(Path::new(&out_dir).join("locales.rs"), source)
+        .expect("failed to write the embedded locales");
+
+    let assets_dir = Path::new(&manifest_dir).join("static").join("assets");
+    let entries: String = embedded_files(&assets_dir, &["css", "js"])
+        .iter()
+        .map(|(name, path)| format!("    ({:?}, include_bytes!({:?})),\n", name, path))
+        .collect();
+    let source = format!(
+        "/// File name and content of every stylesheet and script in `static/assets`\npub static ASSET_SOURCES: &[(&str, &[u8])] = &[\n{}];\n",
+        entries
+    );
+    fs::write(Path::new(&out_dir).join("assets.rs"), source)
+        .expect("failed to write the embedded assets");
+}
@@ -0,0 +1,15 @@
+-- Resumable (tus-style) uploads of original painting images
+CREATE TABLE IF NOT EXISTS rosemary.image_uploads (
+	id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+	created TIMESTAMPTZ NOT NULL DEFAULT now(),
+	expires TIMESTAMPTZ NOT NULL,
+	painting_id UUID NOT NULL REFERENCES rosemary.paintings(id) ON DELETE CASCADE,
+	file_name TEXT NOT NULL,
+	content_type TEXT NOT NULL,
+	upload_length BIGINT NOT NULL CHECK (upload_length > 0),
+	upload_offset BIGINT NOT NULL DEFAULT 0 CHECK (upload_offset >= 0),
+	file_path TEXT NOT NULL,
+	meta JSONB NOT NULL DEFAULT '{}'::jsonb
+);
+
+CREATE INDEX IF NOT EXISTS image_uploads_expires_idx ON rosemary.image_uploads(expires);
@@ -0,0 +1,14 @@
+-- Messages submitted through the public contact form
+CREATE TABLE IF NOT EXISTS rosemary.contact_messages (
+	id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+	created TIMESTAMPTZ NOT NULL DEFAULT now(),
+	name TEXT NOT NULL,
+	email TEXT NOT NULL,
+	phone TEXT,
+	subject TEXT NOT NULL,
+	message TEXT NOT NULL,
+	language TEXT NOT NULL DEFAULT 'cs',
+	notified BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+CREATE INDEX IF NOT EXISTS contact_messages_created_idx ON rosemary.contact_messages(created DESC);
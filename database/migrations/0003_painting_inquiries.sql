@@ -0,0 +1,19 @@
+-- Purchase inquiries sent through the buy dialog on the painting detail page
+CREATE TABLE IF NOT EXISTS rosemary.painting_inquiries (
+	id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+	created TIMESTAMPTZ NOT NULL DEFAULT now(),
+	painting_id UUID NOT NULL REFERENCES rosemary.paintings(id) ON DELETE CASCADE,
+	full_name TEXT NOT NULL,
+	email TEXT NOT NULL,
+	message TEXT,
+	language TEXT NOT NULL DEFAULT 'cs',
+	status TEXT NOT NULL DEFAULT 'open' CHECK (status IN ('open', 'answered', 'closed')),
+	reserved_until TIMESTAMPTZ,
+	answer TEXT,
+	answered TIMESTAMPTZ,
+	closed TIMESTAMPTZ,
+	notified BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+CREATE INDEX IF NOT EXISTS painting_inquiries_painting_idx ON rosemary.painting_inquiries(painting_id);
+CREATE INDEX IF NOT EXISTS painting_inquiries_created_idx ON rosemary.painting_inquiries(created DESC);
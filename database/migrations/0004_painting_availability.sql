@@ -0,0 +1,17 @@
+-- Sale status of paintings, replaces the free-form data->sold flag
+ALTER TABLE rosemary.paintings
+	ADD COLUMN IF NOT EXISTS availability TEXT NOT NULL DEFAULT 'available'
+		CHECK (availability IN ('available', 'reserved', 'sold', 'not_for_sale')),
+	ADD COLUMN IF NOT EXISTS reserved_until TIMESTAMPTZ,
+	ADD COLUMN IF NOT EXISTS sold_date TIMESTAMPTZ,
+	ADD COLUMN IF NOT EXISTS buyer_reference TEXT;
+
+UPDATE rosemary.paintings
+SET availability = 'sold'
+WHERE data::jsonb->>'sold' = 'true';
+
+UPDATE rosemary.paintings
+SET data = data::jsonb - 'sold'
+WHERE data::jsonb ? 'sold';
+
+CREATE INDEX IF NOT EXISTS paintings_availability_idx ON rosemary.paintings(availability);
@@ -0,0 +1,16 @@
+-- Painting prices carry their own currency, CZK is the base all rates are quoted against
+ALTER TABLE rosemary.paintings
+	ADD COLUMN IF NOT EXISTS currency TEXT NOT NULL DEFAULT 'CZK';
+
+-- CZK for `amount` units of `currency`, the way the CNB publishes them
+CREATE TABLE IF NOT EXISTS rosemary.exchange_rates (
+	currency TEXT PRIMARY KEY CHECK (currency ~ '^[A-Z]{3}$'),
+	amount INTEGER NOT NULL DEFAULT 1 CHECK (amount > 0),
+	rate DOUBLE PRECISION NOT NULL CHECK (rate > 0),
+	updated TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+-- the rate the gallery used to hard-code
+INSERT INTO rosemary.exchange_rates(currency, amount, rate)
+VALUES ('EUR', 1, 23.0)
+ON CONFLICT (currency) DO NOTHING;
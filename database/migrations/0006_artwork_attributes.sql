@@ -0,0 +1,11 @@
+-- Typed artwork attributes, technique and support are {"cs": .., "en": ..} translations
+ALTER TABLE rosemary.paintings
+	ADD COLUMN IF NOT EXISTS technique JSONB,
+	ADD COLUMN IF NOT EXISTS support JSONB,
+	ADD COLUMN IF NOT EXISTS year_created INTEGER,
+	ADD COLUMN IF NOT EXISTS framed BOOLEAN,
+	ADD COLUMN IF NOT EXISTS depth BIGINT,
+	ADD COLUMN IF NOT EXISTS weight BIGINT;
+
+COMMENT ON COLUMN rosemary.paintings.depth IS 'centimeters, like width and height';
+COMMENT ON COLUMN rosemary.paintings.weight IS 'grams';
@@ -0,0 +1,35 @@
+-- Ordered series of paintings with their own page at /{lang}/series/:slug
+CREATE TABLE IF NOT EXISTS rosemary.series (
+	id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+	created TIMESTAMPTZ NOT NULL DEFAULT now(),
+	slug TEXT NOT NULL UNIQUE CHECK (slug ~ '^[a-z0-9]+(-[a-z0-9]+)*$'),
+	title JSONB NOT NULL DEFAULT '{}'::jsonb,
+	description JSONB NOT NULL DEFAULT '{}'::jsonb,
+	cover_image_id UUID REFERENCES rosemary.painting_images(id) ON DELETE SET NULL,
+	position INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE TABLE IF NOT EXISTS rosemary.series_paintings (
+	series_id UUID NOT NULL REFERENCES rosemary.series(id) ON DELETE CASCADE,
+	painting_id UUID NOT NULL REFERENCES rosemary.paintings(id) ON DELETE CASCADE,
+	position INTEGER NOT NULL DEFAULT 0,
+	PRIMARY KEY (series_id, painting_id)
+);
+
+CREATE INDEX IF NOT EXISTS series_paintings_painting_idx ON rosemary.series_paintings(painting_id);
+
+-- Free tags, name is a {"cs": .., "en": ..} translation
+CREATE TABLE IF NOT EXISTS rosemary.tags (
+	id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+	created TIMESTAMPTZ NOT NULL DEFAULT now(),
+	slug TEXT NOT NULL UNIQUE CHECK (slug ~ '^[a-z0-9]+(-[a-z0-9]+)*$'),
+	name JSONB NOT NULL DEFAULT '{}'::jsonb
+);
+
+CREATE TABLE IF NOT EXISTS rosemary.painting_tags (
+	painting_id UUID NOT NULL REFERENCES rosemary.paintings(id) ON DELETE CASCADE,
+	tag_id UUID NOT NULL REFERENCES rosemary.tags(id) ON DELETE CASCADE,
+	PRIMARY KEY (painting_id, tag_id)
+);
+
+CREATE INDEX IF NOT EXISTS painting_tags_tag_idx ON rosemary.painting_tags(tag_id);
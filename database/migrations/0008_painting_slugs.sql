@@ -0,0 +1,14 @@
+-- Per-language URL slugs of paintings, replaced slugs stay behind for 301 redirects
+CREATE TABLE IF NOT EXISTS rosemary.painting_slugs (
+	painting_id UUID NOT NULL REFERENCES rosemary.paintings(id) ON DELETE CASCADE,
+	language TEXT NOT NULL CHECK (language IN ('cs', 'en')),
+	slug TEXT NOT NULL CHECK (slug ~ '^[a-z0-9]+(-[a-z0-9]+)*$'),
+	current BOOLEAN NOT NULL DEFAULT TRUE,
+	created TIMESTAMPTZ NOT NULL DEFAULT now(),
+	PRIMARY KEY (language, slug)
+);
+
+-- one current slug per painting and language
+CREATE UNIQUE INDEX IF NOT EXISTS painting_slugs_current_idx
+	ON rosemary.painting_slugs(painting_id, language)
+	WHERE current;
@@ -0,0 +1,140 @@
+-- Full-text search over title, tags, technique and description of paintings
+CREATE EXTENSION IF NOT EXISTS unaccent;
+
+-- Czech stemming needs the hunspell files (czech.affix, czech.dict, czech.stop) in
+-- $SHAREDIR/tsearch_data, without them Czech is matched by whole words only.
+DO $$
+BEGIN
+	IF NOT EXISTS (
+		SELECT 1 FROM pg_ts_dict d JOIN pg_namespace n ON n.oid = d.dictnamespace
+		WHERE n.nspname = 'rosemary' AND d.dictname = 'czech_hunspell'
+	) THEN
+		CREATE TEXT SEARCH DICTIONARY rosemary.czech_hunspell (
+			TEMPLATE = ispell,
+			DictFile = czech,
+			AffFile = czech,
+			StopWords = czech
+		);
+	END IF;
+EXCEPTION WHEN OTHERS THEN
+	RAISE NOTICE 'czech hunspell dictionary is not installed, Czech search runs without stemming';
+END $$;
+
+DO $$
+BEGIN
+	IF NOT EXISTS (
+		SELECT 1 FROM pg_ts_config c JOIN pg_namespace n ON n.oid = c.cfgnamespace
+		WHERE n.nspname = 'rosemary' AND c.cfgname = 'czech'
+	) THEN
+		CREATE TEXT SEARCH CONFIGURATION rosemary.czech (COPY = pg_catalog.simple);
+		IF EXISTS (
+			SELECT 1 FROM pg_ts_dict d JOIN pg_namespace n ON n.oid = d.dictnamespace
+			WHERE n.nspname = 'rosemary' AND d.dictname = 'czech_hunspell'
+		) THEN
+			ALTER TEXT SEARCH CONFIGURATION rosemary.czech
+				ALTER MAPPING FOR asciiword, asciihword, hword_asciipart, word, hword, hword_part
+				WITH rosemary.czech_hunspell, simple;
+		END IF;
+	END IF;
+
+	-- stemming dictionaries expect accents, so Czech gets a second accent-free configuration
+	IF NOT EXISTS (
+		SELECT 1 FROM pg_ts_config c JOIN pg_namespace n ON n.oid = c.cfgnamespace
+		WHERE n.nspname = 'rosemary' AND c.cfgname = 'czech_unaccent'
+	) THEN
+		CREATE TEXT SEARCH CONFIGURATION rosemary.czech_unaccent (COPY = pg_catalog.simple);
+		ALTER TEXT SEARCH CONFIGURATION rosemary.czech_unaccent
+			ALTER MAPPING FOR asciiword, asciihword, hword_asciipart, word, hword, hword_part
+			WITH unaccent, simple;
+	END IF;
+
+	IF NOT EXISTS (
+		SELECT 1 FROM pg_ts_config c JOIN pg_namespace n ON n.oid = c.cfgnamespace
+		WHERE n.nspname = 'rosemary' AND c.cfgname = 'english_unaccent'
+	) THEN
+		CREATE TEXT SEARCH CONFIGURATION rosemary.english_unaccent (COPY = pg_catalog.english);
+		ALTER TEXT SEARCH CONFIGURATION rosemary.english_unaccent
+			ALTER MAPPING FOR asciiword, asciihword, hword_asciipart, word, hword, hword_part
+			WITH unaccent, english_stem;
+	END IF;
+END $$;
+
+ALTER TABLE rosemary.paintings
+	ADD COLUMN IF NOT EXISTS search_cs TSVECTOR,
+	ADD COLUMN IF NOT EXISTS search_en TSVECTOR;
+
+-- weights: title A, tags B, technique and support C, description D
+CREATE OR REPLACE FUNCTION rosemary.painting_search_document(
+	painting rosemary.paintings,
+	language TEXT
+) RETURNS TEXT[] AS $$
+	SELECT ARRAY[
+		COALESCE(painting.painting_title::jsonb->>language, ''),
+		COALESCE((
+			SELECT STRING_AGG(t.name->>language, ' ')
+			FROM rosemary.painting_tags pt
+			JOIN rosemary.tags t ON t.id = pt.tag_id
+			WHERE pt.painting_id = painting.id
+		), ''),
+		CONCAT_WS(' ', painting.technique->>language, painting.support->>language),
+		COALESCE(painting.painting_description::jsonb->>language, '')
+	]
+$$ LANGUAGE sql STABLE;
+
+CREATE OR REPLACE FUNCTION rosemary.painting_search_vector(document TEXT[], config REGCONFIG)
+RETURNS TSVECTOR AS $$
+	SELECT
+		setweight(to_tsvector(config, document[1]), 'A') ||
+		setweight(to_tsvector(config, document[2]), 'B') ||
+		setweight(to_tsvector(config, document[3]), 'C') ||
+		setweight(to_tsvector(config, document[4]), 'D')
+$$ LANGUAGE sql IMMUTABLE;
+
+CREATE OR REPLACE FUNCTION rosemary.paintings_search_update() RETURNS TRIGGER AS $$
+DECLARE
+	document_cs TEXT[] := rosemary.painting_search_document(NEW, 'cs');
+	document_en TEXT[] := rosemary.painting_search_document(NEW, 'en');
+BEGIN
+	NEW.search_cs :=
+		rosemary.painting_search_vector(document_cs, 'rosemary.czech') ||
+		rosemary.painting_search_vector(document_cs, 'rosemary.czech_unaccent');
+	NEW.search_en := rosemary.painting_search_vector(document_en, 'rosemary.english_unaccent');
+	RETURN NEW;
+END
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS paintings_search_update ON rosemary.paintings;
+CREATE TRIGGER paintings_search_update
+	BEFORE INSERT OR UPDATE ON rosemary.paintings
+	FOR EACH ROW EXECUTE FUNCTION rosemary.paintings_search_update();
+
+-- tags live in their own tables, touching the painting rebuilds its vectors
+CREATE OR REPLACE FUNCTION rosemary.painting_tags_search_update() RETURNS TRIGGER AS $$
+BEGIN
+	IF TG_TABLE_NAME = 'tags' THEN
+		UPDATE rosemary.paintings p SET id = p.id
+		FROM rosemary.painting_tags pt
+		WHERE pt.painting_id = p.id AND pt.tag_id = NEW.id;
+	ELSIF TG_OP = 'DELETE' THEN
+		UPDATE rosemary.paintings SET id = id WHERE id = OLD.painting_id;
+	ELSE
+		UPDATE rosemary.paintings SET id = id WHERE id = NEW.painting_id;
+	END IF;
+	RETURN NULL;
+END
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS painting_tags_search_update ON rosemary.painting_tags;
+CREATE TRIGGER painting_tags_search_update
+	AFTER INSERT OR DELETE ON rosemary.painting_tags
+	FOR EACH ROW EXECUTE FUNCTION rosemary.painting_tags_search_update();
+
+DROP TRIGGER IF EXISTS tags_search_update ON rosemary.tags;
+CREATE TRIGGER tags_search_update
+	AFTER UPDATE OF name ON rosemary.tags
+	FOR EACH ROW EXECUTE FUNCTION rosemary.painting_tags_search_update();
+
+UPDATE rosemary.paintings SET id = id;
+
+CREATE INDEX IF NOT EXISTS paintings_search_cs_idx ON rosemary.paintings USING GIN (search_cs);
+CREATE INDEX IF NOT EXISTS paintings_search_en_idx ON rosemary.paintings USING GIN (search_en);
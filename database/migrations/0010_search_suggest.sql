@@ -0,0 +1,14 @@
+-- Trigram matching for the search suggestions of the gallery filter form
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+-- lowercase accent-free text, IMMUTABLE so it can be indexed
+CREATE OR REPLACE FUNCTION rosemary.suggest_text(value TEXT) RETURNS TEXT AS $$
+	SELECT lower(unaccent('unaccent'::REGDICTIONARY, COALESCE(value, '')))
+$$ LANGUAGE sql IMMUTABLE PARALLEL SAFE;
+
+CREATE INDEX IF NOT EXISTS paintings_suggest_cs_idx ON rosemary.paintings
+	USING GIN (rosemary.suggest_text(painting_title::jsonb->>'cs') gin_trgm_ops)
+	WHERE deleted IS NULL;
+CREATE INDEX IF NOT EXISTS paintings_suggest_en_idx ON rosemary.paintings
+	USING GIN (rosemary.suggest_text(painting_title::jsonb->>'en') gin_trgm_ops)
+	WHERE deleted IS NULL;
@@ -0,0 +1,4 @@
+-- Dominant colours of the smallest image variant as [{"rgb": [r, g, b], "share": 0.4}, ...],
+-- used to find visually similar paintings. Filled by the upload and on startup.
+ALTER TABLE rosemary.painting_images
+	ADD COLUMN IF NOT EXISTS palette JSONB;
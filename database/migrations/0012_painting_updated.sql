@@ -0,0 +1,9 @@
+-- Last change of a painting, the lastmod of its sitemap entry
+ALTER TABLE rosemary.paintings
+	ADD COLUMN IF NOT EXISTS updated TIMESTAMPTZ;
+
+UPDATE rosemary.paintings SET updated = COALESCE(created, now()) WHERE updated IS NULL;
+
+ALTER TABLE rosemary.paintings
+	ALTER COLUMN updated SET DEFAULT now(),
+	ALTER COLUMN updated SET NOT NULL;
@@ -0,0 +1,8 @@
+-- Page copy edited in the administration, it replaces the message of the compiled catalog
+CREATE TABLE IF NOT EXISTS rosemary.translation_overrides (
+	language TEXT NOT NULL CHECK (language ~ '^[a-z]{2,3}$'),
+	key TEXT NOT NULL,
+	value TEXT NOT NULL,
+	updated TIMESTAMPTZ NOT NULL DEFAULT now(),
+	PRIMARY KEY (language, key)
+);
@@ -0,0 +1,97 @@
+# Czech strings of the site, every key of TranslationKeys has to be here
+
+-language-name = Čeština
+-locale = cs-CZ
+
+# number, currency and date conventions of the CLDR cs locale
+-decimal-separator = ,
+-group-separator = { "\u00A0" }
+-currency-position = after
+-currency-symbols = CZK Kč, EUR €, USD US$, GBP £
+-date-format = %-d. %-m. %Y
+
+all-paintings = vše
+any = Libovolné
+artist-job-title = Malířka a fotografka
+ascending = vzestupně
+availability = dostupnost
+available = k prodeji
+back-to-contact = zpět na kontaktní formulář
+blog = blog
+buy = koupit
+buy-painting = Koupit obraz
+close = zavřít
+contact = kontakt
+contact-invalid-message = Zprávu se nepodařilo odeslat, zkontrolujte prosím formulář a zkuste to znovu.
+contact-thank-you-message = Děkuji za zprávu, ozvu se vám co nejdříve.
+contact-thank-you-title = Rosemary - děkuji
+created = vytvořeno
+descending = sestupně
+email = email
+faq = často kladené otázky
+feed-description = Nejnovější olejomalby Michaely Halásové
+feed-title = Rosemary - nové obrazy
+filter = filtrovar
+framed = Zarámováno
+framing = Rám
+full-name = celé jméno
+gallery = galerie
+gallery-title = Rosemary - galerie
+general = všeobecné
+height = výška
+home = domů
+index-description =
+    Prací v_bance, návštěvou mnichů v_klášterech ležících v_tibetských Himalájích, mnoha osobními vzestupy a_pády, tím_vším jsem_si_v_životě prošla, než jsem nalezla útěchu a_klid ve_vyjadřování svých emocí na_malířské plátno nebo_fotografický papír.
+    Rytmus mé_oblíbené hudby probouzí něco v_mém nitru a_vede můj štětec.
+    Přestože v_mých obrazech můžete vidět mnoho věcí, nejsou to_portréty a_nejsou to_ani_krajiny, jsem_to_já.
+index-hero-alt = hlavní stránka webových stránek výtvarnice rosemary
+index-meta-description = Rosemary je abstraktní malířka a fotografka žijící v Praze, Česká Republicka
+index-meta-image-summary = { "" }
+index-meta-keywords = obrazy, fotografie, foto, abstrakce, olej, umění, malování
+index-picture-alt = profilový obrázek autorky rosemary
+index-title = Rosemary - obrazy, foto
+inquiry-failed = Odeslání se nezdařilo, zkuste to prosím později.
+inquiry-sent = Děkuji, brzy se vám ozvu.
+landscape = Na šířku
+message = zpráva
+name = jméno
+navigation = navigace
+next = další
+no-paintings = Zatím zde nejsou žádné obrazy.
+not-for-sale = není na prodej
+orientation = Orientace
+painting = obraz
+phone = telefon
+photo = foto
+photo-count =
+    { $count ->
+        [one] { $count } fotka
+        [few] { $count } fotky
+       *[other] { $count } fotek
+    }
+photo-pricing = ceník focení
+photo-reservation = rezervovat focení
+photography = focení
+portrait = Na výšku
+prev = předchozí
+price = cena
+range-from = od
+range-to = do
+related-paintings = Mohlo by se vám líbit
+relevance = Relevance
+reserved = rezervováno
+reserved-until = Rezervováno do
+reset-filters = Zrušit filtry
+search = Hledat
+send = odeslat
+series = Série
+sold = prodáno
+square = Čtverec
+subject = předmět
+tag = Štítek
+title = název
+unframed = Bez rámu
+upcoming-events = nadcházející události
+weight = Hmotnost
+width = šířka
+year-created = Rok vzniku
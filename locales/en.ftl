@@ -0,0 +1,96 @@
+# English strings of the site, every key of TranslationKeys has to be here
+
+-language-name = English
+-locale = en-US
+
+# number, currency and date conventions of the CLDR en locale
+-decimal-separator = .
+-group-separator = ,
+-currency-position = before
+-currency-symbols = EUR €, USD $, GBP £
+-date-format = %b %-d, %Y
+
+all-paintings = all
+any = Any
+artist-job-title = Painter and photographer
+ascending = ascending
+availability = availability
+available = available
+back-to-contact = back to the contact form
+blog = blog
+buy = buy
+buy-painting = Buy painting
+close = close
+contact = contact
+contact-invalid-message = The message could not be sent, please check the form and try again.
+contact-thank-you-message = Thank you for your message, I will get back to you as soon as possible.
+contact-thank-you-title = Rosemary - thank you
+created = created
+descending = descending
+email = email
+faq = FAQ
+feed-description = The newest oil paintings by Michaela Halásová
+feed-title = Rosemary - new paintings
+filter = filter
+framed = Framed
+framing = Framing
+full-name = full name
+gallery = gallery
+gallery-title = Rosemary - gallery
+general = general
+height = height
+home = home
+index-description =
+    Through working_in a_bank, visiting monks in_the_Himalayas of_Nepal, experiencing multiple personal rises and falls to_finally finding a_comfort in_expressing my feelings and_emotions on_canvas or_through photography.
+    Passing control of_my_hands to_whatever lies deep down within my_subconsciousness, letting it flow freely in_harmony with the tunes of_my_favorite music.
+    It_is_not a_portrait, it_is_not a_landscape either, though people may see various things in_it, but most importantly, it_is_me.
+index-hero-alt = rosemary artist hero landing page banner
+index-meta-description = Rosemary is abstract painter and photographer located in Prague, Czechia
+index-meta-image-summary = { "" }
+index-meta-keywords = paitings, abstract, oil, photo, family, weddings, art, paint
+index-picture-alt = author home page profile picture
+index-title = Rosemary - paintings, photo
+inquiry-failed = Sending failed, please try again later.
+inquiry-sent = Thank you, I will get back to you soon.
+landscape = Landscape
+message = message
+name = name
+navigation = navigation
+next = next
+no-paintings = There are no paintings here yet.
+not-for-sale = not for sale
+orientation = Orientation
+painting = painting
+phone = phone
+photo = photo
+photo-count =
+    { $count ->
+        [one] { $count } photo
+       *[other] { $count } photos
+    }
+photo-pricing = photo pricing
+photo-reservation = reserve photoshooting
+photography = photography
+portrait = Portrait
+prev = previous
+price = price
+range-from = from
+range-to = to
+related-paintings = You may also like
+relevance = Relevance
+reserved = reserved
+reserved-until = Reserved until
+reset-filters = Reset filters
+search = Search
+send = send
+series = Series
+sold = sold
+square = Square
+subject = subject
+tag = Tag
+title = title
+unframed = Unframed
+upcoming-events = upcoming events
+weight = Weight
+width = width
+year-created = Year
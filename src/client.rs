@@ -1,3 +1,7 @@
+pub mod catalog;
 pub mod component_props;
-pub mod localization;
+pub mod formatting;
+pub mod negotiation;
+pub mod routes;
+pub mod structured_data;
 pub mod translations;
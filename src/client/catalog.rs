@@ -0,0 +1,327 @@
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentError, FluentResource};
+use fluent_syntax::ast;
+use fluent_syntax::parser::ParserError;
+use fluent_syntax::serializer;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use unic_langid::LanguageIdentifier;
+
+#[derive(Debug)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// Line of a byte offset of `source`, counted from 1.
+fn line_at(source: &str, offset: usize) -> usize {
+    source[..offset.min(source.len())].matches('\n').count() + 1
+}
+
+fn syntax_error(source: &str, error: &ParserError) -> ParseError {
+    ParseError {
+        line: line_at(source, error.pos.start),
+        message: error.to_string(),
+    }
+}
+
+/// An entry defined twice is reported at its last definition.
+fn bundle_error(source: &str, error: &FluentError) -> ParseError {
+    let FluentError::Overriding { id, .. } = error else {
+        return ParseError {
+            line: 1,
+            message: error.to_string(),
+        };
+    };
+    let line = source
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| {
+            line.split_once('=')
+                .is_some_and(|(name, _)| name.trim_end().trim_start_matches('-') == id)
+        })
+        .last()
+        .map_or(1, |(index, _)| index + 1);
+    ParseError {
+        line,
+        message: format!("`{}` is defined twice", id),
+    }
+}
+
+fn parse_resource(source: &str) -> Result<FluentResource, ParseError> {
+    FluentResource::try_new(source.to_string())
+        .map_err(|(_, errors)| syntax_error(source, &errors[0]))
+}
+
+/// Fluent value of a message as `message_source` expects it, the pattern without the id and
+/// without the indentation of its lines.
+fn value_source(message: &ast::Message<&str>) -> String {
+    let entry = ast::Entry::Message(ast::Message {
+        id: message.id.clone(),
+        value: message.value.clone(),
+        attributes: Vec::new(),
+        comment: None,
+    });
+    let serialized = serializer::serialize(&ast::Resource { body: vec![entry] });
+    let value = serialized
+        .strip_prefix(message.id.name)
+        .and_then(|rest| rest.strip_prefix(" ="))
+        .unwrap_or("");
+
+    match value.strip_prefix(' ') {
+        Some(inline) => inline.trim_end().to_string(),
+        None => value
+            .trim_matches('\n')
+            .lines()
+            .map(|line| line.strip_prefix("    ").unwrap_or(line))
+            .collect::<Vec<&str>>()
+            .join("\n"),
+    }
+}
+
+/// Fluent entry of a message whose value may span several lines.
+pub fn message_source(id: &str, value: &str) -> String {
+    let lines: Vec<String> = value.lines().map(|line| format!("    {}", line)).collect();
+    format!("{} =\n{}\n", id, lines.join("\n"))
+}
+
+/// Messages and terms of one language, a Fluent bundle with the plain texts formatted once
+pub struct Catalog {
+    language: LanguageIdentifier,
+    /// the locale file first, the overrides after it
+    resources: Vec<Arc<FluentResource>>,
+    bundle: FluentBundle<Arc<FluentResource>>,
+    /// Fluent value of every message as it is written in the file
+    sources: HashMap<String, String>,
+    /// every message and term formatted without arguments
+    plain_messages: HashMap<String, String>,
+    plain_terms: HashMap<String, String>,
+}
+
+impl Catalog {
+    pub fn parse(language: &'static str, source: &str) -> Result<Self, ParseError> {
+        let language: LanguageIdentifier = language.parse().map_err(|_| ParseError {
+            line: 1,
+            message: format!("`{}` is not a language identifier", language),
+        })?;
+        let resource = parse_resource(source)?;
+        Catalog::build(language, vec![Arc::new(resource)])
+    }
+
+    fn build(
+        language: LanguageIdentifier,
+        resources: Vec<Arc<FluentResource>>,
+    ) -> Result<Self, ParseError> {
+        let mut bundle = FluentBundle::new_concurrent(vec![language.clone()]);
+        // the isolation marks around placeables would end up in titles and the JSON-LD
+        bundle.set_use_isolating(false);
+        for (index, resource) in resources.iter().enumerate() {
+            if index == 0 {
+                bundle
+                    .add_resource(Arc::clone(resource))
+                    .map_err(|errors| bundle_error(resource.source(), &errors[0]))?;
+            } else {
+                bundle.add_resource_overriding(Arc::clone(resource));
+            }
+        }
+
+        let mut sources = HashMap::new();
+        let mut plain_terms = HashMap::new();
+        for resource in resources.iter() {
+            for entry in resource.entries() {
+                match entry {
+                    ast::Entry::Message(message) if message.value.is_some() => {
+                        sources.insert(message.id.name.to_string(), value_source(message));
+                    }
+                    ast::Entry::Term(term) => {
+                        let mut errors = Vec::new();
+                        let plain = bundle.format_pattern(&term.value, None, &mut errors);
+                        plain_terms.insert(term.id.name.to_string(), plain.into_owned());
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let mut catalog = Self {
+            language,
+            resources,
+            bundle,
+            sources,
+            plain_messages: HashMap::new(),
+            plain_terms,
+        };
+        let plain_messages = catalog
+            .sources
+            .keys()
+            .filter_map(|id| Some((id.clone(), catalog.format(id, None)?)))
+            .collect();
+        catalog.plain_messages = plain_messages;
+        Ok(catalog)
+    }
+
+    /// Copy of the catalog with the messages of `source` replacing its own, the terms stay so
+    /// that the replacements can refer to them.
+    pub fn with_overrides(&self, source: &str) -> Result<Catalog, ParseError> {
+        let overrides = parse_resource(source)?;
+        let term = overrides.entries().find_map(|entry| match entry {
+            ast::Entry::Term(term) => Some(term.id.name),
+            _ => None,
+        });
+        if let Some(term) = term {
+            return Err(ParseError {
+                line: 1,
+                message: format!("term `-{}` can not be overridden", term),
+            });
+        }
+
+        let mut resources = self.resources.clone();
+        resources.push(Arc::new(overrides));
+        Catalog::build(self.language.clone(), resources)
+    }
+
+    pub fn has_message(&self, id: &str) -> bool {
+        self.bundle.has_message(id)
+    }
+
+    /// Message without arguments, variables are left as `{$name}`.
+    pub fn message(&self, id: &str) -> Option<&str> {
+        self.plain_messages.get(id).map(String::as_str)
+    }
+
+    /// Fluent value of the message, for editing it.
+    pub fn source(&self, id: &str) -> Option<&str> {
+        self.sources.get(id).map(String::as_str)
+    }
+
+    /// Term by its id without the leading `-`.
+    pub fn term(&self, id: &str) -> Option<&str> {
+        self.plain_terms.get(id).map(String::as_str)
+    }
+
+    /// Message with its placeables filled in, plural variants are selected by the CLDR rules
+    /// of the language.
+    pub fn format(&self, id: &str, arguments: Option<&FluentArgs>) -> Option<String> {
+        let pattern = self.bundle.get_message(id)?.value()?;
+        let mut errors = Vec::new();
+        let formatted = self.bundle.format_pattern(pattern, arguments, &mut errors);
+        Some(formatted.into_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"
+# comment
+-brand = Rosemary
+title = { -brand } - gallery
+empty = { "" }
+size = { $width } cm
+long =
+    First line
+      indented
+    Last line
+photos =
+    { $count ->
+        [0] no photos
+        [one] { $count } photo
+       *[other] { $count } photos
+    }
+"#;
+
+    #[test]
+    fn test_parse_and_format() {
+        let catalog = Catalog::parse("en", SOURCE).unwrap();
+
+        assert_eq!(catalog.message("title"), Some("Rosemary - gallery"));
+        assert_eq!(catalog.message("empty"), Some(""));
+        assert_eq!(catalog.term("brand"), Some("Rosemary"));
+        assert_eq!(
+            catalog.message("long"),
+            Some("First line\n  indented\nLast line")
+        );
+        assert_eq!(
+            catalog
+                .format("size", Some(&FluentArgs::from_iter([("width", 60)])))
+                .as_deref(),
+            Some("60 cm")
+        );
+        assert_eq!(catalog.message("size"), Some("{$width} cm"));
+
+        let photos =
+            |count: i64| catalog.format("photos", Some(&FluentArgs::from_iter([("count", count)])));
+        assert_eq!(photos(0).as_deref(), Some("no photos"));
+        assert_eq!(photos(1).as_deref(), Some("1 photo"));
+        assert_eq!(photos(7).as_deref(), Some("7 photos"));
+        assert_eq!(catalog.format("missing", None), None);
+        assert_eq!(
+            catalog.source("long"),
+            Some("First line\n  indented\nLast line")
+        );
+        assert_eq!(catalog.source("title"), Some("{ -brand } - gallery"));
+        assert_eq!(
+            catalog.source("photos"),
+            Some("{ $count ->\n    [0] no photos\n    [one] { $count } photo\n   *[other] { $count } photos\n}")
+        );
+    }
+
+    #[test]
+    fn test_czech_plurals() {
+        let catalog = Catalog::parse(
+            "cs",
+            "photos = { $count ->\n    [one] fotka\n    [few] fotky\n   *[other] fotek\n}\n",
+        )
+        .unwrap();
+        let photos =
+            |count: i64| catalog.format("photos", Some(&FluentArgs::from_iter([("count", count)])));
+
+        assert_eq!(photos(1).as_deref(), Some("fotka"));
+        assert_eq!(photos(3).as_deref(), Some("fotky"));
+        assert_eq!(photos(5).as_deref(), Some("fotek"));
+        assert_eq!(photos(0).as_deref(), Some("fotek"));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let line = |source: &str| Catalog::parse("en", source).err().map(|error| error.line);
+
+        assert_eq!(line("ok = fine\nbroken line\n"), Some(2));
+        assert_eq!(line("a = one\na = two\n"), Some(2));
+        assert_eq!(line("ok = fine\na = { $count ->\n  [one] x\n}\n"), Some(4));
+        assert_eq!(line("  indented = x\n"), Some(1));
+    }
+
+    #[test]
+    fn test_with_overrides() {
+        let catalog = Catalog::parse("en", SOURCE).unwrap();
+        let source = message_source("title", "paintings by { -brand }\n  and photos");
+        let overridden = catalog.with_overrides(&source).unwrap();
+
+        assert_eq!(
+            overridden.message("title"),
+            Some("paintings by Rosemary\n  and photos")
+        );
+        assert_eq!(
+            overridden.source("title"),
+            Some("paintings by { -brand }\n  and photos")
+        );
+        assert_eq!(overridden.message("empty"), Some(""));
+        assert_eq!(catalog.message("title"), Some("Rosemary - gallery"));
+        assert!(catalog.with_overrides("-brand = Other\n").is_err());
+        assert!(catalog
+            .with_overrides(&message_source("title", ""))
+            .is_err());
+        assert!(catalog
+            .with_overrides(&message_source("title", "{ $count"))
+            .is_err());
+    }
+}
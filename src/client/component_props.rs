@@ -1,19 +1,52 @@
-use crate::client::translations::{get_translation, Language, TranslationKeys};
+use serde_json::Value;
+use std::borrow::Cow;
+
+use crate::client::routes::{alternates, site_url, Alternate, Route, LANGUAGE_SWITCH};
+use crate::client::structured_data::to_script;
+use crate::client::translations::{get_translation, languages, Language, TranslationKeys};
 use crate::config::load;
 
+/// Locale of the language as OpenGraph writes it
+fn og_locale(language: Language) -> String {
+    language.locale().replace('-', "_")
+}
+
+/// Link of the language switcher to the same page in another language
+#[derive(Debug)]
+pub struct LanguageLink {
+    pub code: &'static str,
+    pub label: &'static str,
+    pub href: String,
+}
+
 #[derive(Debug)]
 pub struct MetaProps<'a> {
-    pub description: &'a str,
-    pub keywords: &'a str,
+    pub description: Cow<'a, str>,
+    pub keywords: Cow<'a, str>,
     pub author: &'a str,
     pub robots: &'a str,
-    pub image: &'a str,
+    /// absolute URL of the picture shared with the page, empty when it has none
+    pub image: String,
+    pub image_alt: Cow<'a, str>,
+    /// language code of the page, "cs"
     pub locale: String,
+    pub og_locale: String,
+    pub og_locale_alternates: Vec<String>,
     pub favicon: &'a str,
-    pub summary_large_image: &'a str,
     pub twitter_handle: &'a str,
+    /// canonical URL of the page
     pub url: String,
+    pub alternates: Vec<Alternate>,
+    pub language_links: Vec<LanguageLink>,
+    /// Atom and RSS feeds of new paintings in the language of the page
+    pub feed_title: Cow<'a, str>,
+    pub atom_feed: String,
+    pub rss_feed: String,
+    pub site_url: String,
     pub static_base_url: String,
+    /// JSON-LD blocks of the page, already escaped for a script element
+    pub json_ld: Vec<String>,
+    language: Language,
 }
 
 impl<'a> MetaProps<'a> {
@@ -32,29 +65,88 @@ impl<'a> MetaProps<'a> {
             }
         };
 
-        Self {
-            description: "",
-            keywords: "Rosemary, Michaela, Halásová, malíř, painter, fotograf, photograph, abstract oil paintings, weddings",
+        let site_url = site_url();
+        let mut meta = Self {
+            description: Cow::Borrowed(""),
+            keywords: Cow::Borrowed("Rosemary, Michaela, Halásová, malíř, painter, fotograf, photograph, abstract oil paintings, weddings"),
             author: "Rosemary - Michaela Halásová",
             robots: "index, follow",
-            image: "",
+            image: String::new(),
+            image_alt: Cow::Borrowed(""),
             locale: lang.to_string(),
+            og_locale: og_locale(lang),
+            og_locale_alternates: languages()
+                .iter()
+                .filter(|other| other.to_str() != lang.to_str())
+                .map(|other| og_locale(*other))
+                .collect(),
             favicon: "",
-            summary_large_image: "",
             twitter_handle: "",
-            url: format!("www.rosemary-artist.com/{}", lang.to_str()),
+            url: String::new(),
+            alternates: Vec::new(),
+            language_links: Vec::new(),
+            feed_title: get_translation(TranslationKeys::FeedTitle, lang),
+            atom_feed: Route::AtomFeed.url(&site_url, lang),
+            rss_feed: Route::RssFeed.url(&site_url, lang),
+            site_url,
             static_base_url: static_base_url.clone(),
+            json_ld: Vec::new(),
+            language: lang,
+        };
+        meta.set_route(|_| Route::Index);
+        meta
+    }
+
+    /// Canonical URL and language alternates of the page, `route` gives the page in a language.
+    pub fn set_route(&mut self, route: impl Fn(Language) -> Route) {
+        self.url = route(self.language).url(&self.site_url, self.language);
+        self.language_links = languages()
+            .iter()
+            .filter(|other| **other != self.language)
+            .map(|other| LanguageLink {
+                code: other.to_str(),
+                label: other.native_name(),
+                href: format!(
+                    "/{}/{}?next={}",
+                    LANGUAGE_SWITCH,
+                    other.to_str(),
+                    route(*other).path(*other)
+                ),
+            })
+            .collect();
+        self.alternates = alternates(&self.site_url, route);
+    }
+
+    /// Shared picture from a path relative to the static file url.
+    pub fn set_image(&mut self, path: &str, alt: impl Into<Cow<'a, str>>) {
+        self.image = format!(
+            "{}/{}",
+            self.static_base_url.trim_end_matches('/'),
+            path.trim_start_matches('/')
+        );
+        self.image_alt = alt.into();
+    }
+
+    pub fn add_json_ld(&mut self, value: &Value) {
+        self.json_ld.push(to_script(value));
+    }
+
+    pub fn twitter_card(&self) -> &'static str {
+        if self.image.is_empty() {
+            "summary"
+        } else {
+            "summary_large_image"
         }
     }
 }
 
 #[derive(Debug)]
 pub struct NavbarProps<'a> {
-    pub home: &'a str,
-    pub gallery: &'a str,
-    pub photo: &'a str,
-    pub blog: &'a str,
-    pub contact: &'a str,
+    pub home: Cow<'a, str>,
+    pub gallery: Cow<'a, str>,
+    pub photo: Cow<'a, str>,
+    pub blog: Cow<'a, str>,
+    pub contact: Cow<'a, str>,
 }
 
 impl<'a> NavbarProps<'a> {
@@ -77,15 +169,15 @@ impl<'a> NavbarProps<'a> {
 
 #[derive(Debug)]
 pub struct FooterProps<'a> {
-    pub upcoming_events: &'a str,
-    pub photo_pricing: &'a str,
-    pub photo_reservation: &'a str,
-    pub home: &'a str,
-    pub gallery: &'a str,
-    pub blog: &'a str,
-    pub contact: &'a str,
-    pub faq: &'a str,
-    pub navigation: &'a str,
+    pub upcoming_events: Cow<'a, str>,
+    pub photo_pricing: Cow<'a, str>,
+    pub photo_reservation: Cow<'a, str>,
+    pub home: Cow<'a, str>,
+    pub gallery: Cow<'a, str>,
+    pub blog: Cow<'a, str>,
+    pub contact: Cow<'a, str>,
+    pub faq: Cow<'a, str>,
+    pub navigation: Cow<'a, str>,
 }
 
 impl<'a> FooterProps<'a> {
@@ -0,0 +1,214 @@
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Utc};
+
+use crate::client::translations::Language;
+
+/// Number, currency and date conventions of a language, read from the terms of its catalog
+pub struct LocaleFormat {
+    decimal: String,
+    group: String,
+    /// "12 500 Kč" when true, "€1,250" otherwise
+    currency_after: bool,
+    /// currency code and its symbol, other currencies are written as the code
+    symbols: Vec<(String, String)>,
+    /// medium date as a chrono format string
+    date: String,
+}
+
+/// Terms of a catalog `LocaleFormat::from_terms` reads
+pub const FORMAT_TERMS: [&str; 5] = [
+    "decimal-separator",
+    "group-separator",
+    "currency-position",
+    "currency-symbols",
+    "date-format",
+];
+
+impl LocaleFormat {
+    /// Parses the `FORMAT_TERMS` looked up by `term`, the error names the term that is missing
+    /// or invalid.
+    pub fn from_terms<'a>(
+        term: impl Fn(&str) -> Option<&'a str>,
+    ) -> Result<LocaleFormat, (&'static str, String)> {
+        let [decimal, group, position, symbols, date] = FORMAT_TERMS.map(|id| (id, term(id)));
+        let required = |(id, value): (&'static str, Option<&'a str>)| {
+            value
+                .filter(|value| !value.is_empty())
+                .ok_or((id, String::from("is missing")))
+        };
+
+        let currency_after = match required(position)? {
+            "before" => false,
+            "after" => true,
+            other => {
+                return Err((
+                    position.0,
+                    format!("has to be before or after, not {}", other),
+                ))
+            }
+        };
+        let currency_symbols = required(symbols)?
+            .split(',')
+            .map(
+                |pair| match pair.split_whitespace().collect::<Vec<&str>>()[..] {
+                    [code, symbol] => Ok((code.to_string(), symbol.to_string())),
+                    _ => Err((
+                        symbols.0,
+                        format!("`{}` is not a currency code and its symbol", pair.trim()),
+                    )),
+                },
+            )
+            .collect::<Result<Vec<(String, String)>, _>>()?;
+        let date_format = required(date)?;
+        if StrftimeItems::new(date_format).any(|item| item == Item::Error) {
+            return Err((date.0, format!("`{}` is not a date format", date_format)));
+        }
+
+        Ok(LocaleFormat {
+            decimal: required(decimal)?.to_string(),
+            group: required(group)?.to_string(),
+            currency_after,
+            symbols: currency_symbols,
+            date: date_format.to_string(),
+        })
+    }
+}
+
+fn group_digits(digits: &str, separator: &str) -> String {
+    let mut grouped = String::new();
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            grouped.push_str(separator);
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+/// Whole number with the group separator of the language, "12 500" or "12,500".
+pub fn format_integer(value: i64, language: Language) -> String {
+    let sign = if value < 0 { "-" } else { "" };
+    let digits = value.unsigned_abs().to_string();
+    format!(
+        "{}{}",
+        sign,
+        group_digits(&digits, &language.format().group)
+    )
+}
+
+/// Number rounded to `fraction_digits` places, "1,5" or "1.5".
+pub fn format_decimal(value: f64, fraction_digits: usize, language: Language) -> String {
+    let format = language.format();
+    let rounded = format!("{:.*}", fraction_digits, value.abs());
+    let (whole, fraction) = rounded.split_once('.').unwrap_or((&rounded, ""));
+    let sign = if value < 0.0 && rounded.chars().any(|c| c.is_ascii_digit() && c != '0') {
+        "-"
+    } else {
+        ""
+    };
+
+    let mut formatted = format!("{}{}", sign, group_digits(whole, &format.group));
+    if !fraction.is_empty() {
+        formatted.push_str(&format.decimal);
+        formatted.push_str(fraction);
+    }
+    formatted
+}
+
+/// Whole units in the customs of the language, "12 500 Kč", "€1,250" or "CZK 999".
+pub fn format_money(amount: i64, currency: &str, language: Language) -> String {
+    let format = language.format();
+    let number = group_digits(&amount.unsigned_abs().to_string(), &format.group);
+    let sign = if amount < 0 { "-" } else { "" };
+    let symbol = format
+        .symbols
+        .iter()
+        .find(|(code, _)| code == currency)
+        .map_or(currency, |(_, symbol)| symbol.as_str());
+
+    if format.currency_after {
+        format!("{}{}\u{a0}{}", sign, number, symbol)
+    } else if symbol.chars().all(|c| c.is_ascii_alphabetic()) {
+        // CLDR currency spacing, a code is never glued to the digits
+        format!("{}{}\u{a0}{}", sign, symbol, number)
+    } else {
+        format!("{}{}{}", sign, symbol, number)
+    }
+}
+
+/// Medium date of the language, "1. 3. 2024" or "Mar 1, 2024".
+pub fn format_date(date: &DateTime<Utc>, language: Language) -> String {
+    date.format(&language.format().date).to_string()
+}
+
+/// Askama filters of the frontend templates, a template module imports them with
+/// `use crate::client::formatting::filters;`.
+pub mod filters {
+    use chrono::{DateTime, Utc};
+    use std::fmt;
+
+    use crate::client::translations::Language;
+
+    /// `{{ count|number(page.language) }}`
+    pub fn number<T>(value: &T, language: &Language) -> askama::Result<String>
+    where
+        T: Copy + TryInto<i64>,
+    {
+        let value: i64 = (*value)
+            .try_into()
+            .map_err(|_| askama::Error::Fmt(fmt::Error))?;
+        Ok(super::format_integer(value, *language))
+    }
+
+    /// `{{ reserved_until|date(page.language) }}`
+    pub fn date(value: &DateTime<Utc>, language: &Language) -> askama::Result<String> {
+        Ok(super::format_date(value, *language))
+    }
+
+    /// `{{ "main.css"|asset }}`, the fingerprinted URL of a file in `static/assets`
+    pub fn asset(name: &str) -> askama::Result<String> {
+        Ok(crate::utils::assets::asset_url(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_format_numbers() {
+        assert_eq!(format_integer(1234567, Language::Cs), "1\u{a0}234\u{a0}567");
+        assert_eq!(format_integer(-1234, Language::En), "-1,234");
+        assert_eq!(format_integer(999, Language::En), "999");
+        assert_eq!(format_decimal(2.45, 1, Language::Cs), "2,5");
+        assert_eq!(format_decimal(12500.0, 2, Language::En), "12,500.00");
+        assert_eq!(format_decimal(-0.04, 1, Language::En), "0.0");
+        assert_eq!(format_decimal(3.0, 0, Language::Cs), "3");
+    }
+
+    #[test]
+    fn test_format_money() {
+        assert_eq!(
+            format_money(12500, "CZK", Language::Cs),
+            "12\u{a0}500\u{a0}Kč"
+        );
+        assert_eq!(format_money(1250, "EUR", Language::En), "€1,250");
+        assert_eq!(format_money(999, "CZK", Language::En), "CZK\u{a0}999");
+        assert_eq!(
+            format_money(-1000000, "EUR", Language::Cs),
+            "-1\u{a0}000\u{a0}000\u{a0}€"
+        );
+        assert_eq!(
+            format_money(i64::MIN, "USD", Language::En),
+            "-$9,223,372,036,854,775,808"
+        );
+    }
+
+    #[test]
+    fn test_format_date() {
+        let date = Utc.with_ymd_and_hms(2024, 3, 1, 18, 30, 0).unwrap();
+        assert_eq!(format_date(&date, Language::Cs), "1. 3. 2024");
+        assert_eq!(format_date(&date, Language::En), "Mar 1, 2024");
+    }
+}
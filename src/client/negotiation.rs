@@ -0,0 +1,145 @@
+use warp::http::{header, Uri};
+use warp::{Filter, Rejection, Reply};
+
+use crate::client::translations::{default_language, Language};
+
+/// Cookie the language switcher stores the chosen language in
+pub const LANGUAGE_COOKIE: &str = "lang";
+/// A year
+const LANGUAGE_COOKIE_MAX_AGE: u32 = 31_536_000;
+
+/// Best supported language of an `Accept-Language` header, `None` when it names none of them.
+pub fn from_accept_language(header: &str) -> Option<Language> {
+    let mut ranges: Vec<(f32, usize, Language)> = header
+        .split(',')
+        .enumerate()
+        .filter_map(|(position, range)| {
+            let mut parts = range.split(';').map(str::trim);
+            let tag = parts.next()?;
+            let quality = parts
+                .find_map(|param| param.strip_prefix("q="))
+                .map_or(Some(1.0), |value| value.trim().parse::<f32>().ok())?;
+            let primary = tag.split('-').next()?;
+            let language = if primary == "*" {
+                default_language()
+            } else {
+                Language::from_code(primary)?
+            };
+            (quality > 0.0).then_some((quality, position, language))
+        })
+        .collect();
+
+    ranges.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)));
+    ranges.first().map(|(_, _, language)| *language)
+}
+
+/// The stored choice first, then the browser preference, then the default language.
+pub fn negotiate(cookie: Option<&str>, accept_language: Option<&str>) -> Language {
+    cookie
+        .and_then(Language::from_code)
+        .or_else(|| accept_language.and_then(from_accept_language))
+        .unwrap_or(default_language())
+}
+
+/// Language of a request to a route without a language prefix
+pub fn preferred_language() -> impl Filter<Extract = (Language,), Error = Rejection> + Clone {
+    warp::cookie::optional::<String>(LANGUAGE_COOKIE)
+        .and(warp::header::optional::<String>("accept-language"))
+        .map(|cookie: Option<String>, accept_language: Option<String>| {
+            negotiate(cookie.as_deref(), accept_language.as_deref())
+        })
+}
+
+/// `Set-Cookie` value that remembers `language`.
+pub fn language_cookie(language: Language) -> String {
+    format!(
+        "{}={}; Path=/; Max-Age={}; SameSite=Lax",
+        LANGUAGE_COOKIE,
+        language.to_str(),
+        LANGUAGE_COOKIE_MAX_AGE
+    )
+}
+
+/// 302 to the page in the negotiated language, it differs between visitors so it must not be
+/// cached as permanent.
+pub fn negotiated_redirect(path: &str) -> Result<warp::reply::Response, Rejection> {
+    let uri = Uri::try_from(path).map_err(|_| warp::reject::not_found())?;
+    let redirect = warp::redirect::found(uri);
+    Ok(warp::reply::with_header(redirect, header::VARY, "Accept-Language, Cookie").into_response())
+}
+
+/// Local path to continue to after switching the language, anything else goes to `fallback`.
+pub fn safe_next(next: Option<&str>, fallback: String) -> String {
+    match next {
+        Some(path) if path.starts_with('/') && !path.starts_with("//") && !path.contains('\\') => {
+            path.to_string()
+        }
+        _ => fallback,
+    }
+}
+
+/// Stores the language and sends the visitor on to `path`.
+pub fn switch_language(language: Language, path: &str) -> Result<warp::reply::Response, Rejection> {
+    let uri = Uri::try_from(path).map_err(|_| warp::reject::not_found())?;
+    let redirect = warp::redirect::see_other(uri);
+    Ok(
+        warp::reply::with_header(redirect, header::SET_COOKIE, language_cookie(language))
+            .into_response(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_accept_language() {
+        assert_eq!(
+            from_accept_language("en-US,en;q=0.9,cs;q=0.8"),
+            Some(Language::En)
+        );
+        assert_eq!(
+            from_accept_language("de-DE, en;q=0.5, cs;q=0.7"),
+            Some(Language::Cs)
+        );
+        assert_eq!(from_accept_language("en;q=0, cs-CZ"), Some(Language::Cs));
+        assert_eq!(from_accept_language("de, fr;q=0.8"), None);
+        assert_eq!(from_accept_language("*"), Some(Language::Cs));
+        assert_eq!(from_accept_language(""), None);
+    }
+
+    #[test]
+    fn test_negotiate() {
+        assert_eq!(negotiate(Some("en"), Some("cs")), Language::En);
+        assert_eq!(negotiate(Some("xx"), Some("en-GB")), Language::En);
+        assert_eq!(negotiate(None, Some("de")), Language::Cs);
+        assert_eq!(negotiate(None, None), Language::Cs);
+    }
+
+    #[test]
+    fn test_switch_language() {
+        let response = switch_language(Language::En, "/en/gallery").unwrap();
+        assert_eq!(response.status(), warp::http::StatusCode::SEE_OTHER);
+        assert_eq!(response.headers()[header::LOCATION], "/en/gallery");
+        assert_eq!(
+            response.headers()[header::SET_COOKIE],
+            "lang=en; Path=/; Max-Age=31536000; SameSite=Lax"
+        );
+    }
+
+    #[test]
+    fn test_safe_next() {
+        let fallback = || String::from("/en");
+        assert_eq!(
+            safe_next(Some("/en/gallery/winter-morning"), fallback()),
+            "/en/gallery/winter-morning"
+        );
+        assert_eq!(safe_next(Some("//evil.example.com"), fallback()), "/en");
+        assert_eq!(
+            safe_next(Some("https://evil.example.com"), fallback()),
+            "/en"
+        );
+        assert_eq!(safe_next(Some("/\\evil.example.com"), fallback()), "/en");
+        assert_eq!(safe_next(None, fallback()), "/en");
+    }
+}
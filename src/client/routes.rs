@@ -0,0 +1,132 @@
+use crate::client::translations::{default_language, languages, Language};
+use crate::config::load;
+
+/// Path segments after the language, the same in every language
+pub const GALLERY: &str = "gallery";
+pub const SERIES: &str = "series";
+pub const CONTACT: &str = "contact";
+pub const ATOM_FEED: &str = "feed.xml";
+pub const RSS_FEED: &str = "rss.xml";
+/// `/language/:code?next=` stores the language and continues to `next`
+pub const LANGUAGE_SWITCH: &str = "language";
+
+/// Page of the frontend, `requests::router` serves each of them in every one of `languages()`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Route {
+    Index,
+    Gallery,
+    Contact,
+    /// painting detail by its slug in the language, or by UUID until it has one
+    Painting(String),
+    /// series by its slug, the same in every language
+    Series(String),
+    /// newest paintings in the language
+    AtomFeed,
+    RssFeed,
+}
+
+impl Route {
+    /// Pages without parameters
+    pub const STATIC: [Route; 3] = [Route::Index, Route::Gallery, Route::Contact];
+
+    /// Path of the page in `language`, "/en/gallery/winter-morning".
+    pub fn path(&self, language: Language) -> String {
+        let lang = language.to_str();
+        match self {
+            Route::Index => format!("/{}", lang),
+            Route::Gallery => format!("/{}/{}", lang, GALLERY),
+            Route::Contact => format!("/{}/{}", lang, CONTACT),
+            Route::Painting(slug) => format!("/{}/{}/{}", lang, GALLERY, slug),
+            Route::Series(slug) => format!("/{}/{}/{}", lang, SERIES, slug),
+            Route::AtomFeed => format!("/{}/{}", lang, ATOM_FEED),
+            Route::RssFeed => format!("/{}/{}", lang, RSS_FEED),
+        }
+    }
+
+    /// Absolute URL of the page under `site_url`, which has no trailing slash.
+    pub fn url(&self, site_url: &str, language: Language) -> String {
+        format!("{}{}", site_url, self.path(language))
+    }
+}
+
+/// `<link rel="alternate">` of a page in one language
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alternate {
+    pub hreflang: String,
+    pub href: String,
+}
+
+/// The page in every one of `languages()` and the default one as `x-default`, `route`
+/// gives the page in a language.
+pub fn alternates(site_url: &str, route: impl Fn(Language) -> Route) -> Vec<Alternate> {
+    let mut alternates: Vec<Alternate> = languages()
+        .iter()
+        .map(|language| Alternate {
+            hreflang: language.to_string(),
+            href: route(*language).url(site_url, *language),
+        })
+        .collect();
+    alternates.push(Alternate {
+        hreflang: String::from("x-default"),
+        href: route(default_language()).url(site_url, default_language()),
+    });
+    alternates
+}
+
+/// Public URL of the site from the config, without a trailing slash
+pub fn site_url() -> String {
+    load::get_sync::<String>(load::ConfigField::SiteUrl).unwrap_or_else(|error| {
+        error!(target: "template", "routes:site_url - failed to get site url {}", error);
+        String::new()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route_url() {
+        let site = "https://www.rosemary-artist.com";
+        assert_eq!(Route::Index.url(site, Language::Cs), format!("{}/cs", site));
+        assert_eq!(Route::Gallery.path(Language::En), "/en/gallery");
+        assert_eq!(
+            Route::Painting(String::from("zimni-rano")).path(Language::Cs),
+            "/cs/gallery/zimni-rano"
+        );
+        assert_eq!(
+            Route::Series(String::from("winter")).path(Language::En),
+            "/en/series/winter"
+        );
+    }
+
+    #[test]
+    fn test_alternates() {
+        let slug = |language: Language| match language {
+            Language::Cs => Route::Painting(String::from("zimni-rano")),
+            _ => Route::Painting(String::from("winter-morning")),
+        };
+        let hrefs: Vec<(String, String)> = alternates("https://example.com", slug)
+            .into_iter()
+            .map(|alternate| (alternate.hreflang, alternate.href))
+            .collect();
+
+        assert_eq!(
+            hrefs,
+            vec![
+                (
+                    String::from("cs"),
+                    String::from("https://example.com/cs/gallery/zimni-rano")
+                ),
+                (
+                    String::from("en"),
+                    String::from("https://example.com/en/gallery/winter-morning")
+                ),
+                (
+                    String::from("x-default"),
+                    String::from("https://example.com/cs/gallery/zimni-rano")
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,280 @@
+use serde_json::{json, Map, Value};
+
+use crate::client::routes::Route;
+use crate::client::translations::{get_translation, languages, Language, TranslationKeys};
+use crate::database::models::painting::{Availability, Painting};
+
+const SCHEMA_CONTEXT: &str = "https://schema.org";
+
+/// Public profile of the artist, the same as the footer shows
+pub const ARTIST_NAME: &str = "Michaela Halásová";
+pub const ARTIST_ALTERNATE_NAME: &str = "Rosemary";
+pub const ARTIST_EMAIL: &str = "rosemaryphotography@seznam.cz";
+pub const ARTIST_PROFILES: [&str; 2] = [
+    "https://www.instagram.com/rozmarynamaluje/",
+    "https://www.facebook.com/p/Rozmar%C3%BDna-maluje-100077482535221/",
+];
+
+/// `@id` the artworks refer to the artist by
+fn artist_id(site_url: &str) -> String {
+    format!("{}/#artist", site_url)
+}
+
+fn availability_url(availability: Availability) -> Option<&'static str> {
+    match availability {
+        Availability::Available => Some("https://schema.org/InStock"),
+        Availability::Reserved => Some("https://schema.org/Reserved"),
+        Availability::Sold => Some("https://schema.org/SoldOut"),
+        Availability::NotForSale => None,
+    }
+}
+
+fn centimeters(value: i64) -> Value {
+    json!({
+        "@type": "QuantitativeValue",
+        "value": value,
+        "unitCode": "CMT",
+    })
+}
+
+/// Person with a ContactPoint, for the index and contact pages.
+pub fn artist(site_url: &str, static_base_url: &str, language: Language) -> Value {
+    json!({
+        "@context": SCHEMA_CONTEXT,
+        "@type": "Person",
+        "@id": artist_id(site_url),
+        "name": ARTIST_NAME,
+        "alternateName": ARTIST_ALTERNATE_NAME,
+        "jobTitle": get_translation(TranslationKeys::ArtistJobTitle, language),
+        "url": Route::Index.url(site_url, language),
+        "image": format!("{}/images/author_home", static_base_url.trim_end_matches('/')),
+        "sameAs": ARTIST_PROFILES,
+        "contactPoint": {
+            "@type": "ContactPoint",
+            "contactType": "sales",
+            "email": ARTIST_EMAIL,
+            "url": Route::Contact.url(site_url, language),
+            "availableLanguage": languages()
+                .iter()
+                .map(|language| language.to_str())
+                .collect::<Vec<_>>(),
+        },
+    })
+}
+
+/// VisualArtwork of the painting detail page at `url`, with an Offer unless it is not for sale.
+/// `images` are absolute URLs, the preview first.
+pub fn artwork(
+    painting: &Painting,
+    language: Language,
+    site_url: &str,
+    url: &str,
+    images: &[String],
+) -> Value {
+    let mut artwork = Map::new();
+    artwork.insert(String::from("@context"), json!(SCHEMA_CONTEXT));
+    artwork.insert(String::from("@type"), json!("VisualArtwork"));
+    artwork.insert(String::from("name"), json!(painting.get_title(language)));
+    artwork.insert(
+        String::from("description"),
+        json!(painting.get_description(language)),
+    );
+    artwork.insert(String::from("url"), json!(url));
+    artwork.insert(String::from("inLanguage"), json!(language.to_str()));
+    artwork.insert(String::from("artform"), json!("Painting"));
+    if !images.is_empty() {
+        artwork.insert(String::from("image"), json!(images));
+    }
+    if let Some(technique) = painting.attributes.get_technique(language) {
+        artwork.insert(String::from("artMedium"), json!(technique));
+    }
+    if let Some(support) = painting.attributes.get_support(language) {
+        artwork.insert(String::from("artworkSurface"), json!(support));
+    }
+    artwork.insert(String::from("width"), centimeters(painting.width));
+    artwork.insert(String::from("height"), centimeters(painting.height));
+    if let Some(depth) = painting.attributes.depth {
+        artwork.insert(String::from("depth"), centimeters(depth));
+    }
+    if let Some(year) = painting.attributes.year_created {
+        artwork.insert(String::from("dateCreated"), json!(year.to_string()));
+    }
+    artwork.insert(
+        String::from("creator"),
+        json!({
+            "@type": "Person",
+            "@id": artist_id(site_url),
+            "name": ARTIST_NAME,
+        }),
+    );
+    if let Some(availability) = availability_url(painting.availability) {
+        artwork.insert(
+            String::from("offers"),
+            json!({
+                "@type": "Offer",
+                "price": painting.price,
+                "priceCurrency": painting.currency,
+                "availability": availability,
+                "url": url,
+            }),
+        );
+    }
+    Value::Object(artwork)
+}
+
+/// JSON for a `<script type="application/ld+json">` block, `<` is escaped so a value can not
+/// close the script.
+pub fn to_script(value: &Value) -> String {
+    value.to_string().replace('<', "\\u003c")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use sqlx::types::Json;
+    use uuid::Uuid;
+
+    use crate::database::models::artwork_attributes::ArtworkAttributes;
+    use crate::database::models::generics::Translation;
+    use crate::database::models::image::PaintingImage;
+
+    fn translation(cs: &str, en: &str) -> Translation {
+        Translation {
+            cs: String::from(cs),
+            en: String::from(en),
+        }
+    }
+
+    fn painting(availability: Availability) -> Painting {
+        let id = Uuid::nil();
+        Painting {
+            id,
+            created: Utc::now(),
+            deleted: None,
+            price: 12500,
+            currency: String::from("CZK"),
+            painting_title: Some(translation("Zimní ráno", "Winter morning")),
+            painting_description: Some(translation("Mráz na okně", "Frost on the window")),
+            data: None,
+            width: 60,
+            height: 40,
+            availability,
+            reserved_until: None,
+            sold_date: None,
+            attributes: ArtworkAttributes {
+                technique: Some(translation("olej", "oil")),
+                support: Some(translation("plátno", "canvas")),
+                year_created: Some(2023),
+                ..ArtworkAttributes::default()
+            },
+            tags: Vec::new(),
+            preview: Json(PaintingImage {
+                id,
+                preview: true,
+                alt: None,
+                title: None,
+                painting_id: id,
+                status: None,
+                file_location: None,
+                urls: Vec::new(),
+            }),
+            sort_value: None,
+        }
+    }
+
+    #[test]
+    fn test_artwork() {
+        let url = "https://example.com/en/gallery/winter-morning";
+        let images = vec![String::from("https://static.example.com/a_1900.jpeg")];
+        let value = artwork(
+            &painting(Availability::Available),
+            Language::En,
+            "https://example.com",
+            url,
+            &images,
+        );
+
+        assert_eq!(
+            value,
+            json!({
+                "@context": "https://schema.org",
+                "@type": "VisualArtwork",
+                "name": "Winter morning",
+                "description": "Frost on the window",
+                "url": url,
+                "inLanguage": "en",
+                "artform": "Painting",
+                "image": ["https://static.example.com/a_1900.jpeg"],
+                "artMedium": "oil",
+                "artworkSurface": "canvas",
+                "width": { "@type": "QuantitativeValue", "value": 60, "unitCode": "CMT" },
+                "height": { "@type": "QuantitativeValue", "value": 40, "unitCode": "CMT" },
+                "dateCreated": "2023",
+                "creator": {
+                    "@type": "Person",
+                    "@id": "https://example.com/#artist",
+                    "name": "Michaela Halásová",
+                },
+                "offers": {
+                    "@type": "Offer",
+                    "price": 12500,
+                    "priceCurrency": "CZK",
+                    "availability": "https://schema.org/InStock",
+                    "url": url,
+                },
+            })
+        );
+
+        let not_for_sale = artwork(
+            &painting(Availability::NotForSale),
+            Language::Cs,
+            "https://example.com",
+            url,
+            &[],
+        );
+        assert!(not_for_sale.get("offers").is_none());
+        assert!(not_for_sale.get("image").is_none());
+        assert_eq!(not_for_sale["name"], "Zimní ráno");
+    }
+
+    #[test]
+    fn test_artist() {
+        assert_eq!(
+            artist(
+                "https://example.com",
+                "https://static.example.com/",
+                Language::Cs
+            ),
+            json!({
+                "@context": "https://schema.org",
+                "@type": "Person",
+                "@id": "https://example.com/#artist",
+                "name": "Michaela Halásová",
+                "alternateName": "Rosemary",
+                "jobTitle": "Malířka a fotografka",
+                "url": "https://example.com/cs",
+                "image": "https://static.example.com/images/author_home",
+                "sameAs": [
+                    "https://www.instagram.com/rozmarynamaluje/",
+                    "https://www.facebook.com/p/Rozmar%C3%BDna-maluje-100077482535221/",
+                ],
+                "contactPoint": {
+                    "@type": "ContactPoint",
+                    "contactType": "sales",
+                    "email": "rosemaryphotography@seznam.cz",
+                    "url": "https://example.com/cs/contact",
+                    "availableLanguage": ["cs", "en"],
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_to_script() {
+        let value = json!({ "description": "</script><script>alert(1)</script>" });
+        let script = to_script(&value);
+        assert!(!script.contains("</"));
+        assert_eq!(serde_json::from_str::<Value>(&script).unwrap(), value);
+    }
+}
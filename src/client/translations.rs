@@ -1,174 +1,561 @@
-use crate::client::localization::PAGE_TRANSLATIONS;
+use fluent_bundle::FluentArgs;
+use lazy_static::lazy_static;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{OnceLock, RwLock};
 
-pub struct PageTranslation {
-    pub en: &'static str,
-    pub cs: &'static str,
-}
+use crate::client::catalog::{message_source, Catalog, ParseError};
+use crate::client::formatting::{LocaleFormat, FORMAT_TERMS};
+use crate::database::connection::get_client;
+use crate::database::models::translation_override::TranslationOverride;
+use crate::utils::http_cache::content_changed;
 
-#[derive(Copy, Debug, Clone)]
-pub enum Language {
-    En,
-    Cs,
-}
+include!(concat!(env!("OUT_DIR"), "/locales.rs"));
+
+/// Language of the site, one for every `locales/<code>.ftl`
+#[derive(Copy, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Language(&'static str);
 
+#[allow(non_upper_case_globals)]
 impl Language {
-    pub fn to_str(&self) -> &str {
-        match self {
-            Language::Cs => "cs",
-            Language::En => "en",
+    /// Served when negotiation finds no other language
+    pub const Cs: Language = Language("cs");
+    /// Stored content falls back to it in languages it is not written in
+    pub const En: Language = Language("en");
+
+    pub fn from_code(code: &str) -> Option<Language> {
+        let code = code.trim().to_lowercase();
+        languages()
+            .iter()
+            .copied()
+            .find(|language| language.0 == code)
+    }
+
+    pub fn to_str(&self) -> &'static str {
+        self.0
+    }
+
+    /// Language the stored content and slugs are read in, paintings are described in Czech and
+    /// English only so every other language shows the English text.
+    pub fn content(&self) -> Language {
+        if *self == Language::Cs {
+            Language::Cs
+        } else {
+            Language::En
         }
     }
 
+    /// Name of the language in itself, for the language switcher
+    pub fn native_name(&self) -> &'static str {
+        catalog(*self).term("language-name").unwrap_or(self.0)
+    }
+
+    /// BCP 47 locale of the language, "cs-CZ"
+    pub fn locale(&self) -> &'static str {
+        catalog(*self).term("locale").unwrap_or(self.0)
+    }
+
+    /// Number, currency and date conventions declared by the terms of the catalog
+    pub fn format(&self) -> &'static LocaleFormat {
+        &catalogs().formats[self.0]
+    }
+
     pub fn to_string(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+impl FromStr for Language {
+    type Err = ();
+
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        Language::from_code(code).ok_or(())
+    }
+}
+
+/// Declares the keys together with their message ids in the Fluent files.
+macro_rules! translation_keys {
+    ($($key:ident => $id:literal,)*) => {
+        #[derive(Copy, Debug, Clone)]
+        pub enum TranslationKeys {
+            $($key,)*
+        }
+
+        impl TranslationKeys {
+            pub const ALL: &'static [TranslationKeys] = &[$(TranslationKeys::$key,)*];
+
+            pub fn id(self) -> &'static str {
+                match self {
+                    $(TranslationKeys::$key => $id,)*
+                }
+            }
+
+            pub fn from_id(id: &str) -> Option<TranslationKeys> {
+                TranslationKeys::ALL.iter().copied().find(|key| key.id() == id)
+            }
+        }
+    };
+}
+
+translation_keys! {
+    AllPaintings => "all-paintings",
+    Any => "any",
+    ArtistJobTitle => "artist-job-title",
+    Ascending => "ascending",
+    Availability => "availability",
+    Available => "available",
+    BackToContact => "back-to-contact",
+    Blog => "blog",
+    Buy => "buy",
+    BuyPainting => "buy-painting",
+    Close => "close",
+    Contact => "contact",
+    ContactInvalidMessage => "contact-invalid-message",
+    ContactThankYouMessage => "contact-thank-you-message",
+    ContactThankYouTitle => "contact-thank-you-title",
+    Created => "created",
+    Descending => "descending",
+    Email => "email",
+    FAQ => "faq",
+    FeedDescription => "feed-description",
+    FeedTitle => "feed-title",
+    Filter => "filter",
+    Framed => "framed",
+    Framing => "framing",
+    FullName => "full-name",
+    Gallery => "gallery",
+    GalleryTitle => "gallery-title",
+    General => "general",
+    Height => "height",
+    Home => "home",
+    IndexDescription => "index-description",
+    IndexHeroAlt => "index-hero-alt",
+    IndexMetaDescription => "index-meta-description",
+    IndexMetaImageSummary => "index-meta-image-summary",
+    IndexMetaKeywords => "index-meta-keywords",
+    IndexPicturAlt => "index-picture-alt",
+    IndexTitle => "index-title",
+    InquiryFailed => "inquiry-failed",
+    InquirySent => "inquiry-sent",
+    Landscape => "landscape",
+    Message => "message",
+    Name => "name",
+    Navigation => "navigation",
+    Next => "next",
+    NoPaintings => "no-paintings",
+    NotForSale => "not-for-sale",
+    Orientation => "orientation",
+    Painting => "painting",
+    Phone => "phone",
+    Photo => "photo",
+    PhotoCount => "photo-count",
+    Photography => "photography",
+    PhotoPricing => "photo-pricing",
+    PhotoReservation => "photo-reservation",
+    Portrait => "portrait",
+    Prev => "prev",
+    Price => "price",
+    RangeFrom => "range-from",
+    RangeTo => "range-to",
+    RelatedPaintings => "related-paintings",
+    Relevance => "relevance",
+    Reserved => "reserved",
+    ReservedUntil => "reserved-until",
+    ResetFilters => "reset-filters",
+    Search => "search",
+    Send => "send",
+    Series => "series",
+    Sold => "sold",
+    Square => "square",
+    Subject => "subject",
+    Tag => "tag",
+    Title => "title",
+    Unframed => "unframed",
+    UpcomingEvents => "upcoming-events",
+    Weight => "weight",
+    Width => "width",
+    YearCreated => "year-created",
+}
+
+/// Terms every catalog has to define besides the `FORMAT_TERMS`
+const REQUIRED_TERMS: [&str; 2] = ["language-name", "locale"];
+
+#[derive(Debug)]
+pub enum CatalogError {
+    Parse {
+        language: String,
+        error: ParseError,
+    },
+    MissingMessages {
+        language: String,
+        ids: Vec<String>,
+    },
+    InvalidTerm {
+        language: String,
+        id: &'static str,
+        message: String,
+    },
+    MissingLanguage(String),
+}
+
+impl fmt::Display for CatalogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Language::Cs => String::from("cs"),
-            Language::En => String::from("en"),
+            CatalogError::Parse { language, error } => {
+                write!(f, "locales/{}.ftl {}", language, error)
+            }
+            CatalogError::MissingMessages { language, ids } => {
+                write!(f, "locales/{}.ftl is missing {}", language, ids.join(", "))
+            }
+            CatalogError::InvalidTerm {
+                language,
+                id,
+                message,
+            } => {
+                write!(f, "locales/{}.ftl term -{} {}", language, id, message)
+            }
+            CatalogError::MissingLanguage(language) => {
+                write!(f, "locales/{}.ftl does not exist", language)
+            }
         }
     }
 }
 
-#[derive(Copy, Debug, Clone)]
-pub enum TranslationKeys {
-    Ascending,
-    Blog,
-    Buy,
-    Created,
-    Contact,
-    Close,
-    Descending,
-    Email,
-    FAQ,
-    Filter,
-    FullName,
-    Gallery,
-    GalleryTitle,
-    General,
-    Height,
-    Home,
-    IndexTitle,
-    IndexHeroAlt,
-    IndexPicturAlt,
-    IndexDescription,
-    IndexMetaDescription,
-    IndexMetaKeywords,
-    IndexMetaImageSummary,
-    Message,
-    Name,
-    Navigation,
-    Next,
-    Painting,
-    Phone,
-    Photo,
-    Photography,
-    PhotoPricing,
-    PhotoReservation,
-    Prev,
-    Price,
-    Send,
-    Sold,
-    Subject,
-    Title,
-    UpcomingEvents,
-    Width,
-}
-
-pub struct PageTranslations {
-    pub ascending: PageTranslation,
-    pub blog: PageTranslation,
-    pub buy: PageTranslation,
-    pub created: PageTranslation,
-    pub contact: PageTranslation,
-    pub close: PageTranslation,
-    pub descending: PageTranslation,
-    pub email: PageTranslation,
-    pub faq: PageTranslation,
-    pub filter: PageTranslation,
-    pub full_name: PageTranslation,
-    pub gallery: PageTranslation,
-    pub gallery_title: PageTranslation,
-    pub general: PageTranslation,
-    pub height: PageTranslation,
-    pub home: PageTranslation,
-    pub index_title: PageTranslation,
-    pub index_hero_alt: PageTranslation,
-    pub index_picture_alt: PageTranslation,
-    pub index_description: PageTranslation,
-    pub index_meta_description: PageTranslation,
-    pub index_meta_keywords: PageTranslation,
-    pub index_meta_image_summary: PageTranslation,
-    pub message: PageTranslation,
-    pub name: PageTranslation,
-    pub navigation: PageTranslation,
-    pub next: PageTranslation,
-    pub painting: PageTranslation,
-    pub phone: PageTranslation,
-    pub photo: PageTranslation,
-    pub photography: PageTranslation,
-    pub photo_pricing: PageTranslation,
-    pub photo_reservation: PageTranslation,
-    pub prev: PageTranslation,
-    pub price: PageTranslation,
-    pub send: PageTranslation,
-    pub sold: PageTranslation,
-    pub subject: PageTranslation,
-    pub title: PageTranslation,
-    pub upcoming_events: PageTranslation,
-    pub width: PageTranslation,
-}
-
-impl PageTranslations {
-    pub fn get_translation(&self, key: TranslationKeys, lang: Language) -> &'static str {
-        let translation = match key {
-            TranslationKeys::Ascending => &self.ascending,
-            TranslationKeys::Blog => &self.blog,
-            TranslationKeys::Buy => &self.buy,
-            TranslationKeys::Created => &self.created,
-            TranslationKeys::Contact => &self.contact,
-            TranslationKeys::Close => &self.close,
-            TranslationKeys::Descending => &self.descending,
-            TranslationKeys::Email => &self.email,
-            TranslationKeys::FAQ => &self.faq,
-            TranslationKeys::Filter => &self.filter,
-            TranslationKeys::FullName => &self.full_name,
-            TranslationKeys::Gallery => &self.gallery,
-            TranslationKeys::GalleryTitle => &self.gallery_title,
-            TranslationKeys::General => &self.general,
-            TranslationKeys::Height => &self.height,
-            TranslationKeys::Home => &self.home,
-            TranslationKeys::IndexTitle => &self.index_title,
-            TranslationKeys::IndexHeroAlt => &self.index_hero_alt,
-            TranslationKeys::IndexPicturAlt => &self.index_picture_alt,
-            TranslationKeys::IndexDescription => &self.index_description,
-            TranslationKeys::IndexMetaDescription => &self.index_meta_description,
-            TranslationKeys::IndexMetaKeywords => &self.index_meta_keywords,
-            TranslationKeys::IndexMetaImageSummary => &self.index_meta_image_summary,
-            TranslationKeys::Navigation => &self.navigation,
-            TranslationKeys::Next => &self.next,
-            TranslationKeys::Message => &self.message,
-            TranslationKeys::Name => &self.name,
-            TranslationKeys::Painting => &self.painting,
-            TranslationKeys::Phone => &self.phone,
-            TranslationKeys::Photo => &self.photo,
-            TranslationKeys::Photography => &self.photography,
-            TranslationKeys::PhotoPricing => &self.photo_pricing,
-            TranslationKeys::PhotoReservation => &self.photo_reservation,
-            TranslationKeys::Prev => &self.prev,
-            TranslationKeys::Price => &self.price,
-            TranslationKeys::Send => &self.send,
-            TranslationKeys::Sold => &self.sold,
-            TranslationKeys::Subject => &self.subject,
-            TranslationKeys::Title => &self.title,
-            TranslationKeys::UpcomingEvents => &self.upcoming_events,
-            TranslationKeys::Width => &self.width,
+impl std::error::Error for CatalogError {}
+
+struct Catalogs {
+    /// the default language first, the rest by code
+    languages: Vec<Language>,
+    catalogs: HashMap<&'static str, Catalog>,
+    formats: HashMap<&'static str, LocaleFormat>,
+}
+
+static CATALOGS: OnceLock<Catalogs> = OnceLock::new();
+
+/// Messages edited in the administration
+#[derive(Default)]
+struct Overrides {
+    /// compiled catalog with the overrides merged in, for languages that have any
+    catalogs: HashMap<&'static str, Catalog>,
+    /// overridden messages formatted without arguments, by language code and message id
+    texts: HashMap<(&'static str, &'static str), String>,
+}
+
+lazy_static! {
+    static ref OVERRIDES: RwLock<Overrides> = RwLock::new(Overrides::default());
+}
+
+/// Parses the embedded catalogs and checks that every language has every message and valid
+/// formatting terms.
+fn load_catalogs(sources: &[(&'static str, &str)]) -> Result<Catalogs, CatalogError> {
+    let mut catalogs = HashMap::new();
+    let mut formats = HashMap::new();
+    for (code, source) in sources {
+        let catalog = Catalog::parse(code, source).map_err(|error| CatalogError::Parse {
+            language: code.to_string(),
+            error,
+        })?;
+
+        let mut missing: Vec<String> = TranslationKeys::ALL
+            .iter()
+            .map(|key| key.id())
+            .filter(|id| !catalog.has_message(id))
+            .map(String::from)
+            .collect();
+        missing.extend(
+            REQUIRED_TERMS
+                .iter()
+                .chain(FORMAT_TERMS.iter())
+                .filter(|id| catalog.term(id).is_none())
+                .map(|id| format!("-{}", id)),
+        );
+        if !missing.is_empty() {
+            return Err(CatalogError::MissingMessages {
+                language: code.to_string(),
+                ids: missing,
+            });
+        }
+
+        let format = LocaleFormat::from_terms(|id| catalog.term(id)).map_err(|(id, message)| {
+            CatalogError::InvalidTerm {
+                language: code.to_string(),
+                id,
+                message,
+            }
+        })?;
+        formats.insert(*code, format);
+        catalogs.insert(*code, catalog);
+    }
+
+    for required in [Language::Cs, Language::En] {
+        if !catalogs.contains_key(required.0) {
+            return Err(CatalogError::MissingLanguage(required.0.to_string()));
+        }
+    }
+
+    let mut languages: Vec<Language> = catalogs.keys().map(|code| Language(code)).collect();
+    languages.sort_by_key(|language| (*language != Language::Cs, language.0));
+
+    Ok(Catalogs {
+        languages,
+        catalogs,
+        formats,
+    })
+}
+
+fn catalogs() -> &'static Catalogs {
+    CATALOGS.get_or_init(|| {
+        load_catalogs(LOCALE_SOURCES).unwrap_or_else(|error| panic!("translations: {}", error))
+    })
+}
+
+fn catalog(language: Language) -> &'static Catalog {
+    let catalogs = catalogs();
+    catalogs
+        .catalogs
+        .get(language.0)
+        .unwrap_or_else(|| &catalogs.catalogs[Language::Cs.0])
+}
+
+/// Loads the catalogs on start so that a broken one stops the server instead of a request.
+pub fn init_translations() -> Result<usize, CatalogError> {
+    if CATALOGS.get().is_none() {
+        let _ = CATALOGS.set(load_catalogs(LOCALE_SOURCES)?);
+    }
+    Ok(languages().len())
+}
+
+/// Checks that `value` is a Fluent value the message can be replaced with.
+pub fn validate_override(
+    key: TranslationKeys,
+    language: Language,
+    value: &str,
+) -> Result<(), ParseError> {
+    catalog(language)
+        .with_overrides(&message_source(key.id(), value))
+        .map(|_| ())
+}
+
+/// Compiles the overrides of `rows`, rows of unknown languages or keys and invalid values are
+/// skipped.
+fn merge_overrides(rows: &[TranslationOverride]) -> Overrides {
+    let mut sources: HashMap<Language, String> = HashMap::new();
+    let mut applied: Vec<(Language, TranslationKeys)> = Vec::new();
+    for row in rows {
+        let (Some(language), Some(key)) = (
+            Language::from_code(&row.language),
+            TranslationKeys::from_id(&row.key),
+        ) else {
+            error!(target: "app", "translations:set_overrides - unknown {} {}", row.language, row.key);
+            continue;
         };
+        if let Err(error) = validate_override(key, language, &row.value) {
+            error!(target: "app", "translations:set_overrides - {} {} {}", row.language, row.key, error);
+            continue;
+        }
+        sources
+            .entry(language)
+            .or_default()
+            .push_str(&message_source(key.id(), &row.value));
+        applied.push((language, key));
+    }
 
-        match lang {
-            Language::En => translation.en,
-            Language::Cs => translation.cs,
+    let mut overrides = Overrides::default();
+    for (language, source) in sources {
+        match catalog(language).with_overrides(&source) {
+            Ok(merged) => {
+                overrides.catalogs.insert(language.0, merged);
+            }
+            Err(error) => {
+                error!(target: "app", "translations:set_overrides - {} {}", language.0, error)
+            }
+        }
+    }
+    for (language, key) in applied.iter() {
+        if let Some(text) = overrides
+            .catalogs
+            .get(language.0)
+            .and_then(|merged| merged.message(key.id()))
+        {
+            overrides
+                .texts
+                .insert((language.0, key.id()), text.to_string());
         }
     }
+
+    overrides
+}
+
+/// Replaces the overrides in use, returns how many of the rows were applied.
+pub fn set_overrides(rows: &[TranslationOverride]) -> usize {
+    let overrides = merge_overrides(rows);
+    let count = overrides.texts.len();
+    *OVERRIDES.write().unwrap_or_else(|error| error.into_inner()) = overrides;
+    count
 }
 
-pub fn get_translation(key: TranslationKeys, lang: Language) -> &'static str {
-    PAGE_TRANSLATIONS.get_translation(key, lang)
+/// Reloads the overrides from the database.
+pub async fn load_overrides() -> Result<usize, sqlx::Error> {
+    let client = get_client().await?;
+    let query = TranslationOverride::get_all_query();
+    debug!(target: "db", "translations:load_overrides - TranslationOverride::get_all_query {}", &query);
+    let rows = sqlx::query_as::<_, TranslationOverride>(&query)
+        .fetch_all(client)
+        .await?;
+    let count = set_overrides(&rows);
+    content_changed();
+    Ok(count)
+}
+
+/// Fluent value of the message in the compiled catalog.
+pub fn default_source(key: TranslationKeys, lang: Language) -> &'static str {
+    catalog(lang).source(key.id()).unwrap_or("")
+}
+
+/// Every language the site is served in, the default one first.
+pub fn languages() -> &'static [Language] {
+    &catalogs().languages
+}
+
+pub fn default_language() -> Language {
+    Language::Cs
+}
+
+/// Text of the message, borrowed from the compiled catalog unless it is overridden.
+pub fn get_translation(key: TranslationKeys, lang: Language) -> Cow<'static, str> {
+    let overrides = OVERRIDES.read().unwrap_or_else(|error| error.into_inner());
+    if let Some(text) = overrides.texts.get(&(lang.0, key.id())) {
+        return Cow::Owned(text.clone());
+    }
+    Cow::Borrowed(catalog(lang).message(key.id()).unwrap_or(key.id()))
+}
+
+/// Message with its `{ $name }` placeables filled in and plural variants selected.
+pub fn get_translation_with(
+    key: TranslationKeys,
+    lang: Language,
+    arguments: &FluentArgs,
+) -> String {
+    let overrides = OVERRIDES.read().unwrap_or_else(|error| error.into_inner());
+    overrides
+        .catalogs
+        .get(lang.0)
+        .unwrap_or_else(|| catalog(lang))
+        .format(key.id(), Some(arguments))
+        .unwrap_or_else(|| key.id().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embedded_catalogs() {
+        let catalogs = load_catalogs(LOCALE_SOURCES).unwrap();
+        assert_eq!(catalogs.languages[0], Language::Cs);
+        assert_eq!(Language::from_code(" EN "), Some(Language::En));
+        assert_eq!(Language::from_code("xx"), None);
+        assert_eq!(Language::Cs.native_name(), "Čeština");
+        assert_eq!(Language::En.locale(), "en-US");
+        assert_eq!(
+            get_translation_with(
+                TranslationKeys::PhotoCount,
+                Language::Cs,
+                &FluentArgs::from_iter([("count", 3)])
+            ),
+            "3 fotky"
+        );
+    }
+
+    #[test]
+    fn test_merge_overrides() {
+        let row = |language: &str, key: &str, value: &str| TranslationOverride {
+            language: String::from(language),
+            key: String::from(key),
+            value: String::from(value),
+            updated: chrono::Utc::now(),
+        };
+
+        let overrides = merge_overrides(&[
+            row("cs", "blog", "deník"),
+            row("en", "blog", "{ -language-name } journal"),
+            row("xx", "blog", "blog"),
+            row("cs", "no-such-key", "x"),
+            row("en", "faq", "{ $broken"),
+        ]);
+        let text = |language: Language, key: TranslationKeys| {
+            overrides
+                .texts
+                .get(&(language.0, key.id()))
+                .map(String::as_str)
+        };
+        assert_eq!(overrides.texts.len(), 2);
+        assert_eq!(text(Language::Cs, TranslationKeys::Blog), Some("deník"));
+        assert_eq!(
+            text(Language::En, TranslationKeys::Blog),
+            Some("English journal")
+        );
+        assert_eq!(text(Language::En, TranslationKeys::FAQ), None);
+        assert_eq!(
+            overrides.catalogs[Language::En.0].message(TranslationKeys::FAQ.id()),
+            Some("FAQ")
+        );
+        assert_eq!(default_source(TranslationKeys::Blog, Language::Cs), "blog");
+        assert!(merge_overrides(&[]).catalogs.is_empty());
+    }
+
+    #[test]
+    fn test_new_language_needs_every_message() {
+        let header = "-language-name = Deutsch\n-locale = de-DE\n-decimal-separator = ,\n\
+            -group-separator = .\n-currency-position = after\n-currency-symbols = EUR €\n\
+            -date-format = %d.%m.%Y\n";
+        let partial = format!("{}gallery = Galerie\n", header);
+        let sources = [
+            ("cs", LOCALE_SOURCES[0].1),
+            ("de", partial.as_str()),
+            ("en", LOCALE_SOURCES[1].1),
+        ];
+        match load_catalogs(&sources) {
+            Err(CatalogError::MissingMessages { language, ids }) => {
+                assert_eq!(language, "de");
+                assert!(ids.contains(&String::from("photo-count")));
+                assert!(!ids.contains(&String::from("gallery")));
+            }
+            other => panic!("expected missing messages, got {:?}", other.map(|_| ())),
+        }
+
+        let complete: String = TranslationKeys::ALL
+            .iter()
+            .map(|key| format!("{} = {}\n", key.id(), key.id()))
+            .collect();
+        let complete = format!("{}{}", header, complete);
+        let sources = [
+            ("cs", LOCALE_SOURCES[0].1),
+            ("de", complete.as_str()),
+            ("en", LOCALE_SOURCES[1].1),
+        ];
+        let catalogs = load_catalogs(&sources).unwrap();
+        assert!(catalogs.formats.contains_key("de"));
+        let codes: Vec<&str> = catalogs
+            .languages
+            .iter()
+            .map(|language| language.0)
+            .collect();
+        assert_eq!(codes, vec!["cs", "de", "en"]);
+
+        let invalid = complete.replace("-currency-position = after", "-currency-position = left");
+        let sources = [
+            ("cs", LOCALE_SOURCES[0].1),
+            ("de", invalid.as_str()),
+            ("en", LOCALE_SOURCES[1].1),
+        ];
+        match load_catalogs(&sources) {
+            Err(CatalogError::InvalidTerm { language, id, .. }) => {
+                assert_eq!((language.as_str(), id), ("de", "currency-position"));
+            }
+            other => panic!("expected an invalid term, got {:?}", other.map(|_| ())),
+        }
+    }
 }
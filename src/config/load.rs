@@ -12,6 +12,21 @@ use tokio::sync::OnceCell;
 /// JwtSecret: String // for creating jwt keys
 /// RegisterUserSecret: String // verification that user is allowed to create another users
 /// DatabaseCertProvided: bool // does connection to database require certificate?
+/// MailTransport: String // "smtp" or "file", defaults to "file"
+/// SmtpHost: String // SMTP relay host name
+/// SmtpPort: u16 // SMTP relay port, defaults to 587
+/// SmtpUsername: String // SMTP login, empty for relays without authentication
+/// SmtpPassword: String // SMTP password
+/// SmtpTls: bool // use STARTTLS when talking to the relay, defaults to true
+/// MailFrom: String // sender address of notification emails
+/// MailNotifyTo: String // address notified about new messages
+/// MailFileDir: String // directory the file mail sink writes .eml files into
+/// ProofOfWorkDifficulty: u8 // leading zero bits required from public forms, 0 disables it
+/// TrustProxyHeaders: bool // take the client address from X-Forwarded-For, defaults to false
+/// InquiryReservationHours: u32 // how long an inquiry holds the painting, 0 disables reservations
+/// DisplayCurrencyCs: String // currency prices are shown in on czech pages, defaults to CZK
+/// DisplayCurrencyEn: String // currency prices are shown in on english pages, defaults to EUR
+/// ExchangeRatesFile: String // CNB formatted rates file imported on start, empty to skip
 /// ```
 #[derive(Clone)]
 pub enum ConfigField {
@@ -23,6 +38,24 @@ pub enum ConfigField {
     JwtSecret,
     RegisterUserSecret,
     DatabaseCertProvided,
+    MailTransport,
+    SmtpHost,
+    SmtpPort,
+    SmtpUsername,
+    SmtpPassword,
+    SmtpTls,
+    MailFrom,
+    MailNotifyTo,
+    MailFileDir,
+    ProofOfWorkDifficulty,
+    TrustProxyHeaders,
+    InquiryReservationHours,
+    DisplayCurrencyCs,
+    DisplayCurrencyEn,
+    ExchangeRatesFile,
+    SiteUrl,
+    RobotsIndex,
+    RobotsDisallow,
 }
 
 impl ConfigField {
@@ -36,6 +69,24 @@ impl ConfigField {
             ConfigField::JwtSecret => "jwt_secret",
             ConfigField::RegisterUserSecret => "register_user_secret",
             ConfigField::DatabaseCertProvided => "database_cert_provided",
+            ConfigField::MailTransport => "mail_transport",
+            ConfigField::SmtpHost => "smtp_host",
+            ConfigField::SmtpPort => "smtp_port",
+            ConfigField::SmtpUsername => "smtp_username",
+            ConfigField::SmtpPassword => "smtp_password",
+            ConfigField::SmtpTls => "smtp_tls",
+            ConfigField::MailFrom => "mail_from",
+            ConfigField::MailNotifyTo => "mail_notify_to",
+            ConfigField::MailFileDir => "mail_file_dir",
+            ConfigField::ProofOfWorkDifficulty => "proof_of_work_difficulty",
+            ConfigField::TrustProxyHeaders => "trust_proxy_headers",
+            ConfigField::InquiryReservationHours => "inquiry_reservation_hours",
+            ConfigField::DisplayCurrencyCs => "display_currency_cs",
+            ConfigField::DisplayCurrencyEn => "display_currency_en",
+            ConfigField::ExchangeRatesFile => "exchange_rates_file",
+            ConfigField::SiteUrl => "site_url",
+            ConfigField::RobotsIndex => "robots_index",
+            ConfigField::RobotsDisallow => "robots_disallow",
         }
     }
 }
@@ -50,6 +101,24 @@ pub struct Config {
     pub jwt_secret: String,
     pub register_user_secret: String,
     pub database_cert_provided: bool,
+    pub mail_transport: String,
+    pub smtp_host: String,
+    pub smtp_port: u16,
+    pub smtp_username: String,
+    pub smtp_password: String,
+    pub smtp_tls: bool,
+    pub mail_from: String,
+    pub mail_notify_to: String,
+    pub mail_file_dir: String,
+    pub proof_of_work_difficulty: u8,
+    pub trust_proxy_headers: bool,
+    pub inquiry_reservation_hours: u32,
+    pub display_currency_cs: String,
+    pub display_currency_en: String,
+    pub exchange_rates_file: String,
+    pub site_url: String,
+    pub robots_index: bool,
+    pub robots_disallow: String,
 }
 
 impl Config {
@@ -66,6 +135,24 @@ impl Config {
             ConfigField::JwtSecret => Box::new(self.jwt_secret.clone()),
             ConfigField::RegisterUserSecret => Box::new(self.register_user_secret.clone()),
             ConfigField::DatabaseCertProvided => Box::new(self.database_cert_provided),
+            ConfigField::MailTransport => Box::new(self.mail_transport.clone()),
+            ConfigField::SmtpHost => Box::new(self.smtp_host.clone()),
+            ConfigField::SmtpPort => Box::new(self.smtp_port),
+            ConfigField::SmtpUsername => Box::new(self.smtp_username.clone()),
+            ConfigField::SmtpPassword => Box::new(self.smtp_password.clone()),
+            ConfigField::SmtpTls => Box::new(self.smtp_tls),
+            ConfigField::MailFrom => Box::new(self.mail_from.clone()),
+            ConfigField::MailNotifyTo => Box::new(self.mail_notify_to.clone()),
+            ConfigField::MailFileDir => Box::new(self.mail_file_dir.clone()),
+            ConfigField::ProofOfWorkDifficulty => Box::new(self.proof_of_work_difficulty),
+            ConfigField::TrustProxyHeaders => Box::new(self.trust_proxy_headers),
+            ConfigField::InquiryReservationHours => Box::new(self.inquiry_reservation_hours),
+            ConfigField::DisplayCurrencyCs => Box::new(self.display_currency_cs.clone()),
+            ConfigField::DisplayCurrencyEn => Box::new(self.display_currency_en.clone()),
+            ConfigField::ExchangeRatesFile => Box::new(self.exchange_rates_file.clone()),
+            ConfigField::SiteUrl => Box::new(self.site_url.clone()),
+            ConfigField::RobotsIndex => Box::new(self.robots_index),
+            ConfigField::RobotsDisallow => Box::new(self.robots_disallow.clone()),
         };
 
         if let Some(result) = value.downcast_ref::<T>() {
@@ -123,6 +210,37 @@ pub async fn init() -> Result<(), io::Error> {
         Err(_) => false,
     };
 
+    let optional = |field: ConfigField, default: &str| -> String {
+        env::var(field.to_str()).unwrap_or_else(|_| default.to_string())
+    };
+
+    let mail_transport = optional(ConfigField::MailTransport, "file");
+    let smtp_host = optional(ConfigField::SmtpHost, "");
+    let smtp_port = optional(ConfigField::SmtpPort, "587")
+        .parse::<u16>()
+        .expect("smtp_port has to be a valid port number");
+    let smtp_username = optional(ConfigField::SmtpUsername, "");
+    let smtp_password = optional(ConfigField::SmtpPassword, "");
+    let smtp_tls = optional(ConfigField::SmtpTls, "true") == "true";
+    let mail_from = optional(ConfigField::MailFrom, "noreply@rosemary-artist.com");
+    let mail_notify_to = optional(ConfigField::MailNotifyTo, "rosemaryphotography@seznam.cz");
+    let mail_file_dir = optional(ConfigField::MailFileDir, "mail");
+    let proof_of_work_difficulty = optional(ConfigField::ProofOfWorkDifficulty, "0")
+        .parse::<u8>()
+        .expect("proof_of_work_difficulty has to be a number between 0 and 255");
+    let trust_proxy_headers = optional(ConfigField::TrustProxyHeaders, "false") == "true";
+    let inquiry_reservation_hours = optional(ConfigField::InquiryReservationHours, "48")
+        .parse::<u32>()
+        .expect("inquiry_reservation_hours has to be a whole number of hours");
+    let display_currency_cs = optional(ConfigField::DisplayCurrencyCs, "CZK").to_uppercase();
+    let display_currency_en = optional(ConfigField::DisplayCurrencyEn, "EUR").to_uppercase();
+    let exchange_rates_file = optional(ConfigField::ExchangeRatesFile, "");
+    let site_url = optional(ConfigField::SiteUrl, "https://www.rosemary-artist.com")
+        .trim_end_matches('/')
+        .to_string();
+    let robots_index = optional(ConfigField::RobotsIndex, "true") == "true";
+    let robots_disallow = optional(ConfigField::RobotsDisallow, "/api/");
+
     let config = Arc::new(Config {
         test_variable: "test".to_string(),
         database_url,
@@ -132,6 +250,24 @@ pub async fn init() -> Result<(), io::Error> {
         jwt_secret,
         register_user_secret,
         database_cert_provided,
+        mail_transport,
+        smtp_host,
+        smtp_port,
+        smtp_username,
+        smtp_password,
+        smtp_tls,
+        mail_from,
+        mail_notify_to,
+        mail_file_dir,
+        proof_of_work_difficulty,
+        trust_proxy_headers,
+        inquiry_reservation_hours,
+        display_currency_cs,
+        display_currency_en,
+        exchange_rates_file,
+        site_url,
+        robots_index,
+        robots_disallow,
     });
     debug!(target: "cfg", "config instance created");
 
@@ -1,4 +1,18 @@
+pub mod artwork_attributes;
+pub mod contact_message;
+pub mod exchange_rate;
+pub mod facets;
+pub mod feed;
 pub mod generics;
 pub mod image;
+pub mod inquiry;
 pub mod painting;
+pub mod painting_slug;
+pub mod related_painting;
+pub mod series;
+pub mod sitemap;
+pub mod suggestion;
+pub mod tag;
+pub mod translation_override;
+pub mod upload;
 pub mod user;
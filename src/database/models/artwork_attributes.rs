@@ -0,0 +1,185 @@
+use chrono::{Datelike, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::types::JsonValue;
+use sqlx::Row;
+
+use crate::client::translations::Language;
+use crate::database::models::generics::{escape_sql_string, Translation};
+
+/// Physical facts about a painting, stored in their own columns of rosemary.paintings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ArtworkAttributes {
+    pub technique: Option<Translation>,
+    pub support: Option<Translation>,
+    pub year_created: Option<i32>,
+    pub framed: Option<bool>,
+    /// centimeters
+    pub depth: Option<i64>,
+    /// grams
+    pub weight: Option<i64>,
+}
+
+impl ArtworkAttributes {
+    pub fn from_row(row: &PgRow) -> sqlx::Result<Self> {
+        let translation = |column: &str| -> sqlx::Result<Option<Translation>> {
+            let value: Option<JsonValue> = row.try_get(column)?;
+            value
+                .map(serde_json::from_value)
+                .transpose()
+                .map_err(|err| sqlx::Error::Decode(Box::new(err)))
+        };
+
+        Ok(Self {
+            technique: translation("technique")?,
+            support: translation("support")?,
+            year_created: row.try_get("year_created")?,
+            framed: row.try_get("framed")?,
+            depth: row.try_get("depth")?,
+            weight: row.try_get("weight")?,
+        })
+    }
+
+    pub fn get_technique(&self, language: Language) -> Option<&str> {
+        translated(self.technique.as_ref(), language)
+    }
+
+    pub fn get_support(&self, language: Language) -> Option<&str> {
+        translated(self.support.as_ref(), language)
+    }
+}
+
+fn translated(translation: Option<&Translation>, language: Language) -> Option<&str> {
+    Some(translation?.get(language)).filter(|value| !value.is_empty())
+}
+
+/// Attribute fields accepted by `PaintingCreate` and `PaintingUpdate`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ArtworkAttributesInput {
+    pub technique_cs: Option<String>,
+    pub technique_en: Option<String>,
+    pub support_cs: Option<String>,
+    pub support_en: Option<String>,
+    pub year_created: Option<i32>,
+    pub framed: Option<bool>,
+    pub depth: Option<i64>,
+    pub weight: Option<i64>,
+}
+
+impl ArtworkAttributesInput {
+    /// Returns the message key of the first invalid attribute.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        let too_long =
+            |value: &Option<String>| value.as_ref().is_some_and(|v| v.chars().count() > 200);
+        if too_long(&self.technique_cs) || too_long(&self.technique_en) {
+            return Err("invalidTechnique");
+        }
+        if too_long(&self.support_cs) || too_long(&self.support_en) {
+            return Err("invalidSupport");
+        }
+
+        if let Some(year) = self.year_created {
+            if year < 1900 || year > Utc::now().year() + 1 {
+                return Err("invalidYearCreated");
+            }
+        }
+
+        if self.depth.is_some_and(|depth| depth <= 0) {
+            return Err("invalidDepth");
+        }
+
+        if self.weight.is_some_and(|weight| weight <= 0) {
+            return Err("invalidWeight");
+        }
+
+        Ok(())
+    }
+
+    fn translation_sql(cs: &Option<String>, en: &Option<String>) -> Option<String> {
+        let mut pairs: Vec<String> = Vec::new();
+        if let Some(value) = cs {
+            pairs.push(format!("'cs', '{}'", escape_sql_string(value.trim())));
+        }
+        if let Some(value) = en {
+            pairs.push(format!("'en', '{}'", escape_sql_string(value.trim())));
+        }
+
+        if pairs.is_empty() {
+            None
+        } else {
+            Some(format!("JSONB_BUILD_OBJECT({})", pairs.join(", ")))
+        }
+    }
+
+    /// Column and SQL value pairs of the provided attributes, translations are
+    /// merged into the stored value when `merge` is set.
+    pub fn column_values(&self, merge: bool) -> Vec<(&'static str, String)> {
+        let mut values: Vec<(&'static str, String)> = Vec::new();
+
+        let translations = [
+            ("technique", &self.technique_cs, &self.technique_en),
+            ("support", &self.support_cs, &self.support_en),
+        ];
+        for (column, cs, en) in translations {
+            if let Some(object) = Self::translation_sql(cs, en) {
+                let value = if merge {
+                    format!("COALESCE({}, '{{}}'::jsonb) || {}", column, object)
+                } else {
+                    object
+                };
+                values.push((column, value));
+            }
+        }
+
+        if let Some(value) = self.year_created {
+            values.push(("year_created", value.to_string()));
+        }
+        if let Some(value) = self.framed {
+            values.push(("framed", value.to_string()));
+        }
+        if let Some(value) = self.depth {
+            values.push(("depth", value.to_string()));
+        }
+        if let Some(value) = self.weight {
+            values.push(("weight", value.to_string()));
+        }
+
+        values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column_values() {
+        let input = ArtworkAttributesInput {
+            technique_cs: Some(String::from("olej")),
+            year_created: Some(2021),
+            framed: Some(false),
+            ..Default::default()
+        };
+
+        assert_eq!(input.validate(), Ok(()));
+        assert_eq!(
+            input.column_values(true),
+            vec![
+                (
+                    "technique",
+                    String::from(
+                        "COALESCE(technique, '{}'::jsonb) || JSONB_BUILD_OBJECT('cs', 'olej')"
+                    )
+                ),
+                ("year_created", String::from("2021")),
+                ("framed", String::from("false")),
+            ]
+        );
+
+        let invalid = ArtworkAttributesInput {
+            year_created: Some(1492),
+            ..Default::default()
+        };
+        assert_eq!(invalid.validate(), Err("invalidYearCreated"));
+    }
+}
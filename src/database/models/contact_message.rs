@@ -0,0 +1,201 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::prelude::FromRow;
+use sqlx::Row;
+use uuid::Uuid;
+
+use crate::utils::spam::FormGuard;
+
+/// Subjects offered by the contact form select
+pub const CONTACT_SUBJECTS: [&str; 3] = ["painting", "photography", "general"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContactMessageCreate {
+    pub name: String,
+    pub email: String,
+    pub phone: Option<String>,
+    pub subject: Option<String>,
+    pub message: String,
+    pub lang: Option<String>,
+    #[serde(flatten)]
+    pub guard: FormGuard,
+}
+
+impl ContactMessageCreate {
+    /// Trims all fields and checks them, returns the message key of the first problem.
+    pub fn validate(mut self) -> Result<Self, &'static str> {
+        self.name = self.name.trim().to_string();
+        self.email = self.email.trim().to_string();
+        self.message = self.message.trim().to_string();
+        self.phone = self
+            .phone
+            .map(|phone| phone.trim().to_string())
+            .filter(|phone| !phone.is_empty());
+        self.subject = self
+            .subject
+            .map(|subject| subject.trim().to_string())
+            .filter(|subject| !subject.is_empty());
+
+        if self.name.is_empty() || self.name.chars().count() > 200 {
+            return Err("invalidName");
+        }
+
+        if !is_valid_email(&self.email) {
+            return Err("invalidEmail");
+        }
+
+        if let Some(phone) = &self.phone {
+            let valid_phone = phone.chars().count() <= 32
+                && phone
+                    .chars()
+                    .all(|c| c.is_ascii_digit() || c == '+' || c == ' ' || c == '-');
+            if !valid_phone {
+                return Err("invalidPhone");
+            }
+        }
+
+        if let Some(subject) = &self.subject {
+            if !CONTACT_SUBJECTS.contains(&subject.as_str()) {
+                return Err("invalidSubject");
+            }
+        }
+
+        if self.message.is_empty() || self.message.chars().count() > 5000 {
+            return Err("invalidMessage");
+        }
+
+        Ok(self)
+    }
+}
+
+pub fn is_valid_email(email: &str) -> bool {
+    if email.len() > 254 || email.chars().any(|c| c.is_whitespace()) {
+        return false;
+    }
+
+    match email.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+        }
+        None => false,
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContactMessage {
+    pub id: Uuid,
+    pub created: DateTime<Utc>,
+    pub name: String,
+    pub email: String,
+    pub phone: Option<String>,
+    pub subject: String,
+    pub message: String,
+    pub language: String,
+    pub notified: bool,
+}
+
+impl<'r> FromRow<'r, PgRow> for ContactMessage {
+    fn from_row(row: &'r PgRow) -> sqlx::Result<Self> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            created: row.try_get("created")?,
+            name: row.try_get("name")?,
+            email: row.try_get("email")?,
+            phone: row.try_get("phone")?,
+            subject: row.try_get("subject")?,
+            message: row.try_get("message")?,
+            language: row.try_get("language")?,
+            notified: row.try_get("notified")?,
+        })
+    }
+}
+
+impl ContactMessage {
+    /// Binds: $1 name, $2 email, $3 phone, $4 subject, $5 message, $6 language
+    pub fn create_query() -> String {
+        String::from(
+            r#"
+			INSERT INTO rosemary.contact_messages(
+				name,
+				email,
+				phone,
+				subject,
+				message,
+				language
+			) VALUES ($1, $2, $3, $4, $5, $6)
+			RETURNING *"#,
+        )
+    }
+
+    pub fn count_all_query() -> String {
+        String::from("SELECT COUNT(id) FROM rosemary.contact_messages")
+    }
+
+    pub fn get_all_query(limit: u8, offset: u32) -> String {
+        format!(
+            r#"
+			SELECT *
+			FROM rosemary.contact_messages
+			ORDER BY created DESC
+			LIMIT {} OFFSET {}
+		"#,
+            limit, offset
+        )
+    }
+
+    pub fn set_notified_query(id: Uuid) -> String {
+        format!(
+            "UPDATE rosemary.contact_messages SET notified = TRUE WHERE id = '{}'",
+            id
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message() -> ContactMessageCreate {
+        ContactMessageCreate {
+            name: String::from(" Jan Novák "),
+            email: String::from("jan.novak@example.com"),
+            phone: Some(String::from("")),
+            subject: Some(String::from("painting")),
+            message: String::from("Dobrý den"),
+            lang: None,
+            guard: FormGuard::default(),
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        let valid = message().validate().unwrap();
+        assert_eq!(valid.name, "Jan Novák");
+        assert_eq!(valid.phone, None);
+
+        let mut invalid = message();
+        invalid.email = String::from("jan.novak@");
+        assert_eq!(invalid.validate().unwrap_err(), "invalidEmail");
+
+        let mut invalid = message();
+        invalid.subject = Some(String::from("obraz"));
+        assert_eq!(invalid.validate().unwrap_err(), "invalidSubject");
+
+        let mut invalid = message();
+        invalid.message = String::from("   ");
+        assert_eq!(invalid.validate().unwrap_err(), "invalidMessage");
+    }
+
+    #[test]
+    fn test_is_valid_email() {
+        assert!(is_valid_email("rosemary@rosemary-artist.com"));
+        assert!(!is_valid_email("rosemary"));
+        assert!(!is_valid_email("rose mary@example.com"));
+        assert!(!is_valid_email("rosemary@localhost"));
+    }
+}
@@ -0,0 +1,60 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::prelude::FromRow;
+use sqlx::Row;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExchangeRateUpdate {
+    pub amount: Option<i32>,
+    pub rate: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExchangeRate {
+    pub currency: String,
+    /// rate is quoted for this many units of the currency
+    pub amount: i32,
+    /// CZK paid for `amount` units of the currency
+    pub rate: f64,
+    pub updated: DateTime<Utc>,
+}
+
+impl<'r> FromRow<'r, PgRow> for ExchangeRate {
+    fn from_row(row: &'r PgRow) -> sqlx::Result<Self> {
+        Ok(Self {
+            currency: row.try_get("currency")?,
+            amount: row.try_get("amount")?,
+            rate: row.try_get("rate")?,
+            updated: row.try_get("updated")?,
+        })
+    }
+}
+
+impl ExchangeRate {
+    /// CZK for a single unit of the currency
+    pub fn unit_rate(&self) -> f64 {
+        self.rate / f64::from(self.amount.max(1))
+    }
+
+    pub fn get_all_query() -> String {
+        String::from("SELECT * FROM rosemary.exchange_rates ORDER BY currency")
+    }
+
+    /// Binds: $1 currency, $2 amount, $3 rate
+    pub fn upsert_query() -> String {
+        String::from(
+            r#"
+			INSERT INTO rosemary.exchange_rates(currency, amount, rate, updated)
+			VALUES ($1, $2, $3, now())
+			ON CONFLICT (currency) DO UPDATE
+			SET amount = EXCLUDED.amount, rate = EXCLUDED.rate, updated = now()
+			RETURNING *"#,
+        )
+    }
+
+    /// Binds: $1 currency
+    pub fn delete_query() -> String {
+        String::from("DELETE FROM rosemary.exchange_rates WHERE currency = $1 RETURNING *")
+    }
+}
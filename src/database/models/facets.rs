@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::prelude::FromRow;
+use sqlx::types::JsonValue;
+use sqlx::Row;
+
+/// Number of paintings the listing would show with `value` picked
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FacetCount {
+    pub value: String,
+    pub label: String,
+    pub count: i64,
+}
+
+/// Smallest and largest value in the listing, none when it is empty
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FacetRange {
+    pub min: Option<i64>,
+    pub max: Option<i64>,
+}
+
+/// Filter options of the painting listing, see `Painting::facets_query`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Facets {
+    pub availability: Vec<FacetCount>,
+    pub orientation: Vec<FacetCount>,
+    pub year: Vec<FacetCount>,
+    pub series: Vec<FacetCount>,
+    pub tag: Vec<FacetCount>,
+    /// whole units of `currency`
+    pub price: FacetRange,
+    pub currency: String,
+    pub width: FacetRange,
+    pub height: FacetRange,
+}
+
+impl<'r> FromRow<'r, PgRow> for Facets {
+    fn from_row(row: &'r PgRow) -> sqlx::Result<Self> {
+        let facets_json: JsonValue = row.try_get("facets")?;
+        serde_json::from_value(facets_json).map_err(|err| sqlx::Error::Decode(Box::new(err)))
+    }
+}
+
+impl Facets {
+    /// Count of `value` in `facet`, zero when no painting has it.
+    pub fn count(facet: &[FacetCount], value: &str) -> i64 {
+        facet
+            .iter()
+            .find(|item| item.value == value)
+            .map(|item| item.count)
+            .unwrap_or(0)
+    }
+}
@@ -0,0 +1,32 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::prelude::FromRow;
+use sqlx::Row;
+
+use crate::database::models::painting::PaintingStub;
+
+/// Paintings listed in the Atom and RSS feeds
+pub const FEED_LIMIT: i64 = 20;
+
+/// Painting as `Painting::get_feed_query` returns it
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FeedPainting {
+    #[serde(flatten)]
+    pub stub: PaintingStub,
+    pub updated: DateTime<Utc>,
+    pub description: String,
+    /// absolute URL of the largest preview variant
+    pub image: Option<String>,
+}
+
+impl<'r> FromRow<'r, PgRow> for FeedPainting {
+    fn from_row(row: &'r PgRow) -> sqlx::Result<Self> {
+        Ok(Self {
+            stub: PaintingStub::from_row(row)?,
+            updated: row.try_get("updated")?,
+            description: row.try_get("description")?,
+            image: row.try_get("image")?,
+        })
+    }
+}
@@ -1,11 +1,44 @@
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
-#[derive(Debug, Serialize, Deserialize)]
+use crate::client::translations::Language;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Translation {
+    #[serde(default)]
     pub en: String,
+    #[serde(default)]
     pub cs: String,
 }
 
+impl Translation {
+    /// Text in the content language of `language`.
+    pub fn get(&self, language: Language) -> &str {
+        if language.content() == Language::Cs {
+            self.cs.as_str()
+        } else {
+            self.en.as_str()
+        }
+    }
+}
+
+/// Doubles single quotes so the value can sit inside a '...' literal of a formatted query.
+pub fn escape_sql_string(value: &str) -> String {
+    value.replace('\'', "''")
+}
+
+/// Object of only the provided languages, merged into a stored translation with `||`.
+pub fn translation_patch(cs: Option<&str>, en: Option<&str>) -> Value {
+    let mut patch = Map::new();
+    if let Some(value) = cs {
+        patch.insert(String::from("cs"), Value::from(value.trim()));
+    }
+    if let Some(value) = en {
+        patch.insert(String::from("en"), Value::from(value.trim()));
+    }
+    Value::Object(patch)
+}
+
 pub fn deserialize_json_string<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
 where
     D: serde::Deserializer<'de>,
@@ -18,6 +18,17 @@ pub struct PaintingImageCreate {
     pub title_en: String,
     pub painting_id: Uuid,
 }
+/// Per-file metadata sent as a JSON part named `meta_<key>` next to the file part
+/// named `image_<key>`. Missing fields fall back to the query values.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ImageMetaPart {
+    pub preview: Option<bool>,
+    pub title_cs: Option<String>,
+    pub title_en: Option<String>,
+    pub alt_cs: Option<String>,
+    pub alt_en: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PaintingImageUpdate {
     pub alt_cs: Option<String>,
@@ -163,6 +174,21 @@ impl PaintingImage {
         )
     }
 
+    /// Binds: $1 id, $2 palette
+    pub fn update_palette_query() -> String {
+        String::from("UPDATE rosemary.painting_images SET palette = $2 WHERE id = $1")
+    }
+
+    /// Processed images without a palette with the path of their smallest variant
+    pub fn get_missing_palette_query() -> String {
+        String::from(
+            r#"
+			SELECT pi.id, pi.urls->>0 AS file
+			FROM rosemary.painting_images pi
+			WHERE pi.status = 'PROCESSED' AND pi.palette IS NULL AND pi.urls->>0 IS NOT NULL"#,
+        )
+    }
+
     pub fn delete_query(id: Uuid) -> String {
         format!(
             r#"
@@ -176,10 +202,7 @@ impl PaintingImage {
 
     pub fn get_title(&self, language: Language) -> &str {
         if let Some(title) = &self.title {
-            match language {
-                Language::Cs => title.cs.as_str(),
-                Language::En => title.en.as_str(),
-            }
+            title.get(language)
         } else {
             "Failed to get image title"
         }
@@ -187,10 +210,7 @@ impl PaintingImage {
 
     pub fn get_alt(&self, language: Language) -> &str {
         if let Some(alt) = &self.alt {
-            match language {
-                Language::Cs => alt.cs.as_str(),
-                Language::En => alt.en.as_str(),
-            }
+            alt.get(language)
         } else {
             "Failed to get image alt"
         }
@@ -0,0 +1,318 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::prelude::FromRow;
+use sqlx::Row;
+use uuid::Uuid;
+
+use crate::database::models::contact_message::is_valid_email;
+use crate::database::models::painting::AVAILABILITY_SQL;
+use crate::utils::spam::FormGuard;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InquiryStatus {
+    Open,
+    Answered,
+    Closed,
+}
+
+impl InquiryStatus {
+    pub fn to_str(self) -> &'static str {
+        match self {
+            InquiryStatus::Open => "open",
+            InquiryStatus::Answered => "answered",
+            InquiryStatus::Closed => "closed",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<InquiryStatus> {
+        match code {
+            "open" => Some(InquiryStatus::Open),
+            "answered" => Some(InquiryStatus::Answered),
+            "closed" => Some(InquiryStatus::Closed),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaintingInquiryCreate {
+    pub full_name: String,
+    pub email: String,
+    pub message: Option<String>,
+    pub lang: Option<String>,
+    #[serde(flatten)]
+    pub guard: FormGuard,
+}
+
+impl PaintingInquiryCreate {
+    /// Trims all fields and checks them, returns the message key of the first problem.
+    pub fn validate(mut self) -> Result<Self, &'static str> {
+        self.full_name = self.full_name.trim().to_string();
+        self.email = self.email.trim().to_string();
+        self.message = self
+            .message
+            .map(|message| message.trim().to_string())
+            .filter(|message| !message.is_empty());
+
+        if self.full_name.is_empty() || self.full_name.chars().count() > 200 {
+            return Err("invalidName");
+        }
+
+        if !is_valid_email(&self.email) {
+            return Err("invalidEmail");
+        }
+
+        if self
+            .message
+            .as_ref()
+            .is_some_and(|message| message.chars().count() > 5000)
+        {
+            return Err("invalidMessage");
+        }
+
+        Ok(self)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PaintingInquiryAnswer {
+    pub answer: String,
+    pub close: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PaintingInquiriesQuery {
+    pub status: Option<InquiryStatus>,
+    pub painting_id: Option<Uuid>,
+    pub limit: Option<u8>,
+    pub offset: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PaintingInquiry {
+    pub id: Uuid,
+    pub created: DateTime<Utc>,
+    pub painting_id: Uuid,
+    pub full_name: String,
+    pub email: String,
+    pub message: Option<String>,
+    pub language: String,
+    pub status: InquiryStatus,
+    pub reserved_until: Option<DateTime<Utc>>,
+    pub answer: Option<String>,
+    pub answered: Option<DateTime<Utc>>,
+    pub closed: Option<DateTime<Utc>>,
+    pub notified: bool,
+}
+
+impl<'r> FromRow<'r, PgRow> for PaintingInquiry {
+    fn from_row(row: &'r PgRow) -> sqlx::Result<Self> {
+        let status: String = row.try_get("status")?;
+        let status = InquiryStatus::from_code(&status).ok_or_else(|| {
+            sqlx::Error::Decode(format!("unknown inquiry status {}", status).into())
+        })?;
+
+        Ok(Self {
+            id: row.try_get("id")?,
+            created: row.try_get("created")?,
+            painting_id: row.try_get("painting_id")?,
+            full_name: row.try_get("full_name")?,
+            email: row.try_get("email")?,
+            message: row.try_get("message")?,
+            language: row.try_get("language")?,
+            status,
+            reserved_until: row.try_get("reserved_until")?,
+            answer: row.try_get("answer")?,
+            answered: row.try_get("answered")?,
+            closed: row.try_get("closed")?,
+            notified: row.try_get("notified")?,
+        })
+    }
+}
+
+impl PaintingInquiry {
+    /// Binds: $1 painting_id, $2 full_name, $3 email, $4 message, $5 language
+    ///
+    /// An available painting gets reserved for the inquirer, returns no row when
+    /// the painting does not exist or is not for sale any more. The painting row is locked so
+    /// that a concurrent inquiry sees the reservation instead of making its own.
+    pub fn create_query(reservation_hours: u32) -> String {
+        format!(
+            r#"
+			WITH painting AS (
+				SELECT p.id, {availability} AS availability
+				FROM rosemary.paintings p
+				WHERE p.id = $1
+					AND p.deleted IS NULL
+					AND {availability} IN ('available', 'reserved')
+				FOR UPDATE
+			), inquiry AS (
+				INSERT INTO rosemary.painting_inquiries(
+					painting_id,
+					full_name,
+					email,
+					message,
+					language,
+					reserved_until
+				)
+				SELECT
+					painting.id,
+					$2,
+					$3,
+					$4,
+					$5,
+					CASE WHEN {hours} > 0 AND painting.availability = 'available'
+						THEN now() + INTERVAL '{hours} hours'
+						ELSE NULL
+					END
+				FROM painting
+				RETURNING *
+			), reservation AS (
+				UPDATE rosemary.paintings p
+				SET availability = 'reserved', reserved_until = inquiry.reserved_until
+				FROM inquiry
+				WHERE p.id = inquiry.painting_id
+					AND inquiry.reserved_until IS NOT NULL
+			)
+			SELECT * FROM inquiry"#,
+            availability = AVAILABILITY_SQL,
+            hours = reservation_hours
+        )
+    }
+
+    /// Closes the inquiry and hands back the painting when the reservation is still its own.
+    fn close_statement(id: Uuid, answer: bool) -> String {
+        let answer_values = if answer {
+            "answer = $1, answered = now(),"
+        } else {
+            ""
+        };
+
+        format!(
+            r#"
+			WITH previous AS (
+				SELECT id, painting_id, reserved_until
+				FROM rosemary.painting_inquiries
+				WHERE id = '{id}' AND status <> 'closed'
+			), released AS (
+				UPDATE rosemary.paintings p
+				SET availability = 'available', reserved_until = NULL
+				FROM previous
+				WHERE p.id = previous.painting_id
+					AND p.availability = 'reserved'
+					AND p.reserved_until = previous.reserved_until
+			)
+			UPDATE rosemary.painting_inquiries i
+			SET {answer_values} status = 'closed', closed = now(), reserved_until = NULL
+			FROM previous
+			WHERE i.id = previous.id
+			RETURNING i.*"#,
+            id = id,
+            answer_values = answer_values
+        )
+    }
+
+    fn filter_clause(query: &PaintingInquiriesQuery) -> String {
+        let mut conditions: Vec<String> = vec![String::from("TRUE")];
+
+        if let Some(status) = query.status {
+            conditions.push(format!("status = '{}'", status.to_str()));
+        }
+
+        if let Some(painting_id) = query.painting_id {
+            conditions.push(format!("painting_id = '{}'", painting_id));
+        }
+
+        conditions.join(" AND ")
+    }
+
+    pub fn count_all_query(query: &PaintingInquiriesQuery) -> String {
+        format!(
+            "SELECT COUNT(id) FROM rosemary.painting_inquiries WHERE {}",
+            Self::filter_clause(query)
+        )
+    }
+
+    pub fn get_all_query(query: &PaintingInquiriesQuery, limit: u8, offset: u32) -> String {
+        format!(
+            r#"
+			SELECT *
+			FROM rosemary.painting_inquiries
+			WHERE {}
+			ORDER BY created DESC
+			LIMIT {} OFFSET {}
+		"#,
+            Self::filter_clause(query),
+            limit,
+            offset
+        )
+    }
+
+    /// Binds: $1 answer
+    pub fn answer_query(id: Uuid, close: bool) -> String {
+        if close {
+            return Self::close_statement(id, true);
+        }
+
+        format!(
+            r#"
+			UPDATE rosemary.painting_inquiries
+			SET answer = $1, answered = now(), status = 'answered'
+			WHERE id = '{}' AND status <> 'closed'
+			RETURNING *"#,
+            id
+        )
+    }
+
+    /// Closing an inquiry releases the reservation it holds.
+    pub fn close_query(id: Uuid) -> String {
+        Self::close_statement(id, false)
+    }
+
+    pub fn set_notified_query(id: Uuid) -> String {
+        format!(
+            "UPDATE rosemary.painting_inquiries SET notified = TRUE WHERE id = '{}'",
+            id
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        let inquiry = PaintingInquiryCreate {
+            full_name: String::from(" Jana Nováková "),
+            email: String::from("jana@example.com"),
+            message: Some(String::from("  ")),
+            lang: None,
+            guard: FormGuard::default(),
+        };
+
+        let valid = inquiry.clone().validate().unwrap();
+        assert_eq!(valid.full_name, "Jana Nováková");
+        assert_eq!(valid.message, None);
+
+        let mut invalid = inquiry;
+        invalid.full_name = String::new();
+        assert_eq!(invalid.validate().unwrap_err(), "invalidName");
+    }
+
+    #[test]
+    fn test_filter_clause() {
+        let query = PaintingInquiriesQuery {
+            status: Some(InquiryStatus::Open),
+            painting_id: None,
+            limit: None,
+            offset: None,
+        };
+        assert_eq!(
+            PaintingInquiry::filter_clause(&query),
+            "TRUE AND status = 'open'"
+        );
+    }
+}
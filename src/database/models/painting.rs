@@ -10,20 +10,215 @@ use std::collections::HashMap;
 use uuid::Uuid;
 use warp::Reply;
 
+use crate::client::routes::Route;
 use crate::client::translations::Language;
-use crate::database::models::generics::{deserialize_json_string, Translation};
+use crate::database::models::artwork_attributes::{ArtworkAttributes, ArtworkAttributesInput};
+use crate::database::models::generics::{deserialize_json_string, escape_sql_string, Translation};
 use crate::database::models::image::PaintingImage;
-use crate::requests::dto::get_paintings_query::{GetPaintingsQuery, GetPaintingsQueryParsed};
+use crate::requests::dto::cursor::Cursor;
+use crate::requests::dto::get_paintings_query::{
+    GetPaintingsQuery, GetPaintingsQueryParsed, RangeFilter,
+};
+use crate::utils::money::{is_currency_code, BASE_CURRENCY};
+
+/// Availability as the public sees it, reservations past `reserved_until` count as available again
+pub const AVAILABILITY_SQL: &str = "CASE WHEN p.availability = 'reserved' AND p.reserved_until <= now() THEN 'available' ELSE p.availability END";
+
+/// Slugs of the painting tags as a text array
+const TAGS_SQL: &str = "ARRAY(SELECT t.slug FROM rosemary.painting_tags pt JOIN rosemary.tags t ON t.id = pt.tag_id WHERE pt.painting_id = p.id ORDER BY t.slug)";
+
+/// `ts_headline` wraps matches in these, `PaintingStub::snippet_html` turns them into `<mark>`
+const HEADLINE_START: &str = "[[mark]]";
+const HEADLINE_STOP: &str = "[[/mark]]";
+
+/// Search vector column of the language, kept up to date by the 0009 migration triggers
+fn search_column(language: Language) -> &'static str {
+    if language.content() == Language::Cs {
+        "p.search_cs"
+    } else {
+        "p.search_en"
+    }
+}
+
+/// Czech is matched both stemmed and without accents, see `rosemary.czech_unaccent`.
+fn search_tsquery(language: Language, search: &str) -> String {
+    let search = escape_sql_string(search);
+    if language.content() == Language::Cs {
+        format!(
+            "(websearch_to_tsquery('rosemary.czech', '{0}') || websearch_to_tsquery('rosemary.czech_unaccent', '{0}'))",
+            search
+        )
+    } else {
+        format!(
+            "websearch_to_tsquery('rosemary.english_unaccent', '{}')",
+            search
+        )
+    }
+}
+
+/// Description excerpt around the matches, the title when there is no description.
+fn search_headline(language: Language, search: &str) -> String {
+    let config = if language.content() == Language::Cs {
+        "rosemary.czech_unaccent"
+    } else {
+        "rosemary.english_unaccent"
+    };
+    format!(
+        "ts_headline('{config}', COALESCE(NULLIF(p.painting_description->>'{lang}', ''), p.painting_title->>'{lang}'), websearch_to_tsquery('{config}', '{search}'), 'StartSel={start}, StopSel={stop}, MaxWords=30, MinWords=12, MaxFragments=2')",
+        config = config,
+        lang = language.content().to_str(),
+        search = escape_sql_string(search),
+        start = HEADLINE_START,
+        stop = HEADLINE_STOP
+    )
+}
+
+fn search_rank(language: Language, search: Option<&str>) -> String {
+    match search {
+        Some(value) => format!(
+            "ts_rank_cd({}, {})",
+            search_column(language),
+            search_tsquery(language, value)
+        ),
+        None => String::from("0::REAL"),
+    }
+}
+
+/// Price converted to the base currency with the stored exchange rates
+const BASE_PRICE_SQL: &str = "p.price * COALESCE((SELECT r.rate / r.amount FROM rosemary.exchange_rates r WHERE r.currency = p.currency), 1)";
+
+/// Score of a series the painting shares with the related one, see `Painting::get_related_query`
+const RELATED_SERIES_WEIGHT: f64 = 3.0;
+
+/// Score of a tag the painting shares with the related one
+const RELATED_TAG_WEIGHT: f64 = 1.5;
+
+/// Orientation of the canvas, see `Orientation`
+const ORIENTATION_SQL: &str = "CASE WHEN p.width > p.height THEN 'landscape' WHEN p.width < p.height THEN 'portrait' ELSE 'square' END";
+
+/// Price in `currency`, which has to pass `is_currency_code`.
+fn price_sql(currency: &str) -> String {
+    if currency == BASE_CURRENCY {
+        return format!("({})", BASE_PRICE_SQL);
+    }
+    format!(
+        "({}) / COALESCE((SELECT r.rate / r.amount FROM rosemary.exchange_rates r WHERE r.currency = '{}'), 1)",
+        BASE_PRICE_SQL, currency
+    )
+}
+
+fn range_condition(value: &str, range: &RangeFilter) -> Option<String> {
+    let bounds: Vec<String> = [(">=", range.min), ("<=", range.max)]
+        .iter()
+        .filter_map(|(operator, bound)| bound.map(|b| format!("{} {} {}", value, operator, b)))
+        .collect();
+
+    if bounds.is_empty() {
+        None
+    } else {
+        Some(format!("({})", bounds.join(" AND ")))
+    }
+}
+
+/// Filters of the listing, facets leave their own one out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Filter {
+    Availability,
+    Series,
+    Tag,
+    Search,
+    Price,
+    Width,
+    Height,
+    Orientation,
+    Year,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Orientation {
+    Landscape,
+    Portrait,
+    Square,
+}
+
+impl Orientation {
+    pub fn to_str(self) -> &'static str {
+        match self {
+            Orientation::Landscape => "landscape",
+            Orientation::Portrait => "portrait",
+            Orientation::Square => "square",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Orientation> {
+        match code {
+            "landscape" => Some(Orientation::Landscape),
+            "portrait" => Some(Orientation::Portrait),
+            "square" => Some(Orientation::Square),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Availability {
+    Available,
+    Reserved,
+    Sold,
+    NotForSale,
+}
+
+impl Availability {
+    pub fn to_str(self) -> &'static str {
+        match self {
+            Availability::Available => "available",
+            Availability::Reserved => "reserved",
+            Availability::Sold => "sold",
+            Availability::NotForSale => "not_for_sale",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Availability> {
+        match code {
+            "available" => Some(Availability::Available),
+            "reserved" => Some(Availability::Reserved),
+            "sold" => Some(Availability::Sold),
+            "not_for_sale" => Some(Availability::NotForSale),
+            _ => None,
+        }
+    }
+
+    /// Same rule as `AVAILABILITY_SQL` for rows selected with `p.*`.
+    pub fn effective(code: &str, reserved_until: Option<DateTime<Utc>>) -> sqlx::Result<Self> {
+        let availability = Availability::from_code(code).ok_or_else(|| {
+            sqlx::Error::Decode(format!("unknown painting availability {}", code).into())
+        })?;
+
+        match (availability, reserved_until) {
+            (Availability::Reserved, Some(until)) if until <= Utc::now() => {
+                Ok(Availability::Available)
+            }
+            _ => Ok(availability),
+        }
+    }
+}
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct PaintingStub {
     pub id: Uuid,
     pub created: DateTime<Utc>,
     pub price: i64,
+    pub currency: String,
     pub size: String,
     pub title: String,
     pub preview_alt: String,
     pub preview: String,
+    pub availability: Availability,
+    pub slug: Option<String>,
+    /// search matches wrapped in `HEADLINE_START` and `HEADLINE_STOP`
+    pub snippet: Option<String>,
 }
 
 impl<'r> FromRow<'r, PgRow> for PaintingStub {
@@ -32,20 +227,49 @@ impl<'r> FromRow<'r, PgRow> for PaintingStub {
             id: row.try_get("id")?,
             created: row.try_get("created")?,
             price: row.try_get("price")?,
+            currency: row.try_get("currency")?,
             size: row.try_get("size")?,
             title: row.try_get("title")?,
             preview_alt: row.try_get("preview_alt")?,
             preview: row.try_get("preview")?,
+            availability: Availability::effective(row.try_get("availability")?, None)?,
+            slug: row.try_get("slug")?,
+            snippet: row.try_get("snippet")?,
         })
     }
 }
 
+impl PaintingStub {
+    /// Detail page of the painting, by UUID until it has a slug.
+    pub fn href(&self, language: &Language) -> String {
+        let slug = self.slug.clone().unwrap_or_else(|| self.id.to_string());
+        Route::Painting(slug).path(*language)
+    }
+
+    /// HTML escaped search snippet with the matches in `<mark>`.
+    pub fn snippet_html(&self) -> Option<String> {
+        let snippet = self.snippet.as_deref()?;
+        let escaped = snippet
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+            .replace('\'', "&#x27;");
+        Some(
+            escaped
+                .replace(HEADLINE_START, "<mark>")
+                .replace(HEADLINE_STOP, "</mark>"),
+        )
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct PaintingBase {
     pub id: Uuid,
     pub created: DateTime<Utc>,
     pub deleted: Option<DateTime<Utc>>,
     pub price: i64,
+    pub currency: String,
     #[serde(deserialize_with = "deserialize_json_string")]
     pub painting_title: Option<Translation>,
     #[serde(deserialize_with = "deserialize_json_string")]
@@ -54,6 +278,12 @@ pub struct PaintingBase {
     pub data: Option<HashMap<String, String>>,
     pub width: i64,
     pub height: i64,
+    pub availability: Availability,
+    pub reserved_until: Option<DateTime<Utc>>,
+    pub sold_date: Option<DateTime<Utc>>,
+    pub buyer_reference: Option<String>,
+    #[serde(flatten)]
+    pub attributes: ArtworkAttributes,
 }
 
 impl<'r> FromRow<'r, PgRow> for PaintingBase {
@@ -75,11 +305,20 @@ impl<'r> FromRow<'r, PgRow> for PaintingBase {
             created: row.try_get("created")?,
             deleted: row.try_get("deleted").unwrap_or(None),
             price: row.try_get("price")?,
+            currency: row.try_get("currency")?,
             painting_title: Some(title),
             painting_description: Some(description),
             data: Some(data),
             width: row.try_get("width")?,
             height: row.try_get("height")?,
+            availability: Availability::effective(
+                row.try_get("availability")?,
+                row.try_get("reserved_until")?,
+            )?,
+            reserved_until: row.try_get("reserved_until")?,
+            sold_date: row.try_get("sold_date")?,
+            buyer_reference: row.try_get("buyer_reference")?,
+            attributes: ArtworkAttributes::from_row(row)?,
         })
     }
 }
@@ -87,24 +326,35 @@ impl<'r> FromRow<'r, PgRow> for PaintingBase {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PaintingCreate {
     pub price: i64,
+    pub currency: Option<String>,
     pub title_cs: String,
     pub title_en: String,
     pub description_cs: String,
     pub description_en: String,
     pub width: i64,
     pub height: i64,
+    #[serde(flatten)]
+    pub attributes: ArtworkAttributesInput,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PaintingUpdate {
     pub price: Option<i64>,
+    pub currency: Option<String>,
     pub title_cs: Option<String>,
     pub title_en: Option<String>,
     pub description_cs: Option<String>,
     pub description_en: Option<String>,
     pub width: Option<i64>,
     pub height: Option<i64>,
+    /// kept for older admin clients, `availability` wins when both are sent
     pub sold: Option<bool>,
+    pub availability: Option<Availability>,
+    pub reserved_until: Option<DateTime<Utc>>,
+    pub sold_date: Option<DateTime<Utc>>,
+    pub buyer_reference: Option<String>,
+    #[serde(flatten)]
+    pub attributes: ArtworkAttributesInput,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -118,6 +368,7 @@ pub struct Painting {
     pub created: DateTime<Utc>,
     pub deleted: Option<DateTime<Utc>>,
     pub price: i64,
+    pub currency: String,
     #[serde(deserialize_with = "deserialize_json_string")]
     pub painting_title: Option<Translation>,
     #[serde(deserialize_with = "deserialize_json_string")]
@@ -126,7 +377,16 @@ pub struct Painting {
     pub data: Option<HashMap<String, String>>,
     pub width: i64,
     pub height: i64,
+    pub availability: Availability,
+    pub reserved_until: Option<DateTime<Utc>>,
+    pub sold_date: Option<DateTime<Utc>>,
+    #[serde(flatten)]
+    pub attributes: ArtworkAttributes,
+    pub tags: Vec<String>,
     pub preview: Json<PaintingImage>,
+    /// sort value printed by `get_keyset_query`, only used to make cursors
+    #[serde(skip)]
+    pub sort_value: Option<String>,
 }
 
 impl<'r> FromRow<'r, PgRow> for Painting {
@@ -134,9 +394,13 @@ impl<'r> FromRow<'r, PgRow> for Painting {
         let id: Uuid = row.try_get("id")?;
         let created: DateTime<Utc> = row.try_get("created")?;
         let price: i64 = row.try_get("price").unwrap_or(0);
+        let currency: String = row.try_get("currency")?;
         let width: i64 = row.try_get("width").unwrap_or(0);
         let height: i64 = row.try_get("height").unwrap_or(0);
         let deleted = row.try_get("deleted").unwrap_or(None);
+        let reserved_until: Option<DateTime<Utc>> = row.try_get("reserved_until")?;
+        let availability = Availability::effective(row.try_get("availability")?, reserved_until)?;
+        let sold_date: Option<DateTime<Utc>> = row.try_get("sold_date")?;
 
         let preview_json: JsonValue = row.try_get("preview")?;
         let preview: PaintingImage = serde_json::from_value(preview_json)
@@ -159,12 +423,19 @@ impl<'r> FromRow<'r, PgRow> for Painting {
             created,
             deleted,
             price,
+            currency,
             painting_title: Some(title),
             painting_description: Some(description),
             data: Some(data),
             width,
             height,
+            availability,
+            reserved_until: reserved_until.filter(|_| availability == Availability::Reserved),
+            sold_date,
+            attributes: ArtworkAttributes::from_row(row)?,
+            tags: row.try_get("tags")?,
             preview: Json(preview),
+            sort_value: row.try_get("sort_value").unwrap_or(None),
         })
     }
 }
@@ -176,11 +447,22 @@ impl Reply for Painting {
             "created": self.created,
             "deleted": self.deleted,
             "price": self.price,
+            "currency": self.currency,
             "painting_title": self.painting_title,
             "painting_description": self.painting_description,
             "data": self.data,
             "width": self.width,
             "height": self.height,
+            "availability": self.availability,
+            "reserved_until": self.reserved_until,
+            "sold_date": self.sold_date,
+            "technique": self.attributes.technique,
+            "support": self.attributes.support,
+            "year_created": self.attributes.year_created,
+            "framed": self.attributes.framed,
+            "depth": self.attributes.depth,
+            "weight": self.attributes.weight,
+            "tags": self.tags,
             "preview": self.preview,
         });
 
@@ -194,6 +476,7 @@ impl Painting {
             r#"
 			SELECT
 				p.*,
+				{} AS tags,
 				(JSON_BUILD_OBJECT(
 					'id', pi.id,
 					'preview', pi.preview,
@@ -208,31 +491,246 @@ impl Painting {
 					AND p.deleted IS NULL
 				LIMIT 1
 		"#,
-            id
+            TAGS_SQL, id
+        )
+    }
+
+    /// Binds: $1 id
+    pub fn exists_query() -> String {
+        String::from("SELECT id FROM rosemary.paintings WHERE id = $1 AND deleted IS NULL")
+    }
+
+    /// Binds: $1 id
+    ///
+    /// Marks the painting as changed for the sitemap, returns nothing for deleted paintings.
+    pub fn touch_query() -> String {
+        String::from(
+            "UPDATE rosemary.paintings SET updated = now() WHERE id = $1 AND deleted IS NULL RETURNING id",
         )
     }
 
-    pub fn count_all_query() -> String {
-        r#"
+    /// Binds: $1 from, $2 to
+    ///
+    /// Reservations that ran out between the two instants.
+    pub fn count_expired_reservations_query() -> String {
+        String::from(
+            "SELECT COUNT(*) FROM rosemary.paintings WHERE availability = 'reserved' AND reserved_until > $1 AND reserved_until <= $2 AND deleted IS NULL",
+        )
+    }
+
+    /// Conditions shared by the listing queries, values are validated by `safe_parse`.
+    fn filters(parsed_query: &GetPaintingsQueryParsed) -> Vec<(Filter, String)> {
+        let mut filters: Vec<(Filter, String)> = Vec::new();
+
+        if let Some(availability) = parsed_query.availability {
+            filters.push((
+                Filter::Availability,
+                format!("{} = '{}'", AVAILABILITY_SQL, availability.to_str()),
+            ));
+        }
+
+        if let Some(series) = &parsed_query.series {
+            filters.push((Filter::Series, format!(
+                "EXISTS (SELECT 1 FROM rosemary.series_paintings sp JOIN rosemary.series s ON s.id = sp.series_id WHERE sp.painting_id = p.id AND s.slug = '{}')",
+                series
+            )));
+        }
+
+        if let Some(tag) = &parsed_query.tag {
+            filters.push((Filter::Tag, format!(
+                "EXISTS (SELECT 1 FROM rosemary.painting_tags pt JOIN rosemary.tags t ON t.id = pt.tag_id WHERE pt.painting_id = p.id AND t.slug = '{}')",
+                tag
+            )));
+        }
+
+        if let Some(search) = &parsed_query.search {
+            let language = Language::from_code(&parsed_query.lang).unwrap_or(Language::Cs);
+            filters.push((
+                Filter::Search,
+                format!(
+                    "{} @@ {}",
+                    search_column(language),
+                    search_tsquery(language, search)
+                ),
+            ));
+        }
+
+        let ranges = [
+            (
+                Filter::Price,
+                price_sql(&parsed_query.currency),
+                &parsed_query.price,
+            ),
+            (Filter::Width, String::from("p.width"), &parsed_query.width),
+            (
+                Filter::Height,
+                String::from("p.height"),
+                &parsed_query.height,
+            ),
+        ];
+        for (filter, value, range) in ranges {
+            if let Some(condition) = range.as_ref().and_then(|r| range_condition(&value, r)) {
+                filters.push((filter, condition));
+            }
+        }
+
+        if let Some(orientation) = parsed_query.orientation {
+            filters.push((
+                Filter::Orientation,
+                format!("{} = '{}'", ORIENTATION_SQL, orientation.to_str()),
+            ));
+        }
+
+        if let Some(year) = parsed_query.year {
+            filters.push((Filter::Year, format!("p.year_created = {}", year)));
+        }
+
+        filters
+    }
+
+    fn filter_conditions(parsed_query: &GetPaintingsQueryParsed) -> Vec<String> {
+        Self::filters(parsed_query)
+            .into_iter()
+            .map(|(_, condition)| condition)
+            .collect()
+    }
+
+    /// Facet counts and ranges of the listing, every facet is counted with all
+    /// filters except its own so the other options stay visible.
+    pub fn facets_query(parsed_query: &GetPaintingsQueryParsed) -> String {
+        let language = Language::from_code(&parsed_query.lang).unwrap_or(Language::Cs);
+        let lang = language.content().to_str();
+        let filters = Self::filters(parsed_query);
+        let where_except = |skip: Filter| -> String {
+            filters.iter().filter(|(filter, _)| *filter != skip).fold(
+                String::from("p.deleted IS NULL"),
+                |clause, (_, condition)| format!("{} AND {}", clause, condition),
+            )
+        };
+
+        let counts = |value: &str, label: &str, joins: &str, condition: String, order: &str| {
+            format!(
+                r#"(
+				SELECT COALESCE(JSON_AGG(JSON_BUILD_OBJECT('value', f.value, 'label', f.label, 'count', f.count) ORDER BY {order}), '[]'::JSON)
+				FROM (
+					SELECT {value} AS value, {label} AS label, COUNT(DISTINCT p.id) AS count
+					FROM rosemary.paintings p {joins}
+					WHERE {condition}
+					GROUP BY 1, 2
+				) f
+			)"#,
+                order = order,
+                value = value,
+                label = label,
+                joins = joins,
+                condition = condition
+            )
+        };
+
+        let range = |value: &str, condition: String| {
+            format!(
+                r#"(
+				SELECT JSON_BUILD_OBJECT('min', FLOOR(MIN({value}))::BIGINT, 'max', CEIL(MAX({value}))::BIGINT)
+				FROM rosemary.paintings p
+				WHERE {condition}
+			)"#,
+                value = value,
+                condition = condition
+            )
+        };
+
+        let price = price_sql(&parsed_query.currency);
+        format!(
+            r#"
+			SELECT JSON_BUILD_OBJECT(
+				'availability', {},
+				'orientation', {},
+				'year', {},
+				'series', {},
+				'tag', {},
+				'price', {},
+				'currency', '{}',
+				'width', {},
+				'height', {}
+			) AS facets"#,
+            counts(
+                AVAILABILITY_SQL,
+                AVAILABILITY_SQL,
+                "",
+                where_except(Filter::Availability),
+                "f.value"
+            ),
+            counts(
+                ORIENTATION_SQL,
+                ORIENTATION_SQL,
+                "",
+                where_except(Filter::Orientation),
+                "f.value"
+            ),
+            counts(
+                "p.year_created::TEXT",
+                "p.year_created::TEXT",
+                "",
+                format!("p.year_created IS NOT NULL AND {}", where_except(Filter::Year)),
+                "f.value DESC"
+            ),
+            counts(
+                "s.slug",
+                &format!("s.title->>'{}'", lang),
+                "JOIN rosemary.series_paintings sp ON sp.painting_id = p.id JOIN rosemary.series s ON s.id = sp.series_id",
+                where_except(Filter::Series),
+                "f.label"
+            ),
+            counts(
+                "t.slug",
+                &format!("t.name->>'{}'", lang),
+                "JOIN rosemary.painting_tags pt ON pt.painting_id = p.id JOIN rosemary.tags t ON t.id = pt.tag_id",
+                where_except(Filter::Tag),
+                "f.count DESC, f.label"
+            ),
+            range(&price, where_except(Filter::Price)),
+            parsed_query.currency,
+            range("p.width", where_except(Filter::Width)),
+            range("p.height", where_except(Filter::Height)),
+        )
+    }
+
+    pub fn count_all_query(parsed_query: &GetPaintingsQueryParsed) -> String {
+        let mut query = String::from(
+            r#"
 			SELECT COUNT(p.id)
 			FROM rosemary.paintings p
 			WHERE p.deleted IS NULL
-		"#
-        .to_string()
+		"#,
+        );
+
+        for condition in Self::filter_conditions(parsed_query) {
+            query.push_str(&format!(" AND {}", condition));
+        }
+
+        query
     }
 
-    pub fn get_all_query(query: GetPaintingsQuery, language: Option<Language>) -> String {
-        let lang = if let Some(language_enum) = language {
-            language_enum
-        } else {
-            Language::Cs
-        };
-        let parsed_query = query.safe_parse(Some(lang));
+    /// Painting rows with their tags and preview image for the API listings.
+    fn rows_query(
+        parsed_query: &GetPaintingsQueryParsed,
+        language: Language,
+        columns: &str,
+        conditions: &[String],
+        order_by: &str,
+        page: &str,
+    ) -> String {
+        let filters: String = conditions
+            .iter()
+            .map(|condition| format!("AND {} ", condition))
+            .collect();
 
         format!(
             r#"
     	SELECT
     		p.*,
+    		{} AS tags,
+    		{} AS search_rank,{}
     		(JSON_BUILD_OBJECT(
     			'id', pi.id,
     			'preview', pi.preview,
@@ -245,28 +743,119 @@ impl Painting {
     	FROM rosemary.paintings p
     	LEFT JOIN rosemary.painting_images pi ON pi.painting_id = p.id AND pi.preview = TRUE
     	WHERE deleted IS NULL
-    	ORDER BY {} {}
-    	LIMIT {} OFFSET {}
+    	{}
+    	ORDER BY {}
+    	{}
     "#,
-            parsed_query.sort, parsed_query.order, parsed_query.limit, parsed_query.offset
+            TAGS_SQL,
+            search_rank(language, parsed_query.search.as_deref()),
+            columns,
+            filters,
+            order_by,
+            page
         )
     }
-    pub fn get_all_stubs_query(
-        parsed_query: GetPaintingsQueryParsed,
-        language: Option<Language>,
-        base_static_files_url: &str,
-    ) -> String {
+
+    pub fn get_all_query(query: GetPaintingsQuery, language: Option<Language>) -> String {
         let lang = if let Some(language_enum) = language {
             language_enum
         } else {
             Language::Cs
         };
-        let lang_string = lang.to_string();
+        let parsed_query = query.safe_parse(Some(lang));
+
+        Self::rows_query(
+            &parsed_query,
+            lang,
+            "",
+            &Self::filter_conditions(&parsed_query),
+            &format!("{} {}", parsed_query.sort, parsed_query.order),
+            &format!(
+                "LIMIT {} OFFSET {}",
+                parsed_query.limit, parsed_query.offset
+            ),
+        )
+    }
+
+    /// Sort of the listing as an expression that also works in `WHERE`.
+    fn sort_expression(parsed_query: &GetPaintingsQueryParsed) -> String {
+        let language = Language::from_code(&parsed_query.lang).unwrap_or(Language::Cs);
+        match parsed_query.sort.as_str() {
+            "search_rank" => search_rank(language, parsed_query.search.as_deref()),
+            column if column.chars().all(|c| c.is_ascii_lowercase() || c == '_') => {
+                format!("p.{}", column)
+            }
+            expression => expression.to_string(),
+        }
+    }
+
+    /// Rows after the cursor in the direction of the page, NULL sort values come
+    /// last going up and first going down the way Postgres orders them.
+    fn keyset_condition(sort: &str, ascending: bool, cursor: &Cursor) -> String {
+        let id = cursor.id;
+        let operator = if ascending { ">" } else { "<" };
+
+        match (&cursor.value, ascending) {
+            (Some(value), true) => format!(
+                "({sort} {op} '{value}' OR ({sort} = '{value}' AND p.id {op} '{id}') OR {sort} IS NULL)",
+                sort = sort,
+                op = operator,
+                value = escape_sql_string(value),
+                id = id
+            ),
+            (Some(value), false) => format!(
+                "({sort} {op} '{value}' OR ({sort} = '{value}' AND p.id {op} '{id}'))",
+                sort = sort,
+                op = operator,
+                value = escape_sql_string(value),
+                id = id
+            ),
+            (None, true) => format!("({} IS NULL AND p.id {} '{}')", sort, operator, id),
+            (None, false) => format!(
+                "(({sort} IS NULL AND p.id {op} '{id}') OR {sort} IS NOT NULL)",
+                sort = sort,
+                op = operator,
+                id = id
+            ),
+        }
+    }
+
+    /// One page more than `limit` after `cursor`, or from the start without one,
+    /// `sort_value` of the rows is what the next cursors are made of.
+    pub fn get_keyset_query(
+        parsed_query: &GetPaintingsQueryParsed,
+        cursor: Option<&Cursor>,
+    ) -> String {
+        let language = Language::from_code(&parsed_query.lang).unwrap_or(Language::Cs);
+        let sort = Self::sort_expression(parsed_query);
+        let backward = cursor.is_some_and(|c| c.backward);
+        let ascending = (parsed_query.order == "asc") != backward;
+        let direction = if ascending { "ASC" } else { "DESC" };
 
-        let mut select = sql::Select::new()
+        let mut conditions = Self::filter_conditions(parsed_query);
+        if let Some(cursor) = cursor {
+            conditions.push(Self::keyset_condition(&sort, ascending, cursor));
+        }
+
+        Self::rows_query(
+            parsed_query,
+            language,
+            &format!(" ({})::TEXT AS sort_value,", sort),
+            &conditions,
+            &format!("{0} {1}, p.id {1}", sort, direction),
+            &format!("LIMIT {}", u32::from(parsed_query.limit) + 1),
+        )
+    }
+
+    /// Columns of `PaintingStub` apart from the snippet, for paintings that are not deleted.
+    fn stub_select(lang: Language, base_static_files_url: &str) -> sql::Select {
+        let lang_string = lang.content().to_string();
+
+        sql::Select::new()
             .select("p.id AS id")
             .select("p.created AS created")
             .select("p.price AS price")
+            .select("p.currency AS currency")
             .select("CONCAT(p.width, 'cm x ', p.height, 'cm') AS size")
             .select(&format!("painting_title->>'{}' AS title", &lang_string))
             .select(&format!("pi.alt->>'{}' AS preview_alt", &lang_string))
@@ -274,43 +863,139 @@ impl Painting {
                 "CONCAT('{}', pi.urls->>0) AS preview",
                 base_static_files_url
             ))
+            .select(&format!("{} AS availability", AVAILABILITY_SQL))
+            .select(&format!(
+                "(SELECT ps.slug FROM rosemary.painting_slugs ps WHERE ps.painting_id = p.id AND ps.language = '{}' AND ps.current) AS slug",
+                &lang_string
+            ))
             .from("rosemary.paintings p")
             .left_join("rosemary.painting_images pi on pi.painting_id = p.id AND pi.preview = TRUE")
             .where_clause("p.deleted IS NULL")
+    }
+
+    pub fn get_all_stubs_query(
+        parsed_query: GetPaintingsQueryParsed,
+        language: Option<Language>,
+        base_static_files_url: &str,
+    ) -> String {
+        let lang = if let Some(language_enum) = language {
+            language_enum
+        } else {
+            Language::Cs
+        };
+
+        let mut select = Self::stub_select(lang, base_static_files_url)
+            .select(&format!(
+                "{} AS search_rank",
+                search_rank(lang, parsed_query.search.as_deref())
+            ))
+            .select(&match &parsed_query.search {
+                Some(search) => format!("{} AS snippet", search_headline(lang, search)),
+                None => String::from("NULL AS snippet"),
+            })
             .limit(&format!("{}", parsed_query.limit))
             .offset(&format!("{}", parsed_query.offset))
             .order_by(&format!("{} {}", parsed_query.sort, parsed_query.order));
 
-        if let Some(search_value) = &parsed_query.search {
-            select = select
-                .where_clause(&format!(
-                    "painting_title->>'{}' LIKE '%{}%'",
-                    &lang_string, &search_value
-                ))
-                .where_clause(&format!(
-                    "painting_description->>'{}' LIKE '%{}%'",
-                    &lang_string, &search_value
-                ));
+        for condition in Self::filter_conditions(&parsed_query) {
+            select = select.where_clause(&condition);
         }
 
         select.to_string()
     }
 
+    /// Binds: $1 limit
+    ///
+    /// Newest paintings for the feeds, with the description and the largest preview variant.
+    pub fn get_feed_query(language: Language, base_static_files_url: &str) -> String {
+        let lang_string = language.content().to_string();
+
+        Self::stub_select(language, base_static_files_url)
+            .select("NULL AS snippet")
+            .select("p.updated AS updated")
+            .select(&format!(
+                "COALESCE(painting_description->>'{}', '') AS description",
+                &lang_string
+            ))
+            .select(&format!(
+                "'{}' || (pi.urls->>-1) AS image",
+                base_static_files_url
+            ))
+            .order_by("p.created DESC")
+            .limit("$1")
+            .to_string()
+    }
+
+    /// Binds: $1 painting id, $2 limit
+    ///
+    /// Other paintings scored by shared series and tags and by how close their size and
+    /// price are, `RelatedPainting::rank` adds the colour similarity of the previews.
+    pub fn get_related_query(language: Language, base_static_files_url: &str) -> String {
+        let closeness = |value: &str, target: &str| {
+            format!(
+                "GREATEST(0, 1 - ABS(LN(GREATEST({}, 1)::FLOAT8 / GREATEST({}, 1)::FLOAT8)))",
+                value, target
+            )
+        };
+        let score = format!(
+            "{} * (SELECT COUNT(*) FROM rosemary.series_paintings a JOIN rosemary.series_paintings b ON b.series_id = a.series_id WHERE a.painting_id = p.id AND b.painting_id = t.id) + {} * (SELECT COUNT(*) FROM rosemary.painting_tags a JOIN rosemary.painting_tags b ON b.tag_id = a.tag_id WHERE a.painting_id = p.id AND b.painting_id = t.id) + {} + {}",
+            RELATED_SERIES_WEIGHT,
+            RELATED_TAG_WEIGHT,
+            closeness("p.width * p.height", "t.area"),
+            closeness(BASE_PRICE_SQL, "t.base_price"),
+        );
+
+        let target = sql::Select::new()
+            .select("p.id")
+            .select("p.width * p.height AS area")
+            .select(&format!("{} AS base_price", BASE_PRICE_SQL))
+            .select("pi.palette")
+            .from("rosemary.paintings p")
+            .left_join("rosemary.painting_images pi on pi.painting_id = p.id AND pi.preview = TRUE")
+            .where_clause("p.id = $1");
+
+        Self::stub_select(language, base_static_files_url)
+            .with("target", target)
+            .select("NULL AS snippet")
+            .select(&format!("({})::FLOAT8 AS score", score))
+            .select("pi.palette AS palette")
+            .select("t.palette AS target_palette")
+            .cross_join("target t")
+            .where_clause("p.id <> t.id")
+            .order_by("score DESC, p.created DESC")
+            .limit("$2")
+            .to_string()
+    }
+
     pub fn create_query(data: PaintingCreate) -> String {
+        let (attribute_columns, attribute_values): (Vec<_>, Vec<_>) = data
+            .attributes
+            .column_values(false)
+            .into_iter()
+            .map(|(column, value)| {
+                (
+                    format!(",\n\t\t\t\t{}", column),
+                    format!(",\n\t\t\t\t{}", value),
+                )
+            })
+            .unzip();
+
         format!(
             r#"INSERT INTO rosemary.paintings(
 				created,
 				deleted,
 				price,
+				currency,
 				painting_title,
 				painting_description,
 				data,
 				width,
-				height
+				height{}
 			) VALUES (
 				now(),
 				NULL,
 				{},
+				'{}',
 				JSON_BUILD_OBJECT(
 					'cs', '{}',
 					'en', '{}'
@@ -321,16 +1006,22 @@ impl Painting {
 				),
 				JSON_BUILD_OBJECT(),
 				{},
-				{}
+				{}{}
 			)
 			RETURNING *"#,
+            attribute_columns.concat(),
             data.price,
+            data.currency
+                .as_deref()
+                .filter(|code| is_currency_code(code))
+                .unwrap_or(BASE_CURRENCY),
             data.title_cs,
             data.title_en,
             data.description_cs,
             data.description_en,
             data.width,
-            data.height
+            data.height,
+            attribute_values.concat()
         )
     }
 
@@ -342,6 +1033,14 @@ impl Painting {
             values.push(format!("price = {}", value));
         }
 
+        if let Some(value) = data
+            .currency
+            .as_deref()
+            .filter(|code| is_currency_code(code))
+        {
+            values.push(format!("currency = '{}'", value));
+        }
+
         if let (Some(cs), Some(en)) = (data.title_cs.as_ref(), data.title_en.as_ref()) {
             values.push(format!(
 				"painting_title = JSONB_SET(JSONB_SET(painting_title::jsonb, '{{cs}}', '\"{}\"', true), '{{en}}', '\"{}\"', true)",
@@ -380,13 +1079,56 @@ impl Painting {
             values.push(format!("width = {}", value));
         }
 
-        if let Some(value) = data.sold {
+        let availability = data.availability.or(data.sold.map(|sold| {
+            if sold {
+                Availability::Sold
+            } else {
+                Availability::Available
+            }
+        }));
+
+        if let Some(value) = availability {
+            values.push(format!("availability = '{}'", value.to_str()));
+
+            if value == Availability::Reserved {
+                match data.reserved_until {
+                    Some(until) => {
+                        values.push(format!("reserved_until = '{}'", until.to_rfc3339()))
+                    }
+                    None => values.push(String::from("reserved_until = NULL")),
+                }
+            } else {
+                values.push(String::from("reserved_until = NULL"));
+            }
+
+            if value == Availability::Sold {
+                match data.sold_date {
+                    Some(date) => values.push(format!("sold_date = '{}'", date.to_rfc3339())),
+                    None => values.push(String::from("sold_date = COALESCE(sold_date, now())")),
+                }
+            } else {
+                values.push(String::from("sold_date = NULL"));
+                values.push(String::from("buyer_reference = NULL"));
+            }
+        } else if let Some(date) = data.sold_date {
+            values.push(format!("sold_date = '{}'", date.to_rfc3339()));
+        }
+
+        if let Some(value) = data
+            .buyer_reference
+            .filter(|_| availability.is_none_or(|value| value == Availability::Sold))
+        {
             values.push(format!(
-                "data = JSONB_SET(data::jsonb, '{{sold}}', {}, true)",
-                value
+                "buyer_reference = '{}'",
+                escape_sql_string(value.trim())
             ));
         }
 
+        for (column, value) in data.attributes.column_values(true) {
+            values.push(format!("{} = {}", column, value));
+        }
+        values.push(String::from("updated = now()"));
+
         query.push_str(&values.join(", "));
         query.push_str(&format!(
             " WHERE id = '{}' AND deleted IS NULL RETURNING *;",
@@ -408,10 +1150,7 @@ impl Painting {
 
     pub fn get_title(&self, language: Language) -> &str {
         if let Some(title) = &self.painting_title {
-            match language {
-                Language::Cs => title.cs.as_str(),
-                Language::En => title.en.as_str(),
-            }
+            title.get(language)
         } else {
             "Failed to get painting title"
         }
@@ -419,12 +1158,144 @@ impl Painting {
 
     pub fn get_description(&self, language: Language) -> &str {
         if let Some(description) = &self.painting_description {
-            match language {
-                Language::Cs => description.cs.as_str(),
-                Language::En => description.en.as_str(),
-            }
+            description.get(language)
         } else {
             "Failed to get painting description"
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_effective_availability() {
+        let past = Some(Utc::now() - Duration::hours(1));
+        let future = Some(Utc::now() + Duration::hours(1));
+
+        assert_eq!(
+            Availability::effective("reserved", past).unwrap(),
+            Availability::Available
+        );
+        assert_eq!(
+            Availability::effective("reserved", future).unwrap(),
+            Availability::Reserved
+        );
+        assert_eq!(
+            Availability::effective("not_for_sale", None).unwrap(),
+            Availability::NotForSale
+        );
+        assert!(Availability::effective("lost", None).is_err());
+    }
+
+    #[test]
+    fn test_snippet_html() {
+        let stub = PaintingStub {
+            id: Uuid::nil(),
+            created: Utc::now(),
+            price: 0,
+            currency: String::from(BASE_CURRENCY),
+            size: String::new(),
+            title: String::new(),
+            preview_alt: String::new(),
+            preview: String::new(),
+            availability: Availability::Available,
+            slug: None,
+            snippet: Some(String::from("<b>Zimní</b> [[mark]]ráno[[/mark]]")),
+        };
+
+        assert_eq!(
+            stub.snippet_html().unwrap(),
+            "&lt;b&gt;Zimní&lt;/b&gt; <mark>ráno</mark>"
+        );
+    }
+
+    #[test]
+    fn test_search_sorts_by_relevance() {
+        let query = GetPaintingsQuery {
+            search: Some(String::from("  ráno o'clock ")),
+            ..Default::default()
+        };
+
+        let parsed_query = query.safe_parse(Some(Language::En));
+        assert_eq!(parsed_query.sort, "search_rank");
+        assert_eq!(parsed_query.search.as_deref(), Some("ráno o'clock"));
+        assert!(Painting::filter_conditions(&parsed_query)[0]
+            .ends_with("websearch_to_tsquery('rosemary.english_unaccent', 'ráno o''clock')"));
+    }
+
+    #[test]
+    fn test_filter_conditions_skip_invalid_slugs() {
+        let query = GetPaintingsQuery {
+            series: Some(String::from("winter-2021")),
+            tag: Some(String::from("x' OR '1'='1")),
+            ..Default::default()
+        };
+
+        let conditions = Painting::filter_conditions(&query.safe_parse(None));
+        assert_eq!(conditions.len(), 1);
+        assert!(conditions[0].contains("s.slug = 'winter-2021'"));
+    }
+
+    #[test]
+    fn test_facets_leave_out_their_own_filter() {
+        let query = GetPaintingsQuery {
+            availability: Some(String::from("sold")),
+            orientation: Some(String::from("portrait")),
+            width_min: Some(String::from("50")),
+            width_max: Some(String::from("20")),
+            year: Some(String::from("twenty")),
+            ..Default::default()
+        };
+
+        let parsed_query = query.safe_parse(None);
+        assert_eq!(parsed_query.year, None);
+        assert_eq!(
+            parsed_query.width,
+            Some(RangeFilter {
+                min: Some(20),
+                max: Some(50)
+            })
+        );
+
+        let conditions = Painting::filter_conditions(&parsed_query);
+        assert_eq!(conditions.len(), 3);
+        assert!(conditions.contains(&String::from("(p.width >= 20 AND p.width <= 50)")));
+
+        // eight facets, all but the availability one filter by it
+        let facets_query = Painting::facets_query(&parsed_query);
+        assert_eq!(facets_query.matches("END = 'sold'").count(), 7);
+        assert_eq!(facets_query.matches("END = 'portrait'").count(), 7);
+    }
+
+    #[test]
+    fn test_keyset_query_continues_after_cursor() {
+        let query = GetPaintingsQuery {
+            sort: Some(String::from("price")),
+            order: Some(String::from("asc")),
+            limit: Some(10),
+            ..Default::default()
+        };
+        let cursor = Cursor {
+            sort: query.sort.clone(),
+            order: query.order.clone(),
+            value: Some(String::from("1000")),
+            id: Uuid::nil(),
+            backward: true,
+        };
+
+        let first_page = Painting::get_keyset_query(&query.safe_parse(None), None);
+        assert!(first_page.contains("ORDER BY p.price ASC, p.id ASC"));
+        assert!(first_page.contains("LIMIT 11"));
+
+        // going back flips the order, NULL prices come first in it
+        let prev_page = Painting::get_keyset_query(&query.safe_parse(None), Some(&cursor));
+        assert!(prev_page.contains("ORDER BY p.price DESC, p.id DESC"));
+        assert!(prev_page.contains(&format!(
+            "(p.price < '1000' OR (p.price = '1000' AND p.id < '{}'))",
+            Uuid::nil()
+        )));
+    }
+}
@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::prelude::FromRow;
+use sqlx::Row;
+use uuid::Uuid;
+
+/// Painting a slug from the URL belongs to, with the slug it should be served under
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PaintingSlug {
+    pub painting_id: Uuid,
+    pub slug: String,
+    pub current: bool,
+    pub current_slug: Option<String>,
+}
+
+impl<'r> FromRow<'r, PgRow> for PaintingSlug {
+    fn from_row(row: &'r PgRow) -> sqlx::Result<Self> {
+        Ok(Self {
+            painting_id: row.try_get("painting_id")?,
+            slug: row.try_get("slug")?,
+            current: row.try_get("current")?,
+            current_slug: row.try_get("current_slug")?,
+        })
+    }
+}
+
+impl PaintingSlug {
+    /// Binds: $1 language, $2 slug
+    pub fn get_by_slug_query() -> String {
+        String::from(
+            r#"
+			SELECT
+				s.painting_id,
+				s.slug,
+				s.current,
+				(
+					SELECT c.slug
+					FROM rosemary.painting_slugs c
+					WHERE c.painting_id = s.painting_id AND c.language = s.language AND c.current
+				) AS current_slug
+			FROM rosemary.painting_slugs s
+			JOIN rosemary.paintings p ON p.id = s.painting_id AND p.deleted IS NULL
+			WHERE s.language = $1 AND s.slug = $2"#,
+        )
+    }
+
+    /// Binds: $1 painting id, $2 language
+    pub fn get_current_query() -> String {
+        String::from(
+            r#"
+			SELECT s.painting_id, s.slug, s.current, s.slug AS current_slug
+			FROM rosemary.painting_slugs s
+			JOIN rosemary.paintings p ON p.id = s.painting_id AND p.deleted IS NULL
+			WHERE s.painting_id = $1 AND s.language = $2 AND s.current"#,
+        )
+    }
+
+    /// Binds: $1 painting id
+    ///
+    /// Language and current slug pairs of the painting.
+    pub fn get_all_current_query() -> String {
+        String::from(
+            r#"
+			SELECT s.language, s.slug
+			FROM rosemary.painting_slugs s
+			WHERE s.painting_id = $1 AND s.current"#,
+        )
+    }
+
+    /// Binds: $1 painting id, $2 language, $3 base slug
+    ///
+    /// Slugs other paintings already use that `unique_slug` has to avoid.
+    pub fn get_taken_query() -> String {
+        String::from(
+            r#"
+			SELECT slug
+			FROM rosemary.painting_slugs
+			WHERE painting_id <> $1
+				AND language = $2
+				AND (slug = $3 OR slug LIKE $3 || '-%')"#,
+        )
+    }
+
+    /// Binds: $1 painting id, $2 language, $3 slug
+    pub fn retire_query() -> String {
+        String::from(
+            r#"
+			UPDATE rosemary.painting_slugs
+			SET current = FALSE
+			WHERE painting_id = $1 AND language = $2 AND slug <> $3 AND current"#,
+        )
+    }
+
+    /// Binds: $1 painting id, $2 language, $3 slug
+    ///
+    /// A slug the painting used before becomes current again, no row is returned when
+    /// another painting holds the slug.
+    pub fn upsert_query() -> String {
+        String::from(
+            r#"
+			INSERT INTO rosemary.painting_slugs(painting_id, language, slug, current)
+			VALUES ($1, $2, $3, TRUE)
+			ON CONFLICT (language, slug) DO UPDATE
+			SET current = TRUE
+			WHERE rosemary.painting_slugs.painting_id = EXCLUDED.painting_id
+			RETURNING slug"#,
+        )
+    }
+
+    /// Paintings missing a current slug in any language
+    pub fn get_missing_query() -> String {
+        String::from(
+            r#"
+			SELECT p.id, p.painting_title
+			FROM rosemary.paintings p
+			WHERE (
+				SELECT COUNT(s.slug)
+				FROM rosemary.painting_slugs s
+				WHERE s.painting_id = p.id AND s.current
+			) < 2"#,
+        )
+    }
+}
@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::prelude::FromRow;
+use sqlx::types::JsonValue;
+use sqlx::Row;
+
+use crate::database::models::painting::PaintingStub;
+use crate::utils::images::palette::{palette_similarity, PaletteColor};
+
+/// Related paintings shown on the detail page and returned by the API
+pub const RELATED_LIMIT: usize = 4;
+
+/// Best scored candidates of `Painting::get_related_query` compared by colour
+pub const RELATED_CANDIDATES: i64 = 40;
+
+/// Score of previews with the same colours, falls to 0 for opposite ones
+const PALETTE_WEIGHT: f64 = 2.0;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RelatedPainting {
+    #[serde(flatten)]
+    pub stub: PaintingStub,
+    pub score: f64,
+    #[serde(skip)]
+    pub palette: Vec<PaletteColor>,
+    /// palette of the painting the related ones are looked up for
+    #[serde(skip)]
+    pub target_palette: Vec<PaletteColor>,
+}
+
+fn palette_from_json(value: Option<JsonValue>) -> Vec<PaletteColor> {
+    value
+        .and_then(|json| serde_json::from_value(json).ok())
+        .unwrap_or_default()
+}
+
+impl<'r> FromRow<'r, PgRow> for RelatedPainting {
+    fn from_row(row: &'r PgRow) -> sqlx::Result<Self> {
+        Ok(Self {
+            stub: PaintingStub::from_row(row)?,
+            score: row.try_get("score")?,
+            palette: palette_from_json(row.try_get("palette")?),
+            target_palette: palette_from_json(row.try_get("target_palette")?),
+        })
+    }
+}
+
+impl RelatedPainting {
+    /// Adds the colour similarity to the database score and keeps the best `limit`.
+    pub fn rank(mut candidates: Vec<RelatedPainting>, limit: usize) -> Vec<RelatedPainting> {
+        for candidate in candidates.iter_mut() {
+            let similarity = palette_similarity(&candidate.palette, &candidate.target_palette);
+            candidate.score += PALETTE_WEIGHT * f64::from(similarity);
+        }
+
+        candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+        candidates.truncate(limit);
+        candidates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::models::painting::Availability;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn candidate(title: &str, score: f64, rgb: [u8; 3]) -> RelatedPainting {
+        RelatedPainting {
+            stub: PaintingStub {
+                id: Uuid::new_v4(),
+                created: Utc::now(),
+                price: 1000,
+                currency: String::from("CZK"),
+                size: String::from("50cm x 70cm"),
+                title: title.to_string(),
+                preview_alt: String::new(),
+                preview: String::new(),
+                availability: Availability::Available,
+                slug: None,
+                snippet: None,
+            },
+            score,
+            palette: vec![PaletteColor { rgb, share: 1.0 }],
+            target_palette: vec![PaletteColor {
+                rgb: [200, 30, 30],
+                share: 1.0,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_rank_adds_palette_similarity() {
+        let ranked = RelatedPainting::rank(
+            vec![
+                candidate("blue", 2.0, [20, 20, 200]),
+                candidate("red", 1.5, [190, 40, 30]),
+                candidate("green", 0.5, [30, 200, 30]),
+            ],
+            2,
+        );
+
+        let titles: Vec<&str> = ranked.iter().map(|r| r.stub.title.as_str()).collect();
+        assert_eq!(titles, vec!["red", "blue"]);
+    }
+}
@@ -0,0 +1,192 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::prelude::FromRow;
+use sqlx::types::JsonValue;
+use sqlx::Row;
+use uuid::Uuid;
+
+use crate::client::translations::Language;
+use crate::database::models::generics::Translation;
+use crate::utils::slug::is_slug;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SeriesCreate {
+    pub slug: String,
+    pub title_cs: String,
+    pub title_en: String,
+    pub description_cs: Option<String>,
+    pub description_en: Option<String>,
+    pub cover_image_id: Option<Uuid>,
+    pub position: Option<i32>,
+}
+
+impl SeriesCreate {
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if !is_slug(&self.slug) {
+            return Err("invalidSlug");
+        }
+        if self.title_cs.trim().is_empty() || self.title_en.trim().is_empty() {
+            return Err("invalidTitle");
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SeriesUpdate {
+    pub slug: Option<String>,
+    pub title_cs: Option<String>,
+    pub title_en: Option<String>,
+    pub description_cs: Option<String>,
+    pub description_en: Option<String>,
+    pub cover_image_id: Option<Uuid>,
+    pub position: Option<i32>,
+}
+
+impl SeriesUpdate {
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if self.slug.as_deref().is_some_and(|slug| !is_slug(slug)) {
+            return Err("invalidSlug");
+        }
+        let blank = |value: &Option<String>| value.as_ref().is_some_and(|v| v.trim().is_empty());
+        if blank(&self.title_cs) || blank(&self.title_en) {
+            return Err("invalidTitle");
+        }
+        Ok(())
+    }
+}
+
+/// Paintings of a series in the order they should be shown
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SeriesPaintingsUpdate {
+    pub painting_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Series {
+    pub id: Uuid,
+    pub created: DateTime<Utc>,
+    pub slug: String,
+    pub title: Translation,
+    pub description: Translation,
+    pub cover_image_id: Option<Uuid>,
+    /// path of the smallest cover image variant, relative to the static file url
+    pub cover: Option<String>,
+    pub position: i32,
+    pub painting_count: i64,
+}
+
+impl<'r> FromRow<'r, PgRow> for Series {
+    fn from_row(row: &'r PgRow) -> sqlx::Result<Self> {
+        let title_json: JsonValue = row.try_get("title")?;
+        let description_json: JsonValue = row.try_get("description")?;
+
+        Ok(Self {
+            id: row.try_get("id")?,
+            created: row.try_get("created")?,
+            slug: row.try_get("slug")?,
+            title: serde_json::from_value(title_json)
+                .map_err(|err| sqlx::Error::Decode(Box::new(err)))?,
+            description: serde_json::from_value(description_json)
+                .map_err(|err| sqlx::Error::Decode(Box::new(err)))?,
+            cover_image_id: row.try_get("cover_image_id")?,
+            cover: row.try_get("cover")?,
+            position: row.try_get("position")?,
+            painting_count: row.try_get("painting_count")?,
+        })
+    }
+}
+
+impl Series {
+    /// Series rows of `table` with their cover and painting count.
+    fn select_query(table: &str, condition: &str) -> String {
+        format!(
+            r#"
+			SELECT
+				s.*,
+				pi.urls->>0 AS cover,
+				(SELECT COUNT(sp.painting_id) FROM rosemary.series_paintings sp WHERE sp.series_id = s.id) AS painting_count
+			FROM {} s
+			LEFT JOIN rosemary.painting_images pi ON pi.id = s.cover_image_id
+			WHERE {}
+			ORDER BY s.position, s.created"#,
+            table, condition
+        )
+    }
+
+    pub fn get_all_query() -> String {
+        Self::select_query("rosemary.series", "TRUE")
+    }
+
+    /// Binds: $1 slug
+    pub fn get_by_slug_query() -> String {
+        Self::select_query("rosemary.series", "s.slug = $1")
+    }
+
+    /// Binds: $1 id
+    pub fn get_by_id_query() -> String {
+        Self::select_query("rosemary.series", "s.id = $1")
+    }
+
+    /// Binds: $1 slug, $2 title, $3 description, $4 cover_image_id, $5 position
+    pub fn create_query() -> String {
+        format!(
+            r#"
+			WITH changed AS (
+				INSERT INTO rosemary.series(slug, title, description, cover_image_id, position)
+				VALUES ($1, $2, $3, $4, COALESCE($5, 0))
+				RETURNING *
+			){}"#,
+            Self::select_query("changed", "TRUE")
+        )
+    }
+
+    /// Binds: $1 id, $2 slug, $3 title patch, $4 description patch, $5 cover_image_id, $6 position
+    pub fn update_query() -> String {
+        format!(
+            r#"
+			WITH changed AS (
+				UPDATE rosemary.series
+				SET slug = COALESCE($2, slug),
+					title = title || $3,
+					description = description || $4,
+					cover_image_id = COALESCE($5, cover_image_id),
+					position = COALESCE($6, position)
+				WHERE id = $1
+				RETURNING *
+			){}"#,
+            Self::select_query("changed", "TRUE")
+        )
+    }
+
+    /// Binds: $1 id
+    pub fn delete_query() -> String {
+        String::from("DELETE FROM rosemary.series WHERE id = $1 RETURNING id")
+    }
+
+    /// Binds: $1 series id
+    pub fn clear_paintings_query() -> String {
+        String::from("DELETE FROM rosemary.series_paintings WHERE series_id = $1")
+    }
+
+    /// Binds: $1 series id, $2 painting ids in order, unknown paintings are skipped
+    pub fn add_paintings_query() -> String {
+        String::from(
+            r#"
+			INSERT INTO rosemary.series_paintings(series_id, painting_id, position)
+			SELECT $1, t.painting_id, t.position::INTEGER
+			FROM UNNEST($2::UUID[]) WITH ORDINALITY AS t(painting_id, position)
+			JOIN rosemary.paintings p ON p.id = t.painting_id AND p.deleted IS NULL
+			ON CONFLICT (series_id, painting_id) DO NOTHING"#,
+        )
+    }
+
+    pub fn get_title(&self, language: Language) -> &str {
+        self.title.get(language)
+    }
+
+    pub fn get_description(&self, language: Language) -> &str {
+        self.description.get(language)
+    }
+}
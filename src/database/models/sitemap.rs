@@ -0,0 +1,58 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::prelude::FromRow;
+use sqlx::types::JsonValue;
+use sqlx::Row;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Painting detail page as the sitemap lists it
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SitemapPainting {
+    pub id: Uuid,
+    pub updated: DateTime<Utc>,
+    /// current slug by language code
+    pub slugs: HashMap<String, String>,
+    /// paths of the largest variant of every processed image, the preview first
+    pub images: Vec<String>,
+}
+
+impl<'r> FromRow<'r, PgRow> for SitemapPainting {
+    fn from_row(row: &'r PgRow) -> sqlx::Result<Self> {
+        let slugs_json: JsonValue = row.try_get("slugs")?;
+
+        Ok(Self {
+            id: row.try_get("id")?,
+            updated: row.try_get("updated")?,
+            slugs: serde_json::from_value(slugs_json)
+                .map_err(|err| sqlx::Error::Decode(Box::new(err)))?,
+            images: row.try_get("images")?,
+        })
+    }
+}
+
+impl SitemapPainting {
+    pub fn get_all_query() -> String {
+        String::from(
+            r#"
+			SELECT
+				p.id,
+				p.updated,
+				COALESCE((
+					SELECT JSONB_OBJECT_AGG(s.language, s.slug)
+					FROM rosemary.painting_slugs s
+					WHERE s.painting_id = p.id AND s.current
+				), '{}'::JSONB) AS slugs,
+				ARRAY(
+					SELECT pi.urls->>-1
+					FROM rosemary.painting_images pi
+					WHERE pi.painting_id = p.id AND pi.status = 'PROCESSED' AND pi.urls->>-1 IS NOT NULL
+					ORDER BY pi.preview DESC, pi.id
+				) AS images
+			FROM rosemary.paintings p
+			WHERE p.deleted IS NULL
+			ORDER BY p.created"#,
+        )
+    }
+}
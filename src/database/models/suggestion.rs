@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::prelude::FromRow;
+use sqlx::Row;
+
+use crate::client::translations::Language;
+
+/// Most suggestions returned for one query
+pub const SUGGESTION_LIMIT: i64 = 8;
+
+/// Painting title, tag or series matching what the visitor typed so far
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Suggestion {
+    /// "painting", "tag" or "series"
+    pub kind: String,
+    pub label: String,
+    /// page of the painting or series, gallery filtered by the tag
+    pub url: String,
+    pub score: f32,
+}
+
+impl<'r> FromRow<'r, PgRow> for Suggestion {
+    fn from_row(row: &'r PgRow) -> sqlx::Result<Self> {
+        Ok(Self {
+            kind: row.try_get("kind")?,
+            label: row.try_get("label")?,
+            url: row.try_get("url")?,
+            score: row.try_get("score")?,
+        })
+    }
+}
+
+impl Suggestion {
+    /// Binds: $1 query, $2 limit
+    ///
+    /// Ranked by trigram word similarity, a prefix or substring match lifts the score.
+    pub fn suggest_query(language: Language) -> String {
+        let lang = language.content().to_str();
+        let path_lang = language.to_str();
+        format!(
+            r#"
+			WITH input AS (
+				SELECT rosemary.suggest_text($1) AS q
+			),
+			candidates AS (
+				SELECT
+					'painting' AS kind,
+					p.painting_title::jsonb->>'{lang}' AS label,
+					CONCAT('/{path_lang}/gallery/', COALESCE(s.slug, p.id::TEXT)) AS url,
+					rosemary.suggest_text(p.painting_title::jsonb->>'{lang}') AS normalized
+				FROM rosemary.paintings p
+				LEFT JOIN rosemary.painting_slugs s
+					ON s.painting_id = p.id AND s.language = '{lang}' AND s.current
+				WHERE p.deleted IS NULL
+				UNION ALL
+				SELECT
+					'tag',
+					t.name->>'{lang}',
+					CONCAT('/{path_lang}/gallery?tag=', t.slug),
+					rosemary.suggest_text(t.name->>'{lang}')
+				FROM rosemary.tags t
+				UNION ALL
+				SELECT
+					'series',
+					r.title->>'{lang}',
+					CONCAT('/{path_lang}/series/', r.slug),
+					rosemary.suggest_text(r.title->>'{lang}')
+				FROM rosemary.series r
+			)
+			SELECT
+				c.kind,
+				c.label,
+				c.url,
+				(
+					word_similarity(input.q, c.normalized)
+					+ CASE
+						WHEN starts_with(c.normalized, input.q) THEN 0.5
+						WHEN strpos(c.normalized, input.q) > 0 THEN 0.25
+						ELSE 0
+					END
+				)::REAL AS score
+			FROM candidates c
+			CROSS JOIN input
+			WHERE c.label <> '' AND (input.q <% c.normalized OR strpos(c.normalized, input.q) > 0)
+			ORDER BY score DESC, c.label
+			LIMIT $2"#,
+        )
+    }
+}
@@ -0,0 +1,161 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::prelude::FromRow;
+use sqlx::types::JsonValue;
+use sqlx::Row;
+use uuid::Uuid;
+
+use crate::client::translations::Language;
+use crate::database::models::generics::Translation;
+use crate::utils::slug::is_slug;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TagCreate {
+    pub slug: String,
+    pub name_cs: String,
+    pub name_en: String,
+}
+
+impl TagCreate {
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if !is_slug(&self.slug) {
+            return Err("invalidSlug");
+        }
+        if self.name_cs.trim().is_empty() || self.name_en.trim().is_empty() {
+            return Err("invalidName");
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TagUpdate {
+    pub slug: Option<String>,
+    pub name_cs: Option<String>,
+    pub name_en: Option<String>,
+}
+
+impl TagUpdate {
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if self.slug.as_deref().is_some_and(|slug| !is_slug(slug)) {
+            return Err("invalidSlug");
+        }
+        let blank = |value: &Option<String>| value.as_ref().is_some_and(|v| v.trim().is_empty());
+        if blank(&self.name_cs) || blank(&self.name_en) {
+            return Err("invalidName");
+        }
+        Ok(())
+    }
+}
+
+/// Replaces all tags of a painting
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PaintingTagsUpdate {
+    pub tag_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Tag {
+    pub id: Uuid,
+    pub created: DateTime<Utc>,
+    pub slug: String,
+    pub name: Translation,
+    pub painting_count: i64,
+}
+
+impl<'r> FromRow<'r, PgRow> for Tag {
+    fn from_row(row: &'r PgRow) -> sqlx::Result<Self> {
+        let name_json: JsonValue = row.try_get("name")?;
+
+        Ok(Self {
+            id: row.try_get("id")?,
+            created: row.try_get("created")?,
+            slug: row.try_get("slug")?,
+            name: serde_json::from_value(name_json)
+                .map_err(|err| sqlx::Error::Decode(Box::new(err)))?,
+            painting_count: row.try_get("painting_count")?,
+        })
+    }
+}
+
+impl Tag {
+    /// Tag rows of `table` with the number of paintings carrying them.
+    fn select_query(table: &str, condition: &str) -> String {
+        format!(
+            r#"
+			SELECT
+				t.*,
+				(SELECT COUNT(pt.painting_id) FROM rosemary.painting_tags pt WHERE pt.tag_id = t.id) AS painting_count
+			FROM {} t
+			WHERE {}
+			ORDER BY t.slug"#,
+            table, condition
+        )
+    }
+
+    pub fn get_all_query() -> String {
+        Self::select_query("rosemary.tags", "TRUE")
+    }
+
+    /// Binds: $1 painting id
+    pub fn get_for_painting_query() -> String {
+        Self::select_query(
+            "rosemary.tags",
+            "t.id IN (SELECT tag_id FROM rosemary.painting_tags WHERE painting_id = $1)",
+        )
+    }
+
+    /// Binds: $1 slug, $2 name
+    pub fn create_query() -> String {
+        format!(
+            r#"
+			WITH changed AS (
+				INSERT INTO rosemary.tags(slug, name)
+				VALUES ($1, $2)
+				RETURNING *
+			){}"#,
+            Self::select_query("changed", "TRUE")
+        )
+    }
+
+    /// Binds: $1 id, $2 slug, $3 name patch
+    pub fn update_query() -> String {
+        format!(
+            r#"
+			WITH changed AS (
+				UPDATE rosemary.tags
+				SET slug = COALESCE($2, slug), name = name || $3
+				WHERE id = $1
+				RETURNING *
+			){}"#,
+            Self::select_query("changed", "TRUE")
+        )
+    }
+
+    /// Binds: $1 id
+    pub fn delete_query() -> String {
+        String::from("DELETE FROM rosemary.tags WHERE id = $1 RETURNING id")
+    }
+
+    /// Binds: $1 painting id
+    pub fn clear_painting_query() -> String {
+        String::from("DELETE FROM rosemary.painting_tags WHERE painting_id = $1")
+    }
+
+    /// Binds: $1 painting id, $2 tag ids, unknown tags are skipped
+    pub fn add_to_painting_query() -> String {
+        String::from(
+            r#"
+			INSERT INTO rosemary.painting_tags(painting_id, tag_id)
+			SELECT $1, t.id
+			FROM rosemary.tags t
+			WHERE t.id = ANY($2::UUID[])
+			ON CONFLICT (painting_id, tag_id) DO NOTHING"#,
+        )
+    }
+
+    pub fn get_name(&self, language: Language) -> &str {
+        self.name.get(language)
+    }
+}
@@ -0,0 +1,56 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::prelude::FromRow;
+use sqlx::Row;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranslationOverrideUpdate {
+    /// Fluent value of the message, placeables and selects included
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranslationOverride {
+    pub language: String,
+    /// message id in the Fluent files, "index-description"
+    pub key: String,
+    pub value: String,
+    pub updated: DateTime<Utc>,
+}
+
+impl<'r> FromRow<'r, PgRow> for TranslationOverride {
+    fn from_row(row: &'r PgRow) -> sqlx::Result<Self> {
+        Ok(Self {
+            language: row.try_get("language")?,
+            key: row.try_get("key")?,
+            value: row.try_get("value")?,
+            updated: row.try_get("updated")?,
+        })
+    }
+}
+
+impl TranslationOverride {
+    pub fn get_all_query() -> String {
+        String::from("SELECT * FROM rosemary.translation_overrides ORDER BY language, key")
+    }
+
+    /// Binds: $1 language, $2 key, $3 value
+    pub fn upsert_query() -> String {
+        String::from(
+            r#"
+			INSERT INTO rosemary.translation_overrides(language, key, value, updated)
+			VALUES ($1, $2, $3, now())
+			ON CONFLICT (language, key) DO UPDATE
+			SET value = EXCLUDED.value, updated = now()
+			RETURNING *"#,
+        )
+    }
+
+    /// Binds: $1 language, $2 key
+    pub fn delete_query() -> String {
+        String::from(
+            "DELETE FROM rosemary.translation_overrides WHERE language = $1 AND key = $2 RETURNING *",
+        )
+    }
+}
@@ -0,0 +1,150 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::postgres::PgRow;
+use sqlx::prelude::FromRow;
+use sqlx::Row;
+use uuid::Uuid;
+
+use crate::database::models::image::ImageMetaPart;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImageUploadCreate {
+    pub painting_id: Uuid,
+    pub upload_length: i64,
+    pub file_name: String,
+    pub content_type: String,
+    pub preview: Option<bool>,
+    pub title_cs: Option<String>,
+    pub title_en: Option<String>,
+    pub alt_cs: Option<String>,
+    pub alt_en: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImageUpload {
+    pub id: Uuid,
+    pub created: DateTime<Utc>,
+    pub expires: DateTime<Utc>,
+    pub painting_id: Uuid,
+    pub file_name: String,
+    pub content_type: String,
+    pub upload_length: i64,
+    pub upload_offset: i64,
+    #[serde(skip_serializing)]
+    pub file_path: String,
+    pub meta: ImageMetaPart,
+}
+
+impl<'r> FromRow<'r, PgRow> for ImageUpload {
+    fn from_row(row: &'r PgRow) -> sqlx::Result<Self> {
+        let meta_json: Value = row.try_get("meta")?;
+        let meta: ImageMetaPart =
+            serde_json::from_value(meta_json).map_err(|err| sqlx::Error::Decode(Box::new(err)))?;
+
+        Ok(Self {
+            id: row.try_get("id")?,
+            created: row.try_get("created")?,
+            expires: row.try_get("expires")?,
+            painting_id: row.try_get("painting_id")?,
+            file_name: row.try_get("file_name")?,
+            content_type: row.try_get("content_type")?,
+            upload_length: row.try_get("upload_length")?,
+            upload_offset: row.try_get("upload_offset")?,
+            file_path: row.try_get("file_path")?,
+            meta,
+        })
+    }
+}
+
+impl ImageUpload {
+    pub fn is_complete(&self) -> bool {
+        self.upload_offset == self.upload_length
+    }
+
+    /// Binds: $1 id, $2 painting_id, $3 file_name, $4 content_type, $5 upload_length,
+    /// $6 file_path, $7 meta
+    pub fn create_query(expiration_hours: i64) -> String {
+        format!(
+            r#"
+			INSERT INTO rosemary.image_uploads(
+				id,
+				expires,
+				painting_id,
+				file_name,
+				content_type,
+				upload_length,
+				upload_offset,
+				file_path,
+				meta
+			) VALUES (
+				$1,
+				now() + INTERVAL '{} hours',
+				$2,
+				$3,
+				$4,
+				$5,
+				0,
+				$6,
+				$7
+			) RETURNING *"#,
+            expiration_hours
+        )
+    }
+
+    pub fn get_by_id_query(id: Uuid) -> String {
+        format!(
+            r#"
+			SELECT *
+			FROM rosemary.image_uploads
+			WHERE id = '{}'
+				AND expires > now()
+			LIMIT 1
+		"#,
+            id
+        )
+    }
+
+    /// Locks the upload until the transaction ends, chunks of one upload are written one at a time.
+    pub fn lock_query(id: Uuid) -> String {
+        format!(
+            r#"
+			SELECT *
+			FROM rosemary.image_uploads
+			WHERE id = '{}'
+				AND expires > now()
+			FOR UPDATE"#,
+            id
+        )
+    }
+
+    /// Moves the offset only when nobody else has moved it in the meantime.
+    pub fn update_offset_query(
+        id: Uuid,
+        previous_offset: i64,
+        new_offset: i64,
+        expiration_hours: i64,
+    ) -> String {
+        format!(
+            r#"
+			UPDATE rosemary.image_uploads SET
+				upload_offset = {},
+				expires = now() + INTERVAL '{} hours'
+			WHERE id = '{}'
+				AND upload_offset = {}
+			RETURNING *"#,
+            new_offset, expiration_hours, id, previous_offset
+        )
+    }
+
+    pub fn delete_query(id: Uuid) -> String {
+        format!(
+            "DELETE FROM rosemary.image_uploads WHERE id = '{}' RETURNING *",
+            id
+        )
+    }
+
+    pub fn delete_expired_query() -> String {
+        String::from("DELETE FROM rosemary.image_uploads WHERE expires <= now() RETURNING *")
+    }
+}
@@ -130,6 +130,66 @@ impl NotFoundError {
 
 impl reject::Reject for NotFoundError {}
 
+/// # ConflictError
+#[derive(Debug, Serialize)]
+pub struct ConflictError {
+    status: Status,
+    #[serde(skip_serializing)]
+    status_code: StatusCode,
+    message: String,
+}
+
+impl ConflictError {
+    pub fn new(error: Option<&str>) -> Self {
+        Self {
+            status: Status::Error,
+            status_code: StatusCode::CONFLICT,
+            message: error.unwrap_or("conflict").to_string(),
+        }
+    }
+
+    pub async fn response(&self) -> WithStatus<warp::reply::Json> {
+        let response = GenericResponse::<ConflictError> {
+            status: self.status,
+            message: &self.message,
+            data: None,
+        };
+        warp::reply::with_status(warp::reply::json(&response), self.status_code)
+    }
+}
+
+impl reject::Reject for ConflictError {}
+
+/// # TooManyRequestsError
+#[derive(Debug, Serialize)]
+pub struct TooManyRequestsError {
+    status: Status,
+    #[serde(skip_serializing)]
+    status_code: StatusCode,
+    message: String,
+}
+
+impl TooManyRequestsError {
+    pub fn new() -> Self {
+        Self {
+            status: Status::Error,
+            status_code: StatusCode::TOO_MANY_REQUESTS,
+            message: String::from("tooManyRequests"),
+        }
+    }
+
+    pub async fn response(&self) -> WithStatus<warp::reply::Json> {
+        let response = GenericResponse::<TooManyRequestsError> {
+            status: self.status,
+            message: &self.message,
+            data: None,
+        };
+        warp::reply::with_status(warp::reply::json(&response), self.status_code)
+    }
+}
+
+impl reject::Reject for TooManyRequestsError {}
+
 /// # InternalServerError
 #[derive(Debug, Serialize)]
 pub struct InternalServerError {
@@ -219,6 +279,10 @@ pub async fn handle_rejection(error: Rejection) -> Result<impl warp::Reply, Reje
         return Ok(unauthorized_error.response().await);
     } else if let Some(validation_error) = error.find::<ValidationError>() {
         return Ok(validation_error.response().await);
+    } else if let Some(conflict_error) = error.find::<ConflictError>() {
+        return Ok(conflict_error.response().await);
+    } else if let Some(too_many_requests_error) = error.find::<TooManyRequestsError>() {
+        return Ok(too_many_requests_error.response().await);
     } else if let Some(internal_server_error) = error.find::<InternalServerError>() {
         return Ok(internal_server_error.response().await);
     } else if let Some(token_expired_error) = error.find::<TokenExpiredError>() {
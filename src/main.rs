@@ -1,3 +1,6 @@
+// the router is one deeply nested warp filter type
+#![recursion_limit = "256"]
+
 use sqlx::{Pool, Postgres};
 use std::net::{Ipv4Addr, SocketAddr};
 use warp::Filter;
@@ -20,10 +23,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let _test_config = config::load::test().await?;
     debug!("app config loaded and tested");
 
+    let languages = client::translations::init_translations()?;
+    debug!(target: "app", "Translations loaded for {} languages", languages);
+
     debug!(target: "app", "Database connecting");
     let client: Pool<Postgres> = database::connection::init_connection().await?;
     debug!(target: "app", "Database connected");
 
+    utils::mail::init_mailer().await?;
+    debug!(target: "app", "Mailer initialized");
+
     utils::auth::token::set_keys().await?;
     debug!(target: "app", "JWT secrets initialized");
 
@@ -35,6 +44,27 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     assert_eq!(rows.0, 150_i64);
     debug!(target: "app", "Database connection checked");
 
+    utils::money::init_rates().await?;
+    debug!(target: "app", "Exchange rates loaded");
+
+    let overrides = client::translations::load_overrides().await?;
+    debug!(target: "app", "{} translation overrides loaded", overrides);
+
+    utils::slug::init_slugs().await?;
+    debug!(target: "app", "Painting slugs checked");
+
+    utils::images::palette::init_palettes().await?;
+    debug!(target: "app", "Image palettes checked");
+
+    let assets = utils::assets::init_assets();
+    debug!(target: "app", "{} static assets fingerprinted", assets);
+
+    utils::uploads::spawn_expiry_task();
+    debug!(target: "app", "Upload expiry task started");
+
+    utils::http_cache::spawn_reservation_task();
+    debug!(target: "app", "Reservation expiry task started");
+
     let routes = requests::router::router().recover(errors::api_error::handle_rejection);
     debug!(target: "app", "Router routes initialized");
 
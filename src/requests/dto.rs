@@ -1,4 +1,7 @@
 // Request DTOs
+pub mod cursor;
+pub mod faceted_result;
 pub mod generic_response;
 pub mod get_paintings_query;
 pub mod paginated_result;
+pub mod suggest_query;
@@ -0,0 +1,54 @@
+use serde_derive::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::utils::hex::{from_hex, to_hex};
+
+/// Position in a keyset paginated listing, handed to clients as an opaque string.
+///
+/// The page continues after (or before, when `backward`) the row with `id`
+/// and sort value `value`, so rows inserted meanwhile do not shift it.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Cursor {
+    /// `sort` of the query the cursor was issued for
+    #[serde(rename = "s")]
+    pub sort: Option<String>,
+    #[serde(rename = "o")]
+    pub order: Option<String>,
+    /// sort value of the row as Postgres prints it, none when it is NULL
+    #[serde(rename = "v")]
+    pub value: Option<String>,
+    #[serde(rename = "i")]
+    pub id: Uuid,
+    #[serde(rename = "b")]
+    pub backward: bool,
+}
+
+impl Cursor {
+    pub fn encode(&self) -> String {
+        to_hex(&serde_json::to_vec(self).expect("Cursor is always serializable"))
+    }
+
+    pub fn decode(value: &str) -> Option<Cursor> {
+        serde_json::from_slice(&from_hex(value)?).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let cursor = Cursor {
+            sort: Some(String::from("price")),
+            order: None,
+            value: Some(String::from("12500")),
+            id: Uuid::new_v4(),
+            backward: true,
+        };
+
+        assert_eq!(Cursor::decode(&cursor.encode()), Some(cursor));
+        assert_eq!(Cursor::decode("7b7d"), None);
+        assert_eq!(Cursor::decode("not a cursor"), None);
+    }
+}
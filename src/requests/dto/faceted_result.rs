@@ -0,0 +1,14 @@
+use serde_derive::{Deserialize, Serialize};
+
+use crate::database::models::facets::Facets;
+use crate::requests::dto::paginated_result::PaginatedResult;
+
+/// `PaginatedResult` with the filter options of the listing
+#[derive(Deserialize, Serialize, Debug)]
+pub struct FacetedResult<T> {
+    #[serde(flatten)]
+    pub page: PaginatedResult<T>,
+    /// sent along with `count`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub facets: Option<Facets>,
+}
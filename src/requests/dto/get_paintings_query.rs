@@ -1,6 +1,43 @@
 use serde_derive::{Deserialize, Serialize};
 
 use crate::client::translations::Language;
+use crate::database::models::painting::{Availability, Orientation};
+use crate::requests::dto::cursor::Cursor;
+use crate::utils::money::{display_currency, is_currency_code, BASE_CURRENCY};
+use crate::utils::slug::is_slug;
+
+/// Longer search input is cut off
+const MAX_SEARCH_LENGTH: usize = 200;
+
+/// Largest page the API serves, the gallery falls back to 25 above it
+pub const MAX_LIMIT: u32 = 100;
+
+/// Inclusive range of a numeric filter, either end may be open.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RangeFilter {
+    pub min: Option<u32>,
+    pub max: Option<u32>,
+}
+
+impl RangeFilter {
+    /// Ends that are not numbers are dropped, swapped ends are put back in order.
+    fn parse(min: &Option<String>, max: &Option<String>) -> Option<RangeFilter> {
+        let number = |value: &Option<String>| value.as_deref().and_then(|v| v.trim().parse().ok());
+        let range = match (number(min), number(max)) {
+            (Some(min), Some(max)) if min > max => RangeFilter {
+                min: Some(max),
+                max: Some(min),
+            },
+            (min, max) => RangeFilter { min, max },
+        };
+
+        if range == RangeFilter::default() {
+            None
+        } else {
+            Some(range)
+        }
+    }
+}
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct GetPaintingsQueryParsed {
@@ -10,19 +47,63 @@ pub struct GetPaintingsQueryParsed {
     pub order: String,
     pub lang: String,
     pub search: Option<String>,
+    pub availability: Option<Availability>,
+    /// slug of the series, checked with `is_slug`
+    pub series: Option<String>,
+    /// slug of the tag, checked with `is_slug`
+    pub tag: Option<String>,
+    /// whole units of `currency`
+    pub price: Option<RangeFilter>,
+    /// currency the price range was entered in, the display currency of the language
+    pub currency: String,
+    /// centimeters
+    pub width: Option<RangeFilter>,
+    /// centimeters
+    pub height: Option<RangeFilter>,
+    pub orientation: Option<Orientation>,
+    pub year: Option<i32>,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
 pub struct GetPaintingsQuery {
-    pub limit: Option<u8>,
+    pub limit: Option<u32>,
     pub offset: Option<u32>,
     pub sort: Option<String>,
     pub order: Option<String>,
     pub search: Option<String>,
     pub lang: Option<String>,
+    pub availability: Option<String>,
+    pub series: Option<String>,
+    pub tag: Option<String>,
+    pub price_min: Option<String>,
+    pub price_max: Option<String>,
+    pub width_min: Option<String>,
+    pub width_max: Option<String>,
+    pub height_min: Option<String>,
+    pub height_max: Option<String>,
+    pub orientation: Option<String>,
+    pub year: Option<String>,
+    /// `Cursor::encode` of the page boundary, the API pages by keyset with it
+    pub cursor: Option<String>,
 }
 
 impl GetPaintingsQuery {
+    /// Checks what the API refuses instead of guessing, returns the decoded cursor.
+    pub fn validate(&self) -> Result<Option<Cursor>, &'static str> {
+        if self
+            .limit
+            .is_some_and(|limit| limit == 0 || limit > MAX_LIMIT)
+        {
+            return Err("invalidLimit");
+        }
+
+        match &self.cursor {
+            Some(_) if self.offset.is_some() => Err("cursorWithOffset"),
+            Some(value) => Cursor::decode(value).map(Some).ok_or("invalidCursor"),
+            None => Ok(None),
+        }
+    }
+
     pub fn default(language: Option<Language>) -> GetPaintingsQueryParsed {
         let lang = if let Some(language_enum) = language {
             language_enum.to_string()
@@ -36,6 +117,15 @@ impl GetPaintingsQuery {
             sort: String::from("created"),
             order: String::from("desc"),
             search: None,
+            availability: None,
+            series: None,
+            tag: None,
+            price: None,
+            currency: String::from(BASE_CURRENCY),
+            width: None,
+            height: None,
+            orientation: None,
+            year: None,
             lang,
         }
     }
@@ -48,10 +138,10 @@ impl GetPaintingsQuery {
         };
 
         let limit = if let Some(limit_value) = &self.limit {
-            if *limit_value > 100_u8 {
+            if *limit_value > MAX_LIMIT {
                 25_u8
             } else {
-                *limit_value
+                *limit_value as u8
             }
         } else {
             25_u8
@@ -63,6 +153,15 @@ impl GetPaintingsQuery {
             0_u32
         };
 
+        let series = self.series.clone().filter(|slug| is_slug(slug));
+        let tag = self.tag.clone().filter(|slug| is_slug(slug));
+
+        let search: Option<String> = self
+            .search
+            .as_deref()
+            .map(|value| value.trim().chars().take(MAX_SEARCH_LENGTH).collect())
+            .filter(|value: &String| !value.is_empty());
+
         let sort = if let Some(sort_value) = &self.sort {
             match sort_value.as_str() {
                 "created" => sort_value.clone(),
@@ -73,9 +172,22 @@ impl GetPaintingsQuery {
                 }
                 "width" => sort_value.clone(),
                 "height" => sort_value.clone(),
-                "sold" => sort_value.clone(),
+                "sold" | "sold_date" => String::from("sold_date"),
+                "availability" => String::from(
+                    "CASE p.availability WHEN 'available' THEN 0 WHEN 'reserved' THEN 1 WHEN 'sold' THEN 2 ELSE 3 END",
+                ),
+                "relevance" | "search_rank" if search.is_some() => String::from("search_rank"),
+                "position" => match &series {
+                    Some(slug) => format!(
+                        "(SELECT sp.position FROM rosemary.series_paintings sp JOIN rosemary.series s ON s.id = sp.series_id WHERE sp.painting_id = p.id AND s.slug = '{}')",
+                        slug
+                    ),
+                    None => String::from("created"),
+                },
                 _ => String::from("created"),
             }
+        } else if search.is_some() {
+            String::from("search_rank")
         } else {
             String::from("created")
         };
@@ -92,19 +204,70 @@ impl GetPaintingsQuery {
             String::from("desc")
         };
 
-        let search = if let Some(search_value) = &self.search {
-            Some(search_value.clone())
+        let availability = self
+            .availability
+            .as_deref()
+            .and_then(Availability::from_code);
+
+        let currency = display_currency(language.unwrap_or(Language::Cs));
+        let currency = if is_currency_code(&currency) {
+            currency
         } else {
-            None
+            String::from(BASE_CURRENCY)
         };
 
+        let orientation = self.orientation.as_deref().and_then(Orientation::from_code);
+
+        let year = self
+            .year
+            .as_deref()
+            .and_then(|value| value.trim().parse::<i32>().ok())
+            .filter(|value| (1000..=9999).contains(value));
+
         GetPaintingsQueryParsed {
             limit,
             offset,
             sort,
             order,
             search,
+            availability,
+            series,
+            tag,
+            price: RangeFilter::parse(&self.price_min, &self.price_max),
+            currency,
+            width: RangeFilter::parse(&self.width_min, &self.width_max),
+            height: RangeFilter::parse(&self.height_min, &self.height_max),
+            orientation,
+            year,
             lang: lang.clone(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_limit_and_cursor() {
+        let query =
+            |limit: Option<u32>, offset: Option<u32>, cursor: Option<&str>| GetPaintingsQuery {
+                limit,
+                offset,
+                cursor: cursor.map(String::from),
+                ..Default::default()
+            };
+
+        assert_eq!(query(Some(100), None, None).validate(), Ok(None));
+        assert_eq!(query(Some(101), None, None).validate(), Err("invalidLimit"));
+        assert_eq!(query(Some(0), None, None).validate(), Err("invalidLimit"));
+        assert_eq!(
+            query(None, None, Some("zz")).validate(),
+            Err("invalidCursor")
+        );
+        assert_eq!(
+            query(None, Some(20), Some("7b7d")).validate(),
+            Err("cursorWithOffset")
+        );
+    }
+}
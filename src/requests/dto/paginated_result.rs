@@ -3,14 +3,23 @@ use serde_derive::{Deserialize, Serialize};
 #[derive(Deserialize, Serialize, Debug)]
 pub struct PaginatedResult<T> {
     pub rows: Vec<T>,
-    pub count: i64,
+    /// rows of the whole listing, keyset pages after the first one leave it out
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prev_cursor: Option<String>,
 }
 
 impl<T> PaginatedResult<T> {
-    pub fn new() -> Self {
+    /// Offset paginated rows with the total count.
+    pub fn new(rows: Vec<T>, count: i64) -> Self {
         PaginatedResult {
-            rows: vec![],
-            count: 0,
+            rows,
+            count: Some(count),
+            next_cursor: None,
+            prev_cursor: None,
         }
     }
 }
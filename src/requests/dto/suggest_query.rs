@@ -0,0 +1,65 @@
+use serde_derive::{Deserialize, Serialize};
+
+use crate::client::translations::Language;
+
+/// Shorter input matches too much to be useful
+const MIN_QUERY_LENGTH: usize = 2;
+
+/// Longer input is cut off
+const MAX_QUERY_LENGTH: usize = 100;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct SuggestQuery {
+    pub q: Option<String>,
+    pub lang: Option<String>,
+}
+
+impl SuggestQuery {
+    /// Trimmed lowercase input and the language to suggest in, Czech unless `lang` says otherwise.
+    pub fn parse(&self) -> Result<(String, Language), &'static str> {
+        let language = match self.lang.as_deref() {
+            Some(code) => Language::from_code(code).ok_or("invalidLanguage")?,
+            None => Language::Cs,
+        };
+
+        let q: String = self
+            .q
+            .as_deref()
+            .unwrap_or_default()
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ")
+            .to_lowercase()
+            .chars()
+            .take(MAX_QUERY_LENGTH)
+            .collect();
+        if q.chars().count() < MIN_QUERY_LENGTH {
+            return Err("queryTooShort");
+        }
+
+        Ok((q, language))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let query = |q: &str, lang: Option<&str>| SuggestQuery {
+            q: Some(q.to_string()),
+            lang: lang.map(String::from),
+        };
+
+        let (q, language) = query("  Zimní   Ráno ", None).parse().unwrap();
+        assert_eq!(q, "zimní ráno");
+        assert_eq!(language.to_str(), "cs");
+        assert_eq!(query("ra", Some("en")).parse().unwrap().1.to_str(), "en");
+        assert_eq!(query("r ", None).parse().err(), Some("queryTooShort"));
+        assert_eq!(
+            query("rano", Some("de")).parse().err(),
+            Some("invalidLanguage")
+        );
+    }
+}
@@ -1,25 +1,35 @@
 use crate::errors::api_error::handle_rejection;
 use crate::requests::routes;
+use crate::utils::compression::compression;
 use crate::utils::cors::cors_setting::settings;
 use warp::Filter;
 
 pub fn router() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
     // GET index page
     routes::frontend::index::get()
-        .or(routes::frontend::index::get_cz())
-        .or(routes::frontend::index::get_en())
+        .or(routes::frontend::index::get_localized())
         // GET /gallery/:id
         .or(routes::frontend::gallery_detail::get())
-        .or(routes::frontend::gallery_detail::get_cz())
-        .or(routes::frontend::gallery_detail::get_en())
+        .or(routes::frontend::gallery_detail::get_localized())
         // GET /gallery
         .or(routes::frontend::gallery::get())
-        .or(routes::frontend::gallery::get_cz())
-        .or(routes::frontend::gallery::get_en())
+        .or(routes::frontend::gallery::get_localized())
+        // GET /series/:slug
+        .or(routes::frontend::series::get_localized())
         // GET /contact
         .or(routes::frontend::contact::get())
-        .or(routes::frontend::contact::get_cz())
-        .or(routes::frontend::contact::get_en())
+        .or(routes::frontend::contact::get_localized())
+        // GET /language/:code?next=
+        .or(routes::frontend::language::get())
+        // GET /:lang/feed.xml, /:lang/rss.xml
+        .or(routes::frontend::feed::get_atom())
+        .or(routes::frontend::feed::get_rss())
+        // GET /sitemap.xml
+        .or(routes::frontend::sitemap::get())
+        // GET /robots.txt
+        .or(routes::frontend::robots::get())
+        // GET /assets/:file
+        .or(routes::frontend::assets::get())
         // GET /api/v0.0/paintings/:Uuid
         .or(routes::v1_0::paintings::get::get())
         // GET /api/v1.0/paintings
@@ -30,6 +40,32 @@ pub fn router() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejecti
         .or(routes::v1_0::paintings::update::update())
         // DELETE /api/v1.0/paintings/:Uuid
         .or(routes::v1_0::paintings::delete::delete())
+        // PUT /api/v1.0/paintings/:Uuid/tags
+        .or(routes::v1_0::paintings::update_tags::update())
+        // GET /api/v1.0/paintings/:Uuid/related
+        .or(routes::v1_0::paintings::related::get())
+        // GET /api/v1.0/series
+        .or(routes::v1_0::series::get_all::get())
+        // GET /api/v1.0/series/:slug
+        .or(routes::v1_0::series::get::get())
+        // POST /api/v1.0/series
+        .or(routes::v1_0::series::create::create())
+        // PATCH /api/v1.0/series/:Uuid
+        .or(routes::v1_0::series::update::update())
+        // PUT /api/v1.0/series/:Uuid/paintings
+        .or(routes::v1_0::series::update_paintings::update())
+        // DELETE /api/v1.0/series/:Uuid
+        .or(routes::v1_0::series::delete::delete())
+        // GET /api/v1.0/search/suggest
+        .or(routes::v1_0::search::suggest::get())
+        // GET /api/v1.0/tags
+        .or(routes::v1_0::tags::get_all::get())
+        // POST /api/v1.0/tags
+        .or(routes::v1_0::tags::create::create())
+        // PATCH /api/v1.0/tags/:Uuid
+        .or(routes::v1_0::tags::update::update())
+        // DELETE /api/v1.0/tags/:Uuid
+        .or(routes::v1_0::tags::delete::delete())
         // POST /api/v1.0/images
         .or(routes::v1_0::paintings_images::create::create())
         // PATCH /api/v1.0/images/:Uuid
@@ -38,6 +74,44 @@ pub fn router() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejecti
         .or(routes::v1_0::paintings_images::get_painting_images::get())
         // DELETE /api/v1.0/images/Uuid
         .or(routes::v1_0::paintings_images::delete::delete())
+        // POST /api/v1.0/uploads
+        .or(routes::v1_0::uploads::create::create())
+        // HEAD /api/v1.0/uploads/:Uuid
+        .or(routes::v1_0::uploads::head::head())
+        // PATCH /api/v1.0/uploads/:Uuid
+        .or(routes::v1_0::uploads::patch::patch())
+        // POST /api/v1.0/uploads/:Uuid/finalize
+        .or(routes::v1_0::uploads::finalize::finalize())
+        // DELETE /api/v1.0/uploads/:Uuid
+        .or(routes::v1_0::uploads::delete::delete())
+        // GET /api/v1.0/form-tokens/:form
+        .or(routes::v1_0::form_tokens::get::get())
+        // POST /api/v1.0/contact-form
+        .or(routes::v1_0::contact::create::create())
+        // GET /api/v1.0/contact-messages
+        .or(routes::v1_0::contact::get_all::get())
+        // POST /api/v1.0/paintings/:Uuid/inquiries
+        .or(routes::v1_0::inquiries::create::create())
+        // GET /api/v1.0/inquiries
+        .or(routes::v1_0::inquiries::get_all::get())
+        // POST /api/v1.0/inquiries/:Uuid/answer
+        .or(routes::v1_0::inquiries::answer::answer())
+        // POST /api/v1.0/inquiries/:Uuid/close
+        .or(routes::v1_0::inquiries::close::close())
+        // GET /api/v1.0/exchange-rates
+        .or(routes::v1_0::exchange_rates::get_all::get())
+        // POST /api/v1.0/exchange-rates/import
+        .or(routes::v1_0::exchange_rates::import::import())
+        // PUT /api/v1.0/exchange-rates/:currency
+        .or(routes::v1_0::exchange_rates::update::update())
+        // DELETE /api/v1.0/exchange-rates/:currency
+        .or(routes::v1_0::exchange_rates::delete::delete())
+        // GET /api/v1.0/translations
+        .or(routes::v1_0::translations::get_all::get())
+        // PUT /api/v1.0/translations/:lang/:key
+        .or(routes::v1_0::translations::update::update())
+        // DELETE /api/v1.0/translations/:lang/:key
+        .or(routes::v1_0::translations::delete::delete())
         // POST /api/v1.0/users/login
         .or(routes::v1_0::auth::login::login())
         // POST /api/v1.0/users/register
@@ -47,6 +121,8 @@ pub fn router() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejecti
         // GET /api/v1.0/users/refresh/token
         .or(routes::v1_0::auth::refresh::refresh())
         // Error handling
+        // gzip or brotli, as the client accepts
+        .with(warp::wrap_fn(compression))
         .recover(handle_rejection)
         // Allow CORS
         .with(settings())
@@ -1,4 +1,10 @@
+pub mod assets;
 pub mod contact;
+pub mod feed;
 pub mod gallery;
 pub mod gallery_detail;
 pub mod index;
+pub mod language;
+pub mod robots;
+pub mod series;
+pub mod sitemap;
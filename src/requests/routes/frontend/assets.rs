@@ -0,0 +1,98 @@
+use warp::http::header::{HeaderValue, CACHE_CONTROL, CONTENT_ENCODING, CONTENT_TYPE, ETAG, VARY};
+use warp::http::StatusCode;
+use warp::{path, Filter, Rejection, Reply};
+
+use crate::utils::assets::find_asset;
+use crate::utils::compression::{negotiate, Encoding};
+use crate::utils::http_cache::etag_matches;
+
+/// The content of a fingerprinted name never changes
+const IMMUTABLE: &str = "public, max-age=31536000, immutable";
+/// Plain names are still served for scripts importing each other, but revalidated
+const REVALIDATE: &str = "public, no-cache";
+
+async fn get_asset(
+    file_name: String,
+    accept_encoding: Option<String>,
+    if_none_match: Option<String>,
+) -> Result<warp::reply::Response, Rejection> {
+    let Some((asset, fingerprinted)) = find_asset(&file_name) else {
+        return Err(warp::reject::not_found());
+    };
+    let cache_control = if fingerprinted { IMMUTABLE } else { REVALIDATE };
+
+    let (body, encoding) = asset.encoded(negotiate(accept_encoding.as_deref()));
+    let mut response = if if_none_match
+        .as_deref()
+        .is_some_and(|value| etag_matches(value, &asset.etag))
+    {
+        StatusCode::NOT_MODIFIED.into_response()
+    } else {
+        warp::reply::Response::new(body.to_vec().into())
+    };
+
+    let headers = response.headers_mut();
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static(asset.content_type));
+    headers.insert(CACHE_CONTROL, HeaderValue::from_static(cache_control));
+    headers.insert(VARY, HeaderValue::from_static("Accept-Encoding"));
+    // the compressed bodies are other representations of the same content
+    let etag = match encoding {
+        Encoding::Identity => asset.etag.clone(),
+        _ => format!("W/{}", asset.etag),
+    };
+    if let Ok(value) = HeaderValue::from_str(&etag) {
+        headers.insert(ETAG, value);
+    }
+    if encoding != Encoding::Identity && response.status() == StatusCode::OK {
+        response.headers_mut().insert(
+            CONTENT_ENCODING,
+            HeaderValue::from_static(encoding.to_str()),
+        );
+    }
+    Ok(response)
+}
+
+/// `/assets/:file`, stylesheets and scripts embedded from `static/assets`
+pub fn get() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get()
+        .and(path("assets"))
+        .and(path::param::<String>())
+        .and(path::end())
+        .and(warp::header::optional::<String>("accept-encoding"))
+        .and(warp::header::optional::<String>("if-none-match"))
+        .and_then(get_asset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::assets::asset_url;
+
+    #[tokio::test]
+    async fn test_get_asset() {
+        let url = asset_url("main.js");
+        let response = warp::test::request()
+            .path(&url)
+            .header("accept-encoding", "gzip, br")
+            .reply(&get())
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CACHE_CONTROL], IMMUTABLE);
+        assert_eq!(response.headers()[CONTENT_ENCODING], "br");
+
+        let etag = response.headers()[ETAG].to_str().unwrap().to_string();
+        let response = warp::test::request()
+            .path(&url)
+            .header("if-none-match", etag)
+            .reply(&get())
+            .await;
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+        let response = warp::test::request()
+            .path("/assets/main.js")
+            .reply(&get())
+            .await;
+        assert_eq!(response.headers()[CACHE_CONTROL], REVALIDATE);
+        assert!(!response.headers().contains_key(CONTENT_ENCODING));
+    }
+}
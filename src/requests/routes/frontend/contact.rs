@@ -1,25 +1,31 @@
 use askama::Template;
+use std::borrow::Cow;
 use warp::{path, Filter, Rejection, Reply};
 
 use crate::{
     client::{
         component_props::{FooterProps, MetaProps, NavbarProps},
+        formatting::filters,
+        negotiation::{negotiated_redirect, preferred_language},
+        routes::{Route, CONTACT},
+        structured_data,
         translations::{get_translation, Language, TranslationKeys},
     },
     errors::api_error::InternalServerError,
+    utils::{http_cache::no_store, spam::FormGuardProps},
 };
 
 pub struct ContactPageData<'a> {
-    title: &'a str,
-    name: &'a str,
-    email: &'a str,
-    phone: &'a str,
-    subject: &'a str,
-    painting: &'a str,
-    photography: &'a str,
-    general: &'a str,
-    message: &'a str,
-    send: &'a str,
+    title: Cow<'a, str>,
+    name: Cow<'a, str>,
+    email: Cow<'a, str>,
+    phone: Cow<'a, str>,
+    subject: Cow<'a, str>,
+    painting: Cow<'a, str>,
+    photography: Cow<'a, str>,
+    general: Cow<'a, str>,
+    message: Cow<'a, str>,
+    send: Cow<'a, str>,
 }
 
 #[derive(Template)]
@@ -27,13 +33,56 @@ pub struct ContactPageData<'a> {
 pub struct ContactPage<'a> {
     meta: MetaProps<'a>,
     page: ContactPageData<'a>,
+    guard: FormGuardProps,
     navbar: NavbarProps<'a>,
     footer: FooterProps<'a>,
 }
 
-async fn get_template(lang: Language) -> Result<impl Reply, Rejection> {
-    let lang_string = lang.to_string();
+pub struct ContactThankYouPageData<'a> {
+    title: Cow<'a, str>,
+    message: Cow<'a, str>,
+    back_to_contact: Cow<'a, str>,
+}
+
+#[derive(Template)]
+#[template(path = "./contactThankYou.html")]
+pub struct ContactThankYouPage<'a> {
+    meta: MetaProps<'a>,
+    page: ContactThankYouPageData<'a>,
+    navbar: NavbarProps<'a>,
+    footer: FooterProps<'a>,
+}
+
+/// Page shown after a contact form submission without JavaScript.
+pub fn thank_you_template(lang: Language, success: bool) -> String {
+    let message_key = if success {
+        TranslationKeys::ContactThankYouMessage
+    } else {
+        TranslationKeys::ContactInvalidMessage
+    };
+
+    let mut meta_props = MetaProps::default(Some(lang));
+    meta_props.set_route(|_| Route::Contact);
+    meta_props.robots = "noindex, nofollow";
 
+    let template = ContactThankYouPage {
+        meta: meta_props,
+        page: ContactThankYouPageData {
+            title: get_translation(TranslationKeys::ContactThankYouTitle, lang),
+            message: get_translation(message_key, lang),
+            back_to_contact: get_translation(TranslationKeys::BackToContact, lang),
+        },
+        navbar: NavbarProps::default(Some(lang)),
+        footer: FooterProps::default(Some(lang)),
+    };
+
+    template.render().unwrap_or_else(|error| {
+        error!(target: "template", "contact:thank_you - failed to prepare template {}", error);
+        InternalServerError::new().html_response()
+    })
+}
+
+async fn get_template(lang: Language) -> Result<impl Reply, Rejection> {
     let page_data: ContactPageData = ContactPageData {
         title: get_translation(TranslationKeys::IndexTitle, lang),
         name: get_translation(TranslationKeys::Name, lang),
@@ -48,13 +97,15 @@ async fn get_template(lang: Language) -> Result<impl Reply, Rejection> {
     };
 
     let mut meta_props = MetaProps::default(Some(lang));
-    meta_props.url = format!("www.rosemary-artist.com/{}/contact", &lang_string);
-    meta_props.description = "Rosemary, artist, contact, kontakt, form, formular, email";
-    meta_props.summary_large_image = "";
+    meta_props.set_route(|_| Route::Contact);
+    meta_props.description = "Rosemary, artist, contact, kontakt, form, formular, email".into();
+    let artist = structured_data::artist(&meta_props.site_url, &meta_props.static_base_url, lang);
+    meta_props.add_json_ld(&artist);
 
     let template = ContactPage {
         meta: meta_props,
         page: page_data,
+        guard: FormGuardProps::new("contact"),
         navbar: NavbarProps::default(Some(lang)),
         footer: FooterProps::default(Some(lang)),
     };
@@ -72,25 +123,19 @@ async fn get_template(lang: Language) -> Result<impl Reply, Rejection> {
     }
 }
 
+/// `/contact` redirects to the form in the language of the visitor
 pub fn get() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
     warp::get()
-        .and(path("contact"))
-        .and(path::end())
-        .and_then(|| async { get_template(Language::Cs).await })
-}
-
-pub fn get_cz() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
-    warp::get()
-        .and(path(Language::Cs.to_str()))
-        .and(path("contact"))
+        .and(path(CONTACT))
         .and(path::end())
-        .and_then(|| async { get_template(Language::Cs).await })
+        .and(preferred_language())
+        .and_then(|language| async move { negotiated_redirect(&Route::Contact.path(language)) })
 }
 
-pub fn get_en() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+pub fn get_localized() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
     warp::get()
-        .and(path(Language::En.to_str()))
-        .and(path("contact"))
+        .and(path::param::<Language>())
+        .and(path(CONTACT))
         .and(path::end())
-        .and_then(|| async { get_template(Language::En).await })
+        .and_then(|language| async move { get_template(language).await.map(no_store) })
 }
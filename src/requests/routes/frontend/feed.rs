@@ -0,0 +1,107 @@
+use warp::{path, Filter, Rejection, Reply};
+
+use crate::client::routes::{Route, ATOM_FEED, RSS_FEED};
+use crate::client::structured_data::ARTIST_NAME;
+use crate::client::translations::{get_translation, Language, TranslationKeys};
+use crate::config::load::{self, ConfigField};
+use crate::database::connection::get_client;
+use crate::database::models::feed::{FeedPainting, FEED_LIMIT};
+use crate::database::models::painting::Painting;
+use crate::errors::api_error::InternalServerError;
+use crate::utils::feed::{render_atom, render_rss, Feed, FeedEntry};
+use crate::utils::http_cache::{cache_request, CachePolicy, CacheRequest};
+use crate::utils::money::display_price;
+
+#[derive(Debug, Clone, Copy)]
+enum FeedFormat {
+    Atom,
+    Rss,
+}
+
+async fn get_feed(
+    language: Language,
+    format: FeedFormat,
+) -> Result<warp::reply::Response, Rejection> {
+    let (site_url, static_base_url) = match (
+        load::get::<String>(ConfigField::SiteUrl).await,
+        load::get::<String>(ConfigField::StaticFileUrl).await,
+    ) {
+        (Ok(site_url), Ok(static_base_url)) => (site_url, static_base_url),
+        _ => return Err(warp::reject::custom(InternalServerError::new())),
+    };
+    let static_base_url = format!("{}/", static_base_url.trim_end_matches('/'));
+
+    let client = get_client().await.unwrap();
+    let query = Painting::get_feed_query(language, &static_base_url);
+    debug!(target: "client", "feed:get - Painting::get_feed_query {}", &query);
+    let paintings = match sqlx::query_as::<_, FeedPainting>(&query)
+        .bind(FEED_LIMIT)
+        .fetch_all(client)
+        .await
+    {
+        Ok(paintings) => paintings,
+        Err(error) => {
+            error!(target: "client", "feed:get - failed to list paintings {:?}", error);
+            return Err(warp::reject::custom(InternalServerError::new()));
+        }
+    };
+
+    let (route, content_type) = match format {
+        FeedFormat::Atom => (Route::AtomFeed, "application/atom+xml; charset=utf-8"),
+        FeedFormat::Rss => (Route::RssFeed, "application/rss+xml; charset=utf-8"),
+    };
+
+    let feed = Feed {
+        title: get_translation(TranslationKeys::FeedTitle, language).to_string(),
+        description: get_translation(TranslationKeys::FeedDescription, language).to_string(),
+        author: String::from(ARTIST_NAME),
+        language,
+        page_url: Route::Gallery.url(&site_url, language),
+        self_url: route.url(&site_url, language),
+        entries: paintings
+            .into_iter()
+            .map(|painting| FeedEntry {
+                id: painting.stub.id,
+                url: format!("{}{}", site_url, painting.stub.href(&language)),
+                price: display_price(painting.stub.price, &painting.stub.currency, language),
+                title: painting.stub.title,
+                summary: painting.description,
+                image: painting.image,
+                image_alt: painting.stub.preview_alt,
+                published: painting.stub.created,
+                updated: painting.updated,
+            })
+            .collect(),
+    };
+
+    let xml = match format {
+        FeedFormat::Atom => render_atom(&feed),
+        FeedFormat::Rss => render_rss(&feed),
+    };
+
+    Ok(warp::reply::with_header(xml, "content-type", content_type).into_response())
+}
+
+fn feed(
+    file: &'static str,
+    format: FeedFormat,
+) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get()
+        .and(path::param::<Language>())
+        .and(path(file))
+        .and(path::end())
+        .and(cache_request())
+        .and_then(move |language, request: CacheRequest| async move {
+            request
+                .reply(CachePolicy::Feed, || get_feed(language, format))
+                .await
+        })
+}
+
+pub fn get_atom() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    feed(ATOM_FEED, FeedFormat::Atom)
+}
+
+pub fn get_rss() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    feed(RSS_FEED, FeedFormat::Rss)
+}
@@ -1,14 +1,21 @@
 use askama::Template;
 use sqlx::{Pool, Postgres};
+use std::borrow::Cow;
 use std::sync::Arc;
 use warp::{path, query, Filter, Rejection, Reply};
 
 use crate::client::component_props::{FooterProps, MetaProps, NavbarProps};
+use crate::client::formatting::filters;
+use crate::client::negotiation::{negotiated_redirect, preferred_language};
+use crate::client::routes::{Route, GALLERY};
 use crate::client::translations::{get_translation, Language, TranslationKeys};
 use crate::config::load;
 use crate::database::connection::get_client;
-use crate::database::models::painting::{Painting, PaintingStub};
-use crate::requests::dto::get_paintings_query::GetPaintingsQuery;
+use crate::database::models::facets::{FacetCount, FacetRange, Facets};
+use crate::database::models::painting::{Availability, Painting, PaintingStub};
+use crate::requests::dto::get_paintings_query::{GetPaintingsQuery, RangeFilter};
+use crate::utils::http_cache::{cache_request, CachePolicy, CacheRequest};
+use crate::utils::money::display_price;
 
 #[derive(Debug)]
 struct PaginationData<'a> {
@@ -19,26 +26,155 @@ struct PaginationData<'a> {
     next_page: &'a str,
 }
 
+#[derive(Debug)]
+struct FacetOption {
+    value: String,
+    label: String,
+    selected: bool,
+}
+
+/// Select of one facet, the first option clears the filter.
+#[derive(Debug)]
+struct FacetSelect<'a> {
+    name: &'static str,
+    label: Cow<'a, str>,
+    options: Vec<FacetOption>,
+}
+
+impl<'a> FacetSelect<'a> {
+    fn new(name: &'static str, label: Cow<'a, str>, any: &str, current: &str) -> Self {
+        Self {
+            name,
+            label,
+            options: vec![FacetOption {
+                value: String::new(),
+                label: any.to_string(),
+                selected: current.is_empty(),
+            }],
+        }
+    }
+
+    /// Adds the option with the number of paintings it would show.
+    fn option(mut self, value: &str, label: &str, count: i64, current: &str) -> Self {
+        self.options.push(FacetOption {
+            value: value.to_string(),
+            label: format!("{} ({})", label, count),
+            selected: value == current,
+        });
+        self
+    }
+
+    /// Options of a counted facet, the current value stays even when nothing matches it.
+    fn options(self, facet: &[FacetCount], current: &str) -> Self {
+        let select = facet.iter().fold(self, |select, item| {
+            select.option(&item.value, &item.label, item.count, current)
+        });
+
+        if current.is_empty() || facet.iter().any(|item| item.value == current) {
+            select
+        } else {
+            select.option(current, current, 0, current)
+        }
+    }
+}
+
+/// Pair of number inputs for `{name}_min` and `{name}_max`.
+#[derive(Debug)]
+struct RangeInput {
+    name: &'static str,
+    label: String,
+    min: String,
+    max: String,
+    placeholder_min: String,
+    placeholder_max: String,
+}
+
+impl RangeInput {
+    fn new(
+        name: &'static str,
+        label: String,
+        current: Option<RangeFilter>,
+        facet: &FacetRange,
+    ) -> Self {
+        let text = |value: Option<i64>| value.map(|v| v.to_string()).unwrap_or_default();
+        let current = current.unwrap_or_default();
+        Self {
+            name,
+            label,
+            min: text(current.min.map(i64::from)),
+            max: text(current.max.map(i64::from)),
+            placeholder_min: text(facet.min),
+            placeholder_max: text(facet.max),
+        }
+    }
+}
+
 #[derive(Debug)]
 struct GalleryPageData<'a> {
-    created: &'a str,
-    price: &'a str,
-    width: &'a str,
-    height: &'a str,
-    title: &'a str,
-    sold: &'a str,
-    ascending: &'a str,
-    descending: &'a str,
+    language: Language,
+    created: Cow<'a, str>,
+    price: Cow<'a, str>,
+    width: Cow<'a, str>,
+    height: Cow<'a, str>,
+    title: Cow<'a, str>,
+    sold: Cow<'a, str>,
+    available: Cow<'a, str>,
+    reserved: Cow<'a, str>,
+    not_for_sale: Cow<'a, str>,
+    relevance: Cow<'a, str>,
+    search: Cow<'a, str>,
+    ascending: Cow<'a, str>,
+    descending: Cow<'a, str>,
     gallery_item_stubs: Vec<PaintingStub>,
     filter_form_action: &'a str,
-    filter_action: &'a str,
+    filter_action: Cow<'a, str>,
     current_sort: &'a str,
     current_search: &'a str,
     current_order: &'a str,
+    facet_selects: Vec<FacetSelect<'a>>,
+    range_inputs: Vec<RangeInput>,
+    range_from: Cow<'a, str>,
+    range_to: Cow<'a, str>,
+    reset_filters: Cow<'a, str>,
+    reset_link: String,
     show_prev_page: bool,
     show_next_page: bool,
 }
 
+impl GalleryPageData<'_> {
+    fn display_price(&self, stub: &PaintingStub) -> String {
+        display_price(stub.price, &stub.currency, self.language)
+    }
+
+    fn availability_label(&self, availability: &Availability) -> &str {
+        match availability {
+            Availability::Available => &self.available,
+            Availability::Reserved => &self.reserved,
+            Availability::Sold => &self.sold,
+            Availability::NotForSale => &self.not_for_sale,
+        }
+    }
+}
+
+/// Gallery URL with the same filters at another offset.
+fn page_link(language: Language, query: &GetPaintingsQuery, limit: u8, offset: u32) -> String {
+    let mut page_query = query.clone();
+    page_query.limit = Some(u32::from(limit));
+    page_query.offset = Some(offset);
+    page_query.lang = None;
+    page_query.cursor = None;
+
+    // filters left empty in the form are not worth carrying along
+    let params: Vec<String> = serde_urlencoded::to_string(&page_query)
+        .unwrap_or_default()
+        .split('&')
+        .filter(|pair| !pair.is_empty() && !pair.ends_with('='))
+        .map(String::from)
+        .collect();
+
+    format!("/{}/gallery?{}", language.to_str(), params.join("&"))
+}
+
 #[derive(Template, Debug)]
 #[template(path = "./gallery.html")]
 struct GalleryPage<'a> {
@@ -61,9 +197,11 @@ async fn get_template(
         String::new()
     };
 
+    let availability = parsed_query.availability;
+    let count_query = Painting::count_all_query(&parsed_query);
     let count_client: Arc<Pool<Postgres>> = Arc::new(get_client().await.unwrap().clone());
     let count_task = tokio::spawn(async move {
-        let (count,): (i64,) = sqlx::query_as(&Painting::count_all_query())
+        let (count,): (i64,) = sqlx::query_as(&count_query)
             .fetch_one(&*count_client)
             .await
             .expect("Failed to count paintings");
@@ -89,8 +227,21 @@ async fn get_template(
         rows
     });
 
-    let (count, rows) = tokio::join!(count_task, rows_task);
+    let facets_client: Arc<Pool<Postgres>> = Arc::new(get_client().await.unwrap().clone());
+    let facets_query = Painting::facets_query(&parsed_query);
+    let facets_task = tokio::spawn(async move {
+        sqlx::query_as::<_, Facets>(&facets_query)
+            .fetch_one(&*facets_client)
+            .await
+            .expect("Failed to count painting facets")
+    });
+
+    let (count, rows, facets) = tokio::join!(count_task, rows_task, facets_task);
     debug!(target: "template", "paintings:get_all - rows {:?}", &rows);
+    let facets = facets.unwrap_or_else(|error| {
+        error!(target: "template", "paintings:get_all - failed to get facets {}", error);
+        Facets::default()
+    });
     let parsed_count = count.unwrap_or_else(|error| {
         error!(target: "template", "paintings:get_all - failed to count paintings {}", error);
         0_i64
@@ -101,7 +252,7 @@ async fn get_template(
     let mut current_end = (parsed_query.offset + parsed_query.limit as u32) as u64;
     match (query.offset, query.limit) {
         (Some(offset), Some(limit)) => {
-            if offset > limit as u32 {
+            if offset > limit {
                 show_prev_page = true;
             }
             if (offset as u64 + limit as u64) < parsed_count as u64 {
@@ -113,13 +264,132 @@ async fn get_template(
         _ => {}
     }
 
+    let availability_param = availability.map(|value| value.to_str()).unwrap_or("");
+    let current_sort = if parsed_query.sort == "search_rank" {
+        String::from("relevance")
+    } else {
+        query
+            .sort
+            .clone()
+            .unwrap_or_else(|| String::from("created"))
+    };
+    let series_param = parsed_query.series.as_deref().unwrap_or("");
+    let tag_param = parsed_query.tag.as_deref().unwrap_or("");
+    let orientation_param = parsed_query
+        .orientation
+        .map(|value| value.to_str())
+        .unwrap_or("");
+    let year_param = parsed_query
+        .year
+        .map(|value| value.to_string())
+        .unwrap_or_default();
+
+    let translate = |key: TranslationKeys| get_translation(key, language);
+    let any = translate(TranslationKeys::Any);
+    let availability_select = [
+        (Availability::Available, TranslationKeys::Available),
+        (Availability::Reserved, TranslationKeys::Reserved),
+        (Availability::Sold, TranslationKeys::Sold),
+        (Availability::NotForSale, TranslationKeys::NotForSale),
+    ]
+    .iter()
+    .fold(
+        FacetSelect::new(
+            "availability",
+            translate(TranslationKeys::Availability),
+            &translate(TranslationKeys::AllPaintings),
+            availability_param,
+        ),
+        |select, (value, key)| {
+            select.option(
+                value.to_str(),
+                &translate(*key),
+                Facets::count(&facets.availability, value.to_str()),
+                availability_param,
+            )
+        },
+    );
+    let orientation_select = [
+        ("landscape", TranslationKeys::Landscape),
+        ("portrait", TranslationKeys::Portrait),
+        ("square", TranslationKeys::Square),
+    ]
+    .iter()
+    .fold(
+        FacetSelect::new(
+            "orientation",
+            translate(TranslationKeys::Orientation),
+            &any,
+            orientation_param,
+        ),
+        |select, (value, key)| {
+            select.option(
+                value,
+                &translate(*key),
+                Facets::count(&facets.orientation, value),
+                orientation_param,
+            )
+        },
+    );
+    let facet_selects = vec![
+        availability_select,
+        orientation_select,
+        FacetSelect::new(
+            "year",
+            translate(TranslationKeys::YearCreated),
+            &any,
+            &year_param,
+        )
+        .options(&facets.year, &year_param),
+        FacetSelect::new(
+            "series",
+            translate(TranslationKeys::Series),
+            &any,
+            series_param,
+        )
+        .options(&facets.series, series_param),
+        FacetSelect::new("tag", translate(TranslationKeys::Tag), &any, tag_param)
+            .options(&facets.tag, tag_param),
+    ];
+
+    let range_inputs = vec![
+        RangeInput::new(
+            "price",
+            format!(
+                "{} ({})",
+                translate(TranslationKeys::Price),
+                &facets.currency
+            ),
+            parsed_query.price,
+            &facets.price,
+        ),
+        RangeInput::new(
+            "width",
+            format!("{} (cm)", translate(TranslationKeys::Width)),
+            parsed_query.width,
+            &facets.width,
+        ),
+        RangeInput::new(
+            "height",
+            format!("{} (cm)", translate(TranslationKeys::Height)),
+            parsed_query.height,
+            &facets.height,
+        ),
+    ];
+
     let page_data: GalleryPageData = GalleryPageData {
+        language,
         created: get_translation(TranslationKeys::Created, language),
         price: get_translation(TranslationKeys::Price, language),
         width: get_translation(TranslationKeys::Width, language),
         height: get_translation(TranslationKeys::Height, language),
         title: get_translation(TranslationKeys::GalleryTitle, language),
         sold: get_translation(TranslationKeys::Sold, language),
+        available: get_translation(TranslationKeys::Available, language),
+        reserved: get_translation(TranslationKeys::Reserved, language),
+        not_for_sale: get_translation(TranslationKeys::NotForSale, language),
+        relevance: get_translation(TranslationKeys::Relevance, language),
+        search: get_translation(TranslationKeys::Search, language),
         ascending: get_translation(TranslationKeys::Ascending, language),
         descending: get_translation(TranslationKeys::Descending, language),
         gallery_item_stubs: rows.unwrap_or_else(|error| {
@@ -128,9 +398,15 @@ async fn get_template(
         }),
         filter_form_action: &format!("/{}/gallery?limit=20&offset=0", &language_string),
         filter_action: get_translation(TranslationKeys::Filter, language),
-        current_sort: &parsed_query.sort,
+        current_sort: &current_sort,
         current_order: &parsed_query.order,
         current_search: &search,
+        facet_selects,
+        range_inputs,
+        range_from: translate(TranslationKeys::RangeFrom),
+        range_to: translate(TranslationKeys::RangeTo),
+        reset_filters: translate(TranslationKeys::ResetFilters),
+        reset_link: format!("/{}/gallery", language.to_str()),
         show_prev_page,
         show_next_page,
     };
@@ -144,27 +420,18 @@ async fn get_template(
     let pagination_data: PaginationData = PaginationData {
         total: parsed_count,
         current_start: parsed_query.offset + 1,
-        prev_page: &format!(
-            "/{}/gallery?limit={}&offset={}&sort={}&order={}",
-            &language_string,
-            parsed_query.limit,
-            prev_page_offset,
-            parsed_query.sort,
-            parsed_query.order
-        ),
-        next_page: &format!(
-            "/{}/gallery?limit={}&offset={}&sort={}&order={}",
-            &language_string,
+        prev_page: &page_link(language, &query, parsed_query.limit, prev_page_offset),
+        next_page: &page_link(
+            language,
+            &query,
             parsed_query.limit,
             parsed_query.offset + parsed_query.limit as u32,
-            parsed_query.sort,
-            parsed_query.order
         ),
         current_end,
     };
 
     let mut meta_props = MetaProps::default(Some(language));
-    meta_props.url = format!("www.rosemary-artist.com/{}/gallery", language.to_str());
+    meta_props.set_route(|_| Route::Gallery);
 
     let template = GalleryPage {
         meta: meta_props,
@@ -182,28 +449,55 @@ async fn get_template(
     Ok(warp::reply::html(result))
 }
 
+/// `/gallery` redirects to the gallery in the language of the visitor, filters included
 pub fn get() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
     warp::get()
-        .and(path("gallery"))
+        .and(path(GALLERY))
         .and(path::end())
-        .and(query::<GetPaintingsQuery>())
-        .and_then(|query| async { get_template(Language::Cs, query).await })
+        .and(preferred_language())
+        .and(query::raw().or(warp::any().map(String::new)).unify())
+        .and_then(|language, raw_query: String| async move {
+            let path = Route::Gallery.path(language);
+            if raw_query.is_empty() {
+                negotiated_redirect(&path)
+            } else {
+                negotiated_redirect(&format!("{}?{}", path, raw_query))
+            }
+        })
 }
 
-pub fn get_cz() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+pub fn get_localized() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
     warp::get()
-        .and(path(Language::Cs.to_str()))
-        .and(path("gallery"))
+        .and(path::param::<Language>())
+        .and(path(GALLERY))
         .and(path::end())
         .and(query::<GetPaintingsQuery>())
-        .and_then(|query| async { get_template(Language::Cs, query).await })
+        .and(cache_request())
+        .and_then(|language, query, request: CacheRequest| async move {
+            request
+                .reply(CachePolicy::Revalidate, || get_template(language, query))
+                .await
+        })
 }
 
-pub fn get_en() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
-    warp::get()
-        .and(path(Language::En.to_str()))
-        .and(path("gallery"))
-        .and(path::end())
-        .and(query::<GetPaintingsQuery>())
-        .and_then(|query| async { get_template(Language::En, query).await })
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_link_keeps_filters() {
+        let query = GetPaintingsQuery {
+            offset: Some(20),
+            price_min: Some(String::from("1000")),
+            price_max: Some(String::new()),
+            orientation: Some(String::from("portrait")),
+            tag: Some(String::from("zima")),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            page_link(Language::En, &query, 20, 40),
+            "/en/gallery?limit=20&offset=40&tag=zima&price_min=1000&orientation=portrait"
+        );
+    }
 }
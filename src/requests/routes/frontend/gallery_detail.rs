@@ -1,13 +1,31 @@
 use askama::Template;
+use chrono::{DateTime, Utc};
+use fluent_bundle::FluentArgs;
+use std::borrow::Cow;
+use std::collections::HashMap;
 use uuid::Uuid;
+use warp::http::Uri;
 use warp::{path, Filter, Rejection, Reply};
 
 use crate::client::component_props::{FooterProps, MetaProps, NavbarProps};
-use crate::client::translations::{get_translation, Language, TranslationKeys};
+use crate::client::formatting::{filters, format_decimal};
+use crate::client::negotiation::{negotiated_redirect, preferred_language};
+use crate::client::routes::{Route, GALLERY};
+use crate::client::structured_data;
+use crate::client::translations::{
+    default_language, get_translation, get_translation_with, Language, TranslationKeys,
+};
 use crate::database::connection::get_client;
 use crate::database::models::image::PaintingImage;
-use crate::database::models::painting::Painting;
+use crate::database::models::painting::{Availability, Painting, PaintingStub};
+use crate::database::models::painting_slug::PaintingSlug;
+use crate::database::models::tag::Tag;
 use crate::errors::api_error::InternalServerError;
+use crate::requests::routes::v1_0::paintings::related::get_related;
+use crate::utils::http_cache::no_store;
+use crate::utils::money::display_price;
+use crate::utils::slug::is_slug;
+use crate::utils::spam::FormGuardProps;
 
 #[derive(Debug)]
 struct PaintingPhoto {
@@ -15,29 +33,74 @@ struct PaintingPhoto {
     alt: String,
 }
 
+#[derive(Debug)]
+struct PaintingDetail<'a> {
+    label: Cow<'a, str>,
+    value: String,
+}
+
+#[derive(Debug)]
+struct PaintingTagLink {
+    name: String,
+    href: String,
+}
+
 #[derive(Debug)]
 struct GalleryDetailPageData<'a> {
+    language: Language,
+    id: String,
     title: &'a str,
     description: &'a str,
     dimenzions: &'a str,
     unit: &'a str,
     technique: &'a str,
+    details: Vec<PaintingDetail<'a>>,
+    /// end of a reservation that is still running
+    reserved_until: Option<DateTime<Utc>>,
+    reserved_until_label: Cow<'a, str>,
+    tags: Vec<PaintingTagLink>,
     main_photo_sizes: &'a str,
     main_photo_srcset: &'a str,
     main_photo_src: &'a str,
     main_photo_alt: &'a str,
     photos: Vec<PaintingPhoto>,
     photos_count: &'a str,
+    related_paintings: Cow<'a, str>,
+    gallery_item_stubs: Vec<PaintingStub>,
+    sold: Cow<'a, str>,
+    available: Cow<'a, str>,
+    reserved: Cow<'a, str>,
+    not_for_sale: Cow<'a, str>,
+}
+
+impl GalleryDetailPageData<'_> {
+    fn display_price(&self, stub: &PaintingStub) -> String {
+        display_price(stub.price, &stub.currency, self.language)
+    }
+
+    fn availability_label(&self, availability: &Availability) -> &str {
+        match availability {
+            Availability::Available => &self.available,
+            Availability::Reserved => &self.reserved,
+            Availability::Sold => &self.sold,
+            Availability::NotForSale => &self.not_for_sale,
+        }
+    }
 }
 
 #[derive(Debug)]
 struct GalleryDetailLabels<'a> {
     buy_with_price: &'a str,
-    full_name: &'a str,
-    email: &'a str,
-    close: &'a str,
-    prev: &'a str,
-    next: &'a str,
+    buy_painting: Cow<'a, str>,
+    full_name: Cow<'a, str>,
+    email: Cow<'a, str>,
+    message: Cow<'a, str>,
+    send: Cow<'a, str>,
+    inquiry_sent: Cow<'a, str>,
+    inquiry_failed: Cow<'a, str>,
+    close: Cow<'a, str>,
+    prev: Cow<'a, str>,
+    next: Cow<'a, str>,
 }
 
 #[derive(Debug, Template)]
@@ -45,11 +108,110 @@ struct GalleryDetailLabels<'a> {
 struct GalleryDetailPage<'a> {
     page: GalleryDetailPageData<'a>,
     labels: GalleryDetailLabels<'a>,
+    guard: FormGuardProps,
     navbar: NavbarProps<'a>,
     footer: FooterProps<'a>,
     meta: MetaProps<'a>,
 }
 
+/// Grams as kilograms with one decimal place in the separator of the language.
+fn format_weight(grams: i64, language: Language) -> String {
+    format!(
+        "{}\u{a0}kg",
+        format_decimal(grams as f64 / 1000.0, 1, language)
+    )
+}
+
+/// Where a `/gallery/:param` request ends up
+enum DetailTarget {
+    Render(Uuid),
+    Redirect(String),
+}
+
+fn detail_path(language: Language, slug: &str) -> String {
+    Route::Painting(slug.to_string()).path(language)
+}
+
+/// UUIDs and replaced slugs are redirected to the current slug of the language.
+async fn resolve(language: Language, param: &str) -> Result<DetailTarget, Rejection> {
+    let client = get_client().await.unwrap();
+
+    if let Ok(id) = Uuid::parse_str(param) {
+        let query = PaintingSlug::get_current_query();
+        debug!(target: "client", "painting:get - PaintingSlug::get_current_query {}", &query);
+        return match sqlx::query_as::<_, PaintingSlug>(&query)
+            .bind(id)
+            .bind(language.content().to_str())
+            .fetch_optional(client)
+            .await
+        {
+            Ok(Some(slug)) => Ok(DetailTarget::Redirect(slug.slug)),
+            Ok(None) => Ok(DetailTarget::Render(id)),
+            Err(error) => {
+                error!(target: "client", "painting:get - failed to get slug {:?}", error);
+                Err(warp::reject::custom(InternalServerError::new()))
+            }
+        };
+    }
+
+    if !is_slug(param) {
+        return Err(warp::reject::not_found());
+    }
+
+    let query = PaintingSlug::get_by_slug_query();
+    debug!(target: "client", "painting:get - PaintingSlug::get_by_slug_query {}", &query);
+    match sqlx::query_as::<_, PaintingSlug>(&query)
+        .bind(language.content().to_str())
+        .bind(param)
+        .fetch_optional(client)
+        .await
+    {
+        Ok(Some(slug)) if slug.current => Ok(DetailTarget::Render(slug.painting_id)),
+        Ok(Some(slug)) => match slug.current_slug {
+            Some(current_slug) => Ok(DetailTarget::Redirect(current_slug)),
+            None => Ok(DetailTarget::Render(slug.painting_id)),
+        },
+        Ok(None) => Err(warp::reject::not_found()),
+        Err(error) => {
+            error!(target: "client", "painting:get - failed to get slug {:?}", error);
+            Err(warp::reject::custom(InternalServerError::new()))
+        }
+    }
+}
+
+fn redirect(path: String) -> Result<warp::reply::Response, Rejection> {
+    let uri = Uri::try_from(path).map_err(|_| warp::reject::not_found())?;
+    Ok(warp::redirect::redirect(uri).into_response())
+}
+
+async fn get_page(language: Language, param: String) -> Result<warp::reply::Response, Rejection> {
+    match resolve(language, &param).await? {
+        DetailTarget::Redirect(slug) => redirect(detail_path(language, &slug)),
+        DetailTarget::Render(id) => Ok(get_template(language, id).await?.into_response()),
+    }
+}
+
+/// Old links carry Czech slugs, those the language of the visitor does not know stay Czech.
+async fn get_unprefixed(
+    language: Language,
+    param: String,
+) -> Result<warp::reply::Response, Rejection> {
+    let (language, target) = match resolve(language, &param).await {
+        Ok(target) => (language, target),
+        Err(_) if language != default_language() => (
+            default_language(),
+            resolve(default_language(), &param).await?,
+        ),
+        Err(rejection) => return Err(rejection),
+    };
+
+    let slug = match target {
+        DetailTarget::Redirect(slug) => slug,
+        DetailTarget::Render(_) => param,
+    };
+    negotiated_redirect(&detail_path(language, &slug))
+}
+
 async fn get_template(language: Language, id: Uuid) -> Result<impl Reply, Rejection> {
     let client = get_client().await.unwrap();
 
@@ -85,7 +247,56 @@ async fn get_template(language: Language, id: Uuid) -> Result<impl Reply, Reject
         images
     });
 
-    let (painting_result, images_result) = tokio::join!(painting_task, images_task);
+    let tags_task = tokio::spawn(async move {
+        let query = Tag::get_for_painting_query();
+        debug!(target: "client", "painting:get - Tag::get_for_painting_query {}", &query);
+
+        sqlx::query_as::<_, Tag>(&query)
+            .bind(id)
+            .fetch_all(client)
+            .await
+            .unwrap_or_else(|err| {
+                error!(target: "api", "paitings:get - Tag::get_for_painting_query failed {:?}", err);
+                Vec::new()
+            })
+    });
+
+    let slugs_task = tokio::spawn(async move {
+        let query = PaintingSlug::get_all_current_query();
+        debug!(target: "client", "painting:get - PaintingSlug::get_all_current_query {}", &query);
+
+        sqlx::query_as::<_, (String, String)>(&query)
+            .bind(id)
+            .fetch_all(client)
+            .await
+            .unwrap_or_else(|err| {
+                error!(target: "client", "painting:get - PaintingSlug::get_all_current_query failed {:?}", err);
+                Vec::new()
+            })
+            .into_iter()
+            .collect::<HashMap<String, String>>()
+    });
+
+    let mut meta_props = MetaProps::default(Some(language));
+
+    let static_base_url = meta_props.static_base_url.clone();
+    let related_task = tokio::spawn(async move {
+        get_related(id, language, &static_base_url)
+            .await
+            .unwrap_or_else(|err| {
+                error!(target: "client", "painting:get - get_related failed {:?}", err);
+                None
+            })
+            .unwrap_or_default()
+    });
+
+    let (painting_result, images_result, tags_result, related_result, slugs_result) = tokio::join!(
+        painting_task,
+        images_task,
+        tags_task,
+        related_task,
+        slugs_task
+    );
 
     if painting_result.is_err() {
         return Err(warp::reject::custom(InternalServerError::new()));
@@ -96,8 +307,26 @@ async fn get_template(language: Language, id: Uuid) -> Result<impl Reply, Reject
     }
 
     let metadata = painting_result.unwrap();
+    let tags = tags_result
+        .unwrap_or_default()
+        .iter()
+        .map(|tag| PaintingTagLink {
+            name: tag.get_name(language).to_string(),
+            href: format!("/{}/gallery?tag={}", language.to_str(), tag.slug),
+        })
+        .collect();
     let images = images_result.unwrap();
 
+    let image_urls: Vec<String> = std::iter::once(&metadata.preview.0)
+        .chain(
+            images
+                .iter()
+                .filter(|image| image.id != metadata.preview.id),
+        )
+        .filter_map(|image| image.urls.last())
+        .map(|url| format!("{}/{}", meta_props.static_base_url, url))
+        .collect();
+
     let mut painting_photos: Vec<PaintingPhoto> = Vec::new();
     for image in images.into_iter() {
         painting_photos.push(PaintingPhoto {
@@ -106,8 +335,33 @@ async fn get_template(language: Language, id: Uuid) -> Result<impl Reply, Reject
         });
     }
 
-    let mut meta_props = MetaProps::default(Some(language));
-    meta_props.url = format!("www.rosemary-artist.com/{}/{}", language.to_str(), id);
+    let slugs = slugs_result.unwrap_or_default();
+    meta_props.set_route(|language| {
+        Route::Painting(
+            slugs
+                .get(language.content().to_str())
+                .cloned()
+                .unwrap_or_else(|| id.to_string()),
+        )
+    });
+    meta_props.description = metadata.get_description(language).into();
+    if let Some(preview) = metadata.preview.urls.last() {
+        meta_props.set_image(preview, metadata.preview.get_alt(language));
+    }
+    let artwork = structured_data::artwork(
+        &metadata,
+        language,
+        &meta_props.site_url,
+        &meta_props.url,
+        &image_urls,
+    );
+    meta_props.add_json_ld(&artwork);
+
+    let related = related_result
+        .unwrap_or_default()
+        .into_iter()
+        .map(|related| related.stub)
+        .collect();
 
     let main_srcset = format!(
         "{} 320w, {} 640w, {} 1024w, {} 1900w",
@@ -130,43 +384,66 @@ async fn get_template(language: Language, id: Uuid) -> Result<impl Reply, Reject
     );
 
     let photo_count_number = painting_photos.len();
-    let (price, currency, photo_count) = match language {
-        Language::Cs => {
-            let photo_count_label = if photo_count_number == 1 {
-                "fotka"
-            } else if photo_count_number > 1 && photo_count_number < 5 {
-                "fotky"
-            } else {
-                "fotek"
-            };
-            (
-                metadata.price,
-                String::from("CZK"),
-                format!("{} {}", photo_count_number, photo_count_label),
-            )
-        }
-        Language::En => {
-            let photo_count_label = if photo_count_number == 1 {
-                "photo"
-            } else {
-                "photos"
-            };
-            (
-                (metadata.price as f64 / 23.0).round() as i64,
-                String::from("€"),
-                format!("{} {}", photo_count_number, photo_count_label),
-            )
-        }
+    let photo_count = get_translation_with(
+        TranslationKeys::PhotoCount,
+        language,
+        &FluentArgs::from_iter([("count", photo_count_number)]),
+    );
+
+    let dimenzions = match metadata.attributes.depth {
+        Some(depth) => format!("{}x{}x{}", metadata.width, metadata.height, depth),
+        None => format!("{}x{}", metadata.width, metadata.height),
     };
 
-    let dimenzions = format!("{}x{}", metadata.width, metadata.height);
+    let technique = [
+        metadata.attributes.get_technique(language),
+        metadata.attributes.get_support(language),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<&str>>()
+    .join(", ");
+
+    let mut details: Vec<PaintingDetail> = Vec::new();
+    if let Some(year) = metadata.attributes.year_created {
+        details.push(PaintingDetail {
+            label: get_translation(TranslationKeys::YearCreated, language),
+            value: year.to_string(),
+        });
+    }
+    if let Some(framed) = metadata.attributes.framed {
+        let key = if framed {
+            TranslationKeys::Framed
+        } else {
+            TranslationKeys::Unframed
+        };
+        details.push(PaintingDetail {
+            label: get_translation(TranslationKeys::Framing, language),
+            value: get_translation(key, language).to_string(),
+        });
+    }
+    if let Some(weight) = metadata.attributes.weight {
+        details.push(PaintingDetail {
+            label: get_translation(TranslationKeys::Weight, language),
+            value: format_weight(weight, language),
+        });
+    }
 
     let page_data = GalleryDetailPageData {
+        language,
+        id: id.to_string(),
         title: metadata.get_title(language),
         description: metadata.get_description(language),
         dimenzions: &dimenzions,
         unit: "cm",
-        technique: "oil on canvas",
+        technique: &technique,
+        details,
+        reserved_until: match metadata.availability {
+            Availability::Reserved => metadata.reserved_until,
+            _ => None,
+        },
+        reserved_until_label: get_translation(TranslationKeys::ReservedUntil, language),
+        tags,
         main_photo_sizes:
             "(max-width: 320px) 320px, (max-width: 640px) 640px, (max-width: 1024px) 1024px, 1900px",
         main_photo_srcset: &main_srcset,
@@ -174,13 +451,18 @@ async fn get_template(language: Language, id: Uuid) -> Result<impl Reply, Reject
         main_photo_alt: metadata.preview.get_alt(language),
         photos: painting_photos,
         photos_count: &photo_count,
+        related_paintings: get_translation(TranslationKeys::RelatedPaintings, language),
+        gallery_item_stubs: related,
+        sold: get_translation(TranslationKeys::Sold, language),
+        available: get_translation(TranslationKeys::Available, language),
+        reserved: get_translation(TranslationKeys::Reserved, language),
+        not_for_sale: get_translation(TranslationKeys::NotForSale, language),
     };
 
     let buy_with_price_string = format!(
-        "{} {}{}",
+        "{} {}",
         get_translation(TranslationKeys::Buy, language),
-        price,
-        currency
+        display_price(metadata.price, &metadata.currency, language)
     );
 
     let template = GalleryDetailPage {
@@ -188,10 +470,16 @@ async fn get_template(language: Language, id: Uuid) -> Result<impl Reply, Reject
         navbar: NavbarProps::default(Some(language)),
         footer: FooterProps::default(Some(language)),
         page: page_data,
+        guard: FormGuardProps::new("inquiry"),
         labels: GalleryDetailLabels {
             buy_with_price: &buy_with_price_string,
+            buy_painting: get_translation(TranslationKeys::BuyPainting, language),
             full_name: get_translation(TranslationKeys::FullName, language),
             email: get_translation(TranslationKeys::Email, language),
+            message: get_translation(TranslationKeys::Message, language),
+            send: get_translation(TranslationKeys::Send, language),
+            inquiry_sent: get_translation(TranslationKeys::InquirySent, language),
+            inquiry_failed: get_translation(TranslationKeys::InquiryFailed, language),
             close: get_translation(TranslationKeys::Close, language),
             prev: get_translation(TranslationKeys::Prev, language),
             next: get_translation(TranslationKeys::Next, language),
@@ -205,28 +493,21 @@ async fn get_template(language: Language, id: Uuid) -> Result<impl Reply, Reject
     Ok(warp::reply::html(result))
 }
 
+/// Old links without the language, redirected to the page in the language of the visitor
 pub fn get() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
     warp::get()
-        .and(path("gallery"))
-        .and(path::param::<Uuid>())
-        .and(path::end())
-        .and_then(|param| async move { get_template(Language::Cs, param).await })
-}
-
-pub fn get_cz() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
-    warp::get()
-        .and(path(Language::Cs.to_str()))
-        .and(path("gallery"))
-        .and(path::param::<Uuid>())
+        .and(path(GALLERY))
+        .and(path::param::<String>())
         .and(path::end())
-        .and_then(|param| async move { get_template(Language::Cs, param).await })
+        .and(preferred_language())
+        .and_then(|param, language| async move { get_unprefixed(language, param).await })
 }
 
-pub fn get_en() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+pub fn get_localized() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
     warp::get()
-        .and(path(Language::En.to_str()))
-        .and(path("gallery"))
-        .and(path::param::<Uuid>())
+        .and(path::param::<Language>())
+        .and(path(GALLERY))
+        .and(path::param::<String>())
         .and(path::end())
-        .and_then(|param| async move { get_template(Language::En, param).await })
+        .and_then(|language, param| async move { get_page(language, param).await.map(no_store) })
 }
@@ -1,15 +1,21 @@
 use askama::Template;
+use std::borrow::Cow;
 use warp::{path, Filter, Rejection, Reply};
 
 use crate::client::component_props::{FooterProps, MetaProps, NavbarProps};
+use crate::client::formatting::filters;
+use crate::client::negotiation::{negotiated_redirect, preferred_language};
+use crate::client::routes::Route;
+use crate::client::structured_data;
 use crate::client::translations::{get_translation, Language, TranslationKeys};
+use crate::utils::http_cache::{cache_request, CachePolicy, CacheRequest};
 
 pub struct IndexPageData<'a> {
-    title: &'a str,
+    title: Cow<'a, str>,
     hero_banner: &'a str,
-    hero_banner_alt: &'a str,
+    hero_banner_alt: Cow<'a, str>,
     author_picture: &'a str,
-    author_picture_alt: &'a str,
+    author_picture_alt: Cow<'a, str>,
     author_description: &'a str,
 }
 
@@ -34,11 +40,16 @@ pub async fn get_template(language: Language) -> Result<impl Reply, Rejection> {
     };
 
     let mut meta_props = MetaProps::default(Some(language));
-    meta_props.url = format!("www.rosemary-artist.com/{}/gallery", language.to_str());
+    meta_props.set_route(|_| Route::Index);
     meta_props.description = get_translation(TranslationKeys::IndexMetaDescription, language);
     meta_props.keywords = get_translation(TranslationKeys::IndexMetaKeywords, language);
-    meta_props.summary_large_image =
-        get_translation(TranslationKeys::IndexMetaImageSummary, language);
+    meta_props.set_image(
+        "images/hero_baner",
+        get_translation(TranslationKeys::IndexMetaImageSummary, language),
+    );
+    let artist =
+        structured_data::artist(&meta_props.site_url, &meta_props.static_base_url, language);
+    meta_props.add_json_ld(&artist);
 
     let template = IndexPage {
         meta: meta_props,
@@ -54,22 +65,23 @@ pub async fn get_template(language: Language) -> Result<impl Reply, Rejection> {
     Ok(warp::reply::html(result))
 }
 
+/// `/` redirects to the index in the language of the visitor
 pub fn get() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
     warp::get()
         .and(path::end())
-        .and_then(|| async { get_template(Language::Cs).await })
+        .and(preferred_language())
+        .and_then(|language| async move { negotiated_redirect(&Route::Index.path(language)) })
 }
 
-pub fn get_cz() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+/// `/:lang`, in every language of the catalog
+pub fn get_localized() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
     warp::get()
-        .and(path(Language::Cs.to_str()))
+        .and(path::param::<Language>())
         .and(path::end())
-        .and_then(|| async { get_template(Language::Cs).await })
-}
-
-pub fn get_en() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
-    warp::get()
-        .and(path(Language::En.to_str()))
-        .and(path::end())
-        .and_then(|| async { get_template(Language::En).await })
+        .and(cache_request())
+        .and_then(|language, request: CacheRequest| async move {
+            request
+                .reply(CachePolicy::Revalidate, || get_template(language))
+                .await
+        })
 }
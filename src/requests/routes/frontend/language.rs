@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+use warp::{path, query, Filter, Rejection, Reply};
+
+use crate::client::negotiation::{safe_next, switch_language};
+use crate::client::routes::{Route, LANGUAGE_SWITCH};
+use crate::client::translations::Language;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LanguageSwitchQuery {
+    pub next: Option<String>,
+}
+
+async fn switch(
+    code: String,
+    query: LanguageSwitchQuery,
+) -> Result<warp::reply::Response, Rejection> {
+    let Some(language) = Language::from_code(&code) else {
+        return Err(warp::reject::not_found());
+    };
+
+    let next = safe_next(query.next.as_deref(), Route::Index.path(language));
+    switch_language(language, &next)
+}
+
+pub fn get() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get()
+        .and(path(LANGUAGE_SWITCH))
+        .and(path::param::<String>())
+        .and(path::end())
+        .and(query::<LanguageSwitchQuery>())
+        .and_then(switch)
+}
@@ -0,0 +1,29 @@
+use warp::{path, Filter, Rejection, Reply};
+
+use crate::config::load::{self, ConfigField};
+use crate::errors::api_error::InternalServerError;
+use crate::utils::sitemap::render_robots;
+
+async fn get_robots() -> Result<warp::reply::Response, Rejection> {
+    let (site_url, index, disallow) = match (
+        load::get::<String>(ConfigField::SiteUrl).await,
+        load::get::<bool>(ConfigField::RobotsIndex).await,
+        load::get::<String>(ConfigField::RobotsDisallow).await,
+    ) {
+        (Ok(site_url), Ok(index), Ok(disallow)) => (site_url, index, disallow),
+        _ => return Err(warp::reject::custom(InternalServerError::new())),
+    };
+
+    let robots = render_robots(index, &disallow, &format!("{}/sitemap.xml", site_url));
+    Ok(
+        warp::reply::with_header(robots, "content-type", "text/plain; charset=utf-8")
+            .into_response(),
+    )
+}
+
+pub fn get() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get()
+        .and(path("robots.txt"))
+        .and(path::end())
+        .and_then(get_robots)
+}
@@ -0,0 +1,146 @@
+use askama::Template;
+use std::borrow::Cow;
+use warp::{path, Filter, Rejection, Reply};
+
+use crate::client::component_props::{FooterProps, MetaProps, NavbarProps};
+use crate::client::formatting::filters;
+use crate::client::routes::{Route, SERIES};
+use crate::client::translations::{get_translation, Language, TranslationKeys};
+use crate::database::connection::get_client;
+use crate::database::models::painting::{Availability, Painting, PaintingStub};
+use crate::database::models::series::Series;
+use crate::errors::api_error::InternalServerError;
+use crate::requests::dto::get_paintings_query::GetPaintingsQuery;
+use crate::utils::http_cache::{cache_request, CachePolicy, CacheRequest};
+use crate::utils::money::display_price;
+use crate::utils::slug::is_slug;
+
+#[derive(Debug)]
+struct SeriesPageData<'a> {
+    language: Language,
+    label: Cow<'a, str>,
+    title: &'a str,
+    description: &'a str,
+    cover: Option<String>,
+    no_paintings: Cow<'a, str>,
+    available: Cow<'a, str>,
+    reserved: Cow<'a, str>,
+    sold: Cow<'a, str>,
+    not_for_sale: Cow<'a, str>,
+    gallery_item_stubs: Vec<PaintingStub>,
+}
+
+impl SeriesPageData<'_> {
+    fn display_price(&self, stub: &PaintingStub) -> String {
+        display_price(stub.price, &stub.currency, self.language)
+    }
+
+    fn availability_label(&self, availability: &Availability) -> &str {
+        match availability {
+            Availability::Available => &self.available,
+            Availability::Reserved => &self.reserved,
+            Availability::Sold => &self.sold,
+            Availability::NotForSale => &self.not_for_sale,
+        }
+    }
+}
+
+#[derive(Template, Debug)]
+#[template(path = "./series.html")]
+struct SeriesPage<'a> {
+    meta: MetaProps<'a>,
+    page: SeriesPageData<'a>,
+    navbar: NavbarProps<'a>,
+    footer: FooterProps<'a>,
+}
+
+async fn get_template(language: Language, slug: String) -> Result<impl Reply, Rejection> {
+    if !is_slug(&slug) {
+        return Err(warp::reject::not_found());
+    }
+
+    let client = get_client().await.unwrap();
+    let query = Series::get_by_slug_query();
+    debug!(target: "template", "series:get - Series::get_by_slug_query {}", &query);
+    let series = match sqlx::query_as::<_, Series>(&query)
+        .bind(&slug)
+        .fetch_optional(client)
+        .await
+    {
+        Ok(Some(series)) => series,
+        Ok(None) => return Err(warp::reject::not_found()),
+        Err(error) => {
+            error!(target: "template", "series:get - failed to get series {:?}", error);
+            return Err(warp::reject::custom(InternalServerError::new()));
+        }
+    };
+
+    let mut meta_props = MetaProps::default(Some(language));
+    meta_props.set_route(|_| Route::Series(series.slug.clone()));
+    if let Some(cover) = &series.cover {
+        meta_props.set_image(cover, series.get_title(language));
+    }
+
+    let parsed_query = GetPaintingsQuery {
+        limit: Some(100),
+        offset: None,
+        sort: Some(String::from("position")),
+        order: Some(String::from("asc")),
+        series: Some(series.slug.clone()),
+        ..Default::default()
+    }
+    .safe_parse(Some(language));
+
+    let stubs_query =
+        Painting::get_all_stubs_query(parsed_query, Some(language), &meta_props.static_base_url);
+    debug!(target: "template", "series:get - Painting::get_all_stubs_query {}", &stubs_query);
+    let stubs = sqlx::query_as::<_, PaintingStub>(&stubs_query)
+        .fetch_all(client)
+        .await
+        .unwrap_or_else(|error| {
+            error!(target: "template", "series:get - failed to get paintings {:?}", error);
+            Vec::new()
+        });
+
+    let template = SeriesPage {
+        page: SeriesPageData {
+            language,
+            label: get_translation(TranslationKeys::Series, language),
+            title: series.get_title(language),
+            description: series.get_description(language),
+            cover: series
+                .cover
+                .as_ref()
+                .map(|cover| format!("{}/{}", meta_props.static_base_url, cover)),
+            no_paintings: get_translation(TranslationKeys::NoPaintings, language),
+            available: get_translation(TranslationKeys::Available, language),
+            reserved: get_translation(TranslationKeys::Reserved, language),
+            sold: get_translation(TranslationKeys::Sold, language),
+            not_for_sale: get_translation(TranslationKeys::NotForSale, language),
+            gallery_item_stubs: stubs,
+        },
+        meta: meta_props,
+        navbar: NavbarProps::default(Some(language)),
+        footer: FooterProps::default(Some(language)),
+    };
+
+    let result = template
+        .render()
+        .unwrap_or_else(|_| String::from("<h1>Internal Server Error</h1>"));
+
+    Ok(warp::reply::html(result))
+}
+
+pub fn get_localized() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get()
+        .and(path::param::<Language>())
+        .and(path(SERIES))
+        .and(path::param::<String>())
+        .and(path::end())
+        .and(cache_request())
+        .and_then(|language, slug, request: CacheRequest| async move {
+            request
+                .reply(CachePolicy::Revalidate, || get_template(language, slug))
+                .await
+        })
+}
@@ -0,0 +1,91 @@
+use warp::{path, Filter, Rejection, Reply};
+
+use crate::client::routes::Route;
+use crate::client::translations::languages;
+use crate::config::load::{self, ConfigField};
+use crate::database::connection::get_client;
+use crate::database::models::series::Series;
+use crate::database::models::sitemap::SitemapPainting;
+use crate::errors::api_error::InternalServerError;
+use crate::utils::http_cache::{cache_request, CachePolicy, CacheRequest};
+use crate::utils::sitemap::{render_sitemap, SitemapPage};
+
+async fn get_sitemap() -> Result<warp::reply::Response, Rejection> {
+    let (site_url, static_base_url) = match (
+        load::get::<String>(ConfigField::SiteUrl).await,
+        load::get::<String>(ConfigField::StaticFileUrl).await,
+    ) {
+        (Ok(site_url), Ok(static_base_url)) => (site_url, static_base_url),
+        _ => return Err(warp::reject::custom(InternalServerError::new())),
+    };
+    let static_base_url = static_base_url.trim_end_matches('/');
+
+    let client = get_client().await.unwrap();
+
+    let paintings_query = SitemapPainting::get_all_query();
+    debug!(target: "client", "sitemap:get - SitemapPainting::get_all_query {}", &paintings_query);
+    let series_query = Series::get_all_query();
+    debug!(target: "client", "sitemap:get - Series::get_all_query {}", &series_query);
+
+    let (paintings, series) = tokio::join!(
+        sqlx::query_as::<_, SitemapPainting>(&paintings_query).fetch_all(client),
+        sqlx::query_as::<_, Series>(&series_query).fetch_all(client),
+    );
+    let (paintings, series) = match (paintings, series) {
+        (Ok(paintings), Ok(series)) => (paintings, series),
+        (Err(error), _) | (_, Err(error)) => {
+            error!(target: "client", "sitemap:get - failed to list pages {:?}", error);
+            return Err(warp::reject::custom(InternalServerError::new()));
+        }
+    };
+
+    let mut pages: Vec<SitemapPage> = Route::STATIC
+        .iter()
+        .map(|route| SitemapPage::new(route, &site_url))
+        .collect();
+
+    pages.extend(
+        series
+            .iter()
+            .map(|series| SitemapPage::new(&Route::Series(series.slug.clone()), &site_url)),
+    );
+
+    pages.extend(paintings.into_iter().map(|painting| {
+        SitemapPage {
+            urls: languages()
+                .iter()
+                .map(|language| {
+                    let slug = painting
+                        .slugs
+                        .get(language.content().to_str())
+                        .cloned()
+                        .unwrap_or_else(|| painting.id.to_string());
+                    (*language, Route::Painting(slug).url(&site_url, *language))
+                })
+                .collect(),
+            lastmod: Some(painting.updated),
+            images: painting
+                .images
+                .iter()
+                .map(|image| format!("{}/{}", static_base_url, image))
+                .collect(),
+        }
+    }));
+
+    Ok(warp::reply::with_header(
+        render_sitemap(&pages),
+        "content-type",
+        "application/xml; charset=utf-8",
+    )
+    .into_response())
+}
+
+pub fn get() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get()
+        .and(path("sitemap.xml"))
+        .and(path::end())
+        .and(cache_request())
+        .and_then(|request: CacheRequest| async move {
+            request.reply(CachePolicy::Feed, get_sitemap).await
+        })
+}
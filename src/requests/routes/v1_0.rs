@@ -1,3 +1,12 @@
 pub mod auth;
+pub mod contact;
+pub mod exchange_rates;
+pub mod form_tokens;
+pub mod inquiries;
 pub mod paintings;
 pub mod paintings_images;
+pub mod search;
+pub mod series;
+pub mod tags;
+pub mod translations;
+pub mod uploads;
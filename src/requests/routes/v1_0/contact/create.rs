@@ -0,0 +1,218 @@
+use std::net::IpAddr;
+use warp::http::StatusCode;
+use warp::{body, path, Filter, Rejection, Reply};
+
+use crate::client::translations::Language;
+use crate::config::load::{get, ConfigField};
+use crate::database::connection::get_client;
+use crate::database::models::contact_message::{ContactMessage, ContactMessageCreate};
+use crate::errors::api_error::{InternalServerError, TooManyRequestsError, ValidationError};
+use crate::requests::dto::generic_response::{GenericResponse, Status};
+use crate::requests::routes::frontend::contact::thank_you_template;
+use crate::utils::mail::{get_mailer, Mail};
+use crate::utils::spam::{client_ip, verify, SpamRejection};
+
+/// How the submission arrived, browsers without JavaScript post a plain form
+/// and expect a page back.
+#[derive(Debug, Clone, Copy)]
+enum Submission {
+    Json,
+    Form,
+}
+
+async fn notify(message: ContactMessage) {
+    let mailer = match get_mailer().await {
+        Ok(value) => value,
+        Err(error) => {
+            error!(target: "mail", "contact:notify - mailer not available {}", error);
+            return;
+        }
+    };
+
+    let notify_to = match get::<String>(ConfigField::MailNotifyTo).await {
+        Ok(value) => value,
+        Err(error) => {
+            error!(target: "mail", "contact:notify - missing recipient {}", error);
+            return;
+        }
+    };
+
+    let mail = Mail {
+        to: notify_to,
+        reply_to: Some(message.email.clone()),
+        subject: format!(
+            "[rosemary-artist.com] {} - {}",
+            message.subject, message.name
+        ),
+        body: format!(
+            "name: {}\nemail: {}\nphone: {}\nsubject: {}\nlanguage: {}\n\n{}",
+            message.name,
+            message.email,
+            message.phone.as_deref().unwrap_or("-"),
+            message.subject,
+            message.language,
+            message.message
+        ),
+    };
+
+    if let Err(error) = mailer.send(&mail).await {
+        error!(target: "mail", "contact:notify - {}", error);
+        return;
+    }
+
+    let client = get_client().await.unwrap();
+    let query = ContactMessage::set_notified_query(message.id);
+    debug!(target: "db", "contact:notify - ContactMessage::set_notified_query {}", &query);
+    if let Err(error) = sqlx::query(&query).execute(client).await {
+        error!(target: "db", "contact:notify - failed to mark message as notified {}", error);
+    }
+}
+
+fn respond(
+    submission: Submission,
+    language: Language,
+    message: &str,
+    status_code: StatusCode,
+) -> warp::reply::Response {
+    match submission {
+        Submission::Json => {
+            let status = if status_code.is_success() {
+                Status::Success
+            } else {
+                Status::Error
+            };
+            GenericResponse::<()>::send(status, message, None, status_code).into_response()
+        }
+        Submission::Form => {
+            let page_status = if status_code.is_success() {
+                StatusCode::OK
+            } else {
+                status_code
+            };
+            warp::reply::with_status(
+                warp::reply::html(thank_you_template(language, status_code.is_success())),
+                page_status,
+            )
+            .into_response()
+        }
+    }
+}
+
+async fn submit_contact_form(
+    data: ContactMessageCreate,
+    submission: Submission,
+    ip: Option<IpAddr>,
+) -> Result<warp::reply::Response, Rejection> {
+    let language = data
+        .lang
+        .as_deref()
+        .and_then(Language::from_code)
+        .unwrap_or(Language::Cs);
+
+    // a typo in the form must not burn the single-use token
+    let data = match data.validate() {
+        Ok(value) => value,
+        Err(message) => {
+            debug!(target: "api", "contact:create - validation failed {}", message);
+            return Ok(match submission {
+                Submission::Json => ValidationError::new(Some(message))
+                    .response()
+                    .await
+                    .into_response(),
+                Submission::Form => respond(submission, language, message, StatusCode::BAD_REQUEST),
+            });
+        }
+    };
+
+    match verify("contact", &data.guard, ip) {
+        Ok(()) => {}
+        Err(SpamRejection::Honeypot) => {
+            debug!(target: "api", "contact:create - honeypot filled in, dropping message");
+            return Ok(respond(
+                submission,
+                language,
+                "contactMessageCreated",
+                StatusCode::CREATED,
+            ));
+        }
+        Err(SpamRejection::RateLimited) => {
+            debug!(target: "api", "contact:create - rate limited {:?}", ip);
+            return Ok(match submission {
+                Submission::Json => TooManyRequestsError::new().response().await.into_response(),
+                Submission::Form => respond(
+                    submission,
+                    language,
+                    "tooManyRequests",
+                    StatusCode::TOO_MANY_REQUESTS,
+                ),
+            });
+        }
+        Err(SpamRejection::Invalid(message)) => {
+            debug!(target: "api", "contact:create - spam check failed {}", message);
+            return Ok(match submission {
+                Submission::Json => ValidationError::new(Some(message))
+                    .response()
+                    .await
+                    .into_response(),
+                Submission::Form => respond(submission, language, message, StatusCode::BAD_REQUEST),
+            });
+        }
+    }
+
+    let client = get_client().await.unwrap();
+    let query = ContactMessage::create_query();
+    debug!(target: "db", "contact:create - ContactMessage::create_query {}", &query);
+    let create_result = sqlx::query_as::<_, ContactMessage>(&query)
+        .bind(&data.name)
+        .bind(&data.email)
+        .bind(&data.phone)
+        .bind(data.subject.as_deref().unwrap_or("general"))
+        .bind(&data.message)
+        .bind(language.to_str())
+        .fetch_one(client)
+        .await;
+
+    let message = match create_result {
+        Ok(value) => value,
+        Err(error) => {
+            error!(target: "api", "contact:create - error {:?}", error);
+            return Ok(match submission {
+                Submission::Json => InternalServerError::new().response().await.into_response(),
+                Submission::Form => respond(
+                    submission,
+                    language,
+                    "internalServerError",
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                ),
+            });
+        }
+    };
+
+    tokio::spawn(notify(message));
+
+    Ok(respond(
+        submission,
+        language,
+        "contactMessageCreated",
+        StatusCode::CREATED,
+    ))
+}
+
+pub fn create() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    let json_body = body::json::<ContactMessageCreate>().map(|data| (data, Submission::Json));
+    let form_body = body::form::<ContactMessageCreate>().map(|data| (data, Submission::Form));
+
+    warp::post()
+        .and(path("api"))
+        .and(path("v1.0"))
+        .and(path("contact-form"))
+        .and(path::end())
+        .and(body::content_length_limit(64 * 1024))
+        .and(json_body.or(form_body).unify())
+        .and(client_ip())
+        .and_then(
+            |(data, submission): (ContactMessageCreate, Submission), ip: Option<IpAddr>| async move {
+                submit_contact_form(data, submission, ip).await
+            },
+        )
+}
@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+use warp::{path, query, Filter, Rejection, Reply};
+
+use crate::database::connection::get_client;
+use crate::database::models::contact_message::ContactMessage;
+use crate::errors::api_error::InternalServerError;
+use crate::requests::dto::paginated_result::PaginatedResult;
+use crate::utils::auth::token::{jwt_auth, Claims};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContactMessagesQuery {
+    pub limit: Option<u8>,
+    pub offset: Option<u32>,
+}
+
+async fn get_contact_messages(params: ContactMessagesQuery) -> Result<impl Reply, Rejection> {
+    let client = get_client().await.unwrap();
+    let limit = params.limit.unwrap_or(25).clamp(1, 100);
+    let offset = params.offset.unwrap_or(0);
+
+    let count_query = ContactMessage::count_all_query();
+    let rows_query = ContactMessage::get_all_query(limit, offset);
+    debug!(target: "db", "contact:get_all - ContactMessage::get_all_query {}", &rows_query);
+
+    let (count, rows) = tokio::join!(
+        sqlx::query_as::<_, (i64,)>(&count_query).fetch_one(client),
+        sqlx::query_as::<_, ContactMessage>(&rows_query).fetch_all(client)
+    );
+
+    match (count, rows) {
+        (Ok((count,)), Ok(rows)) => {
+            let result = PaginatedResult::new(rows, count);
+            Ok(warp::reply::with_status(
+                warp::reply::json(&result),
+                warp::http::StatusCode::OK,
+            ))
+        }
+        (Err(error), _) | (_, Err(error)) => {
+            error!(target: "api", "contact:get_all - failed {:?}", error);
+            Ok(InternalServerError::new().response().await)
+        }
+    }
+}
+
+pub fn get() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get()
+        .and(path("api"))
+        .and(path("v1.0"))
+        .and(path("contact-messages"))
+        .and(path::end())
+        .and(query::<ContactMessagesQuery>())
+        .and(jwt_auth())
+        .and_then(|params: ContactMessagesQuery, _claims: Claims| async move {
+            get_contact_messages(params).await
+        })
+}
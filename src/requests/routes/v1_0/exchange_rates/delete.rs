@@ -0,0 +1,52 @@
+use warp::{path, Filter, Rejection, Reply};
+
+use crate::database::connection::get_client;
+use crate::database::models::exchange_rate::ExchangeRate;
+use crate::errors::api_error::{InternalServerError, NotFoundError};
+use crate::requests::dto::generic_response::{GenericResponse, Status};
+use crate::utils::auth::token::{jwt_auth, Claims};
+use crate::utils::money::load_rates;
+
+async fn delete_exchange_rate(currency: String) -> Result<warp::reply::Response, Rejection> {
+    let client = get_client().await.unwrap();
+
+    let query = ExchangeRate::delete_query();
+    debug!(target: "db", "exchange_rates:delete - ExchangeRate::delete_query {}", &query);
+    match sqlx::query_as::<_, ExchangeRate>(&query)
+        .bind(currency.to_uppercase())
+        .fetch_optional(client)
+        .await
+    {
+        Ok(Some(_)) => {}
+        Ok(None) => return Ok(NotFoundError::new().response().await.into_response()),
+        Err(error) => {
+            error!(target: "api", "exchange_rates:delete - error {:?}", error);
+            return Ok(InternalServerError::new().response().await.into_response());
+        }
+    };
+
+    if let Err(error) = load_rates().await {
+        error!(target: "api", "exchange_rates:delete - failed to reload rates {:?}", error);
+    }
+
+    Ok(GenericResponse::<()>::send(
+        Status::Success,
+        "exchangeRateDeleted",
+        None,
+        warp::http::StatusCode::OK,
+    )
+    .into_response())
+}
+
+pub fn delete() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::delete()
+        .and(path("api"))
+        .and(path("v1.0"))
+        .and(path("exchange-rates"))
+        .and(path::param::<String>())
+        .and(path::end())
+        .and(jwt_auth())
+        .and_then(|currency: String, _claims: Claims| async move {
+            delete_exchange_rate(currency).await
+        })
+}
@@ -0,0 +1,40 @@
+use warp::{path, Filter, Rejection, Reply};
+
+use crate::database::connection::get_client;
+use crate::database::models::exchange_rate::ExchangeRate;
+use crate::errors::api_error::InternalServerError;
+use crate::utils::http_cache::{cache_request, CachePolicy, CacheRequest};
+
+async fn get_exchange_rates() -> Result<impl Reply, Rejection> {
+    let client = get_client().await.unwrap();
+
+    let query = ExchangeRate::get_all_query();
+    debug!(target: "db", "exchange_rates:get_all - ExchangeRate::get_all_query {}", &query);
+    match sqlx::query_as::<_, ExchangeRate>(&query)
+        .fetch_all(client)
+        .await
+    {
+        Ok(rates) => Ok(warp::reply::with_status(
+            warp::reply::json(&rates),
+            warp::http::StatusCode::OK,
+        )),
+        Err(error) => {
+            error!(target: "api", "exchange_rates:get_all - error {:?}", error);
+            Ok(InternalServerError::new().response().await)
+        }
+    }
+}
+
+pub fn get() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get()
+        .and(path("api"))
+        .and(path("v1.0"))
+        .and(path("exchange-rates"))
+        .and(path::end())
+        .and(cache_request())
+        .and_then(|request: CacheRequest| async move {
+            request
+                .reply(CachePolicy::Revalidate, get_exchange_rates)
+                .await
+        })
+}
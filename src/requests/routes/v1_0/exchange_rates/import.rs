@@ -0,0 +1,49 @@
+use bytes::Bytes;
+use warp::{body, path, Filter, Rejection, Reply};
+
+use crate::errors::api_error::{InternalServerError, ValidationError};
+use crate::requests::dto::generic_response::{GenericResponse, Status};
+use crate::utils::auth::token::{jwt_auth, Claims};
+use crate::utils::money::{cnb, store_rates};
+
+/// Takes the CNB daily rates text as the request body.
+async fn import_exchange_rates(data: Bytes) -> Result<warp::reply::Response, Rejection> {
+    let text = String::from_utf8_lossy(&data);
+    let rates = match cnb::parse(&text) {
+        Ok(value) => value,
+        Err(error) => {
+            debug!(target: "api", "exchange_rates:import - {}", error);
+            return Ok(ValidationError::new(Some("invalidExchangeRatesFile"))
+                .response()
+                .await
+                .into_response());
+        }
+    };
+
+    match store_rates(&rates).await {
+        Ok(stored) => Ok(GenericResponse::send(
+            Status::Success,
+            "exchangeRatesImported",
+            Some(stored),
+            warp::http::StatusCode::OK,
+        )
+        .into_response()),
+        Err(error) => {
+            error!(target: "api", "exchange_rates:import - error {:?}", error);
+            Ok(InternalServerError::new().response().await.into_response())
+        }
+    }
+}
+
+pub fn import() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::post()
+        .and(path("api"))
+        .and(path("v1.0"))
+        .and(path("exchange-rates"))
+        .and(path("import"))
+        .and(path::end())
+        .and(body::content_length_limit(256 * 1024))
+        .and(body::bytes())
+        .and(jwt_auth())
+        .and_then(|data: Bytes, _claims: Claims| async move { import_exchange_rates(data).await })
+}
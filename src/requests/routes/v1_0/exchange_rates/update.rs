@@ -0,0 +1,75 @@
+use warp::{body, path, Filter, Rejection, Reply};
+
+use crate::database::connection::get_client;
+use crate::database::models::exchange_rate::{ExchangeRate, ExchangeRateUpdate};
+use crate::errors::api_error::{InternalServerError, ValidationError};
+use crate::requests::dto::generic_response::{GenericResponse, Status};
+use crate::utils::auth::token::{jwt_auth, Claims};
+use crate::utils::money::{is_currency_code, load_rates, BASE_CURRENCY};
+
+async fn update_exchange_rate(
+    currency: String,
+    data: ExchangeRateUpdate,
+) -> Result<warp::reply::Response, Rejection> {
+    let currency = currency.to_uppercase();
+    if !is_currency_code(&currency) || currency == BASE_CURRENCY {
+        return Ok(ValidationError::new(Some("invalidCurrency"))
+            .response()
+            .await
+            .into_response());
+    }
+
+    let amount = data.amount.unwrap_or(1);
+    if amount < 1 || !data.rate.is_finite() || data.rate <= 0.0 {
+        return Ok(ValidationError::new(Some("invalidExchangeRate"))
+            .response()
+            .await
+            .into_response());
+    }
+
+    let client = get_client().await.unwrap();
+    let query = ExchangeRate::upsert_query();
+    debug!(target: "db", "exchange_rates:update - ExchangeRate::upsert_query {}", &query);
+    let rate = match sqlx::query_as::<_, ExchangeRate>(&query)
+        .bind(&currency)
+        .bind(amount)
+        .bind(data.rate)
+        .fetch_one(client)
+        .await
+    {
+        Ok(value) => value,
+        Err(error) => {
+            error!(target: "api", "exchange_rates:update - error {:?}", error);
+            return Ok(InternalServerError::new().response().await.into_response());
+        }
+    };
+
+    if let Err(error) = load_rates().await {
+        error!(target: "api", "exchange_rates:update - failed to reload rates {:?}", error);
+    }
+
+    Ok(GenericResponse::send(
+        Status::Success,
+        "exchangeRateUpdated",
+        Some(rate),
+        warp::http::StatusCode::OK,
+    )
+    .into_response())
+}
+
+pub fn update() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::put()
+        .and(path("api"))
+        .and(path("v1.0"))
+        .and(path("exchange-rates"))
+        .and(path::param::<String>())
+        .and(path::end())
+        .and(body::content_length_limit(4 * 1024))
+        .and(body::json::<ExchangeRateUpdate>())
+        .and(jwt_auth())
+        .and_then(
+            |currency: String, data: ExchangeRateUpdate, _claims: Claims| async move {
+                update_exchange_rate(currency, data).await
+            },
+        )
+}
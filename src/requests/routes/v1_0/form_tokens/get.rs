@@ -0,0 +1,46 @@
+use serde::Serialize;
+use warp::http::header::{HeaderValue, CACHE_CONTROL};
+use warp::{path, Filter, Rejection, Reply};
+
+use crate::errors::api_error::NotFoundError;
+use crate::requests::dto::generic_response::{GenericResponse, Status};
+use crate::utils::spam::{difficulty, issue_token, GUARDED_FORMS};
+
+#[derive(Debug, Serialize)]
+pub struct FormTokenResponse {
+    pub token: String,
+    pub difficulty: u8,
+}
+
+async fn get_form_token(form: String) -> Result<warp::reply::Response, Rejection> {
+    if !GUARDED_FORMS.contains(&form.as_str()) {
+        return Ok(NotFoundError::new().response().await.into_response());
+    }
+
+    let data = FormTokenResponse {
+        token: issue_token(&form),
+        difficulty: difficulty(),
+    };
+
+    let mut response = GenericResponse::send(
+        Status::Success,
+        "formTokenCreated",
+        Some(data),
+        warp::http::StatusCode::OK,
+    )
+    .into_response();
+    response
+        .headers_mut()
+        .insert(CACHE_CONTROL, HeaderValue::from_static("no-store"));
+    Ok(response)
+}
+
+pub fn get() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get()
+        .and(path("api"))
+        .and(path("v1.0"))
+        .and(path("form-tokens"))
+        .and(path::param::<String>())
+        .and(path::end())
+        .and_then(|form: String| async move { get_form_token(form).await })
+}
@@ -0,0 +1,4 @@
+pub mod answer;
+pub mod close;
+pub mod create;
+pub mod get_all;
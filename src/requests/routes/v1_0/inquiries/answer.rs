@@ -0,0 +1,117 @@
+use uuid::Uuid;
+use warp::{body, path, Filter, Rejection, Reply};
+
+use crate::config::load::{get, ConfigField};
+use crate::database::connection::get_client;
+use crate::database::models::inquiry::{PaintingInquiry, PaintingInquiryAnswer};
+use crate::errors::api_error::{InternalServerError, NotFoundError, ValidationError};
+use crate::requests::dto::generic_response::{GenericResponse, Status};
+use crate::utils::auth::token::{jwt_auth, Claims};
+use crate::utils::http_cache::content_changed;
+use crate::utils::mail::{get_mailer, Mail};
+
+/// Sends the answer to the inquirer, a failed delivery rolls the stored answer back.
+async fn send_answer(inquiry: &PaintingInquiry, answer: &str) -> Result<(), String> {
+    let mailer = get_mailer().await.map_err(|error| error.to_string())?;
+    let reply_to = get::<String>(ConfigField::MailNotifyTo)
+        .await
+        .map_err(|error| error.to_string())?;
+
+    let subject = match inquiry.language.as_str() {
+        "en" => "Your inquiry at rosemary-artist.com",
+        _ => "Vaše poptávka na rosemary-artist.com",
+    };
+
+    let mail = Mail {
+        to: inquiry.email.clone(),
+        reply_to: Some(reply_to),
+        subject: subject.to_string(),
+        body: answer.to_string(),
+    };
+
+    mailer.send(&mail).await.map_err(|error| error.to_string())
+}
+
+async fn answer_inquiry(
+    id: Uuid,
+    data: PaintingInquiryAnswer,
+) -> Result<warp::reply::Response, Rejection> {
+    let answer = data.answer.trim();
+    if answer.is_empty() {
+        return Ok(ValidationError::new(Some("invalidAnswer"))
+            .response()
+            .await
+            .into_response());
+    }
+
+    let client = get_client().await.unwrap();
+    let mut transaction = match client.begin().await {
+        Ok(value) => value,
+        Err(error) => {
+            error!(target: "api", "inquiries:answer - error {:?}", error);
+            return Ok(InternalServerError::new().response().await.into_response());
+        }
+    };
+
+    // the row stays locked until the mail is out, a closed inquiry gets neither
+    let query = PaintingInquiry::answer_query(id, data.close.unwrap_or(false));
+    debug!(target: "db", "inquiries:answer - PaintingInquiry::answer_query {}", &query);
+    let inquiry = match sqlx::query_as::<_, PaintingInquiry>(&query)
+        .bind(answer)
+        .fetch_optional(&mut *transaction)
+        .await
+    {
+        Ok(Some(value)) => value,
+        Ok(None) => return Ok(NotFoundError::new().response().await.into_response()),
+        Err(error) => {
+            error!(target: "api", "inquiries:answer - error {:?}", error);
+            return Ok(InternalServerError::new().response().await.into_response());
+        }
+    };
+
+    if let Err(error) = send_answer(&inquiry, answer).await {
+        error!(target: "mail", "inquiries:answer - {}", error);
+        if let Err(error) = transaction.rollback().await {
+            error!(target: "api", "inquiries:answer - error {:?}", error);
+        }
+        return Ok(GenericResponse::<()>::send(
+            Status::Error,
+            "inquiryAnswerNotSent",
+            None,
+            warp::http::StatusCode::BAD_GATEWAY,
+        )
+        .into_response());
+    }
+
+    if let Err(error) = transaction.commit().await {
+        error!(target: "api", "inquiries:answer - answer sent but not stored {:?}", error);
+        return Ok(InternalServerError::new().response().await.into_response());
+    }
+
+    content_changed();
+    Ok(GenericResponse::send(
+        Status::Success,
+        "inquiryAnswered",
+        Some(inquiry),
+        warp::http::StatusCode::OK,
+    )
+    .into_response())
+}
+
+pub fn answer() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::post()
+        .and(path("api"))
+        .and(path("v1.0"))
+        .and(path("inquiries"))
+        .and(path::param::<Uuid>())
+        .and(path("answer"))
+        .and(path::end())
+        .and(body::content_length_limit(64 * 1024))
+        .and(body::json::<PaintingInquiryAnswer>())
+        .and(jwt_auth())
+        .and_then(
+            |id: Uuid, data: PaintingInquiryAnswer, _claims: Claims| async move {
+                answer_inquiry(id, data).await
+            },
+        )
+}
@@ -0,0 +1,48 @@
+use uuid::Uuid;
+use warp::{path, Filter, Rejection, Reply};
+
+use crate::database::connection::get_client;
+use crate::database::models::inquiry::PaintingInquiry;
+use crate::errors::api_error::{InternalServerError, NotFoundError};
+use crate::requests::dto::generic_response::{GenericResponse, Status};
+use crate::utils::auth::token::{jwt_auth, Claims};
+use crate::utils::http_cache::content_changed;
+
+async fn close_inquiry(id: Uuid) -> Result<warp::reply::Response, Rejection> {
+    let client = get_client().await.unwrap();
+
+    let query = PaintingInquiry::close_query(id);
+    debug!(target: "db", "inquiries:close - PaintingInquiry::close_query {}", &query);
+    match sqlx::query_as::<_, PaintingInquiry>(&query)
+        .fetch_optional(client)
+        .await
+    {
+        Ok(Some(inquiry)) => {
+            content_changed();
+            Ok(GenericResponse::send(
+                Status::Success,
+                "inquiryClosed",
+                Some(inquiry),
+                warp::http::StatusCode::OK,
+            )
+            .into_response())
+        }
+        Ok(None) => Ok(NotFoundError::new().response().await.into_response()),
+        Err(error) => {
+            error!(target: "api", "inquiries:close - error {:?}", error);
+            Ok(InternalServerError::new().response().await.into_response())
+        }
+    }
+}
+
+pub fn close() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::post()
+        .and(path("api"))
+        .and(path("v1.0"))
+        .and(path("inquiries"))
+        .and(path::param::<Uuid>())
+        .and(path("close"))
+        .and(path::end())
+        .and(jwt_auth())
+        .and_then(|id: Uuid, _claims: Claims| async move { close_inquiry(id).await })
+}
@@ -0,0 +1,173 @@
+use std::net::IpAddr;
+use uuid::Uuid;
+use warp::http::StatusCode;
+use warp::{body, path, Filter, Rejection, Reply};
+
+use crate::client::routes::{site_url, Route};
+use crate::client::translations::Language;
+use crate::config::load::{get, ConfigField};
+use crate::database::connection::get_client;
+use crate::database::models::inquiry::{PaintingInquiry, PaintingInquiryCreate};
+use crate::errors::api_error::{
+    InternalServerError, NotFoundError, TooManyRequestsError, ValidationError,
+};
+use crate::requests::dto::generic_response::{GenericResponse, Status};
+use crate::utils::http_cache::content_changed;
+use crate::utils::mail::{get_mailer, Mail};
+use crate::utils::spam::{client_ip, verify, SpamRejection};
+
+async fn notify(inquiry: PaintingInquiry) {
+    let mailer = match get_mailer().await {
+        Ok(value) => value,
+        Err(error) => {
+            error!(target: "mail", "inquiries:notify - mailer not available {}", error);
+            return;
+        }
+    };
+
+    let notify_to = match get::<String>(ConfigField::MailNotifyTo).await {
+        Ok(value) => value,
+        Err(error) => {
+            error!(target: "mail", "inquiries:notify - missing recipient {}", error);
+            return;
+        }
+    };
+
+    let reserved_until = inquiry
+        .reserved_until
+        .map(|value| value.format("%Y-%m-%d %H:%M UTC").to_string())
+        .unwrap_or_else(|| String::from("-"));
+
+    let mail = Mail {
+        to: notify_to,
+        reply_to: Some(inquiry.email.clone()),
+        subject: format!("[rosemary-artist.com] inquiry - {}", inquiry.full_name),
+        body: format!(
+            "painting: {}\nname: {}\nemail: {}\nlanguage: {}\nreserved until: {}\n\n{}",
+            Route::Painting(inquiry.painting_id.to_string()).url(&site_url(), Language::Cs),
+            inquiry.full_name,
+            inquiry.email,
+            inquiry.language,
+            reserved_until,
+            inquiry.message.as_deref().unwrap_or("")
+        ),
+    };
+
+    if let Err(error) = mailer.send(&mail).await {
+        error!(target: "mail", "inquiries:notify - {}", error);
+        return;
+    }
+
+    let client = get_client().await.unwrap();
+    let query = PaintingInquiry::set_notified_query(inquiry.id);
+    debug!(target: "db", "inquiries:notify - PaintingInquiry::set_notified_query {}", &query);
+    if let Err(error) = sqlx::query(&query).execute(client).await {
+        error!(target: "db", "inquiries:notify - failed to mark inquiry as notified {}", error);
+    }
+}
+
+async fn create_inquiry(
+    painting_id: Uuid,
+    data: PaintingInquiryCreate,
+    ip: Option<IpAddr>,
+) -> Result<warp::reply::Response, Rejection> {
+    // a typo in the form must not burn the single-use token
+    let data = match data.validate() {
+        Ok(value) => value,
+        Err(message) => {
+            return Ok(ValidationError::new(Some(message))
+                .response()
+                .await
+                .into_response())
+        }
+    };
+
+    match verify("inquiry", &data.guard, ip) {
+        Ok(()) => {}
+        Err(SpamRejection::Honeypot) => {
+            debug!(target: "api", "inquiries:create - honeypot filled in, dropping inquiry");
+            return Ok(GenericResponse::<()>::send(
+                Status::Success,
+                "inquiryCreated",
+                None,
+                StatusCode::CREATED,
+            )
+            .into_response());
+        }
+        Err(SpamRejection::RateLimited) => {
+            debug!(target: "api", "inquiries:create - rate limited {:?}", ip);
+            return Ok(TooManyRequestsError::new().response().await.into_response());
+        }
+        Err(SpamRejection::Invalid(message)) => {
+            debug!(target: "api", "inquiries:create - spam check failed {}", message);
+            return Ok(ValidationError::new(Some(message))
+                .response()
+                .await
+                .into_response());
+        }
+    }
+
+    let language = data
+        .lang
+        .as_deref()
+        .and_then(Language::from_code)
+        .unwrap_or(Language::Cs);
+
+    let reservation_hours = get::<u32>(ConfigField::InquiryReservationHours)
+        .await
+        .unwrap_or(0);
+
+    let client = get_client().await.unwrap();
+    let query = PaintingInquiry::create_query(reservation_hours);
+    debug!(target: "db", "inquiries:create - PaintingInquiry::create_query {}", &query);
+    let create_result = sqlx::query_as::<_, PaintingInquiry>(&query)
+        .bind(painting_id)
+        .bind(&data.full_name)
+        .bind(&data.email)
+        .bind(&data.message)
+        .bind(language.to_str())
+        .fetch_optional(client)
+        .await;
+
+    let inquiry = match create_result {
+        Ok(Some(value)) => value,
+        Ok(None) => return Ok(NotFoundError::new().response().await.into_response()),
+        Err(error) => {
+            error!(target: "api", "inquiries:create - error {:?}", error);
+            return Ok(InternalServerError::new().response().await.into_response());
+        }
+    };
+
+    let reserved_until = inquiry.reserved_until;
+    if reserved_until.is_some() {
+        content_changed();
+    }
+    tokio::spawn(notify(inquiry));
+
+    // the inquirer only learns whether and until when the painting is held for them
+    Ok(GenericResponse::send(
+        Status::Success,
+        "inquiryCreated",
+        Some(serde_json::json!({ "reserved_until": reserved_until })),
+        StatusCode::CREATED,
+    )
+    .into_response())
+}
+
+pub fn create() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::post()
+        .and(path("api"))
+        .and(path("v1.0"))
+        .and(path("paintings"))
+        .and(path::param::<Uuid>())
+        .and(path("inquiries"))
+        .and(path::end())
+        .and(body::content_length_limit(64 * 1024))
+        .and(body::json::<PaintingInquiryCreate>())
+        .and(client_ip())
+        .and_then(
+            |painting_id: Uuid, data: PaintingInquiryCreate, ip: Option<IpAddr>| async move {
+                create_inquiry(painting_id, data, ip).await
+            },
+        )
+}
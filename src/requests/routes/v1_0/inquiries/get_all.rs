@@ -0,0 +1,49 @@
+use warp::{path, query, Filter, Rejection, Reply};
+
+use crate::database::connection::get_client;
+use crate::database::models::inquiry::{PaintingInquiriesQuery, PaintingInquiry};
+use crate::errors::api_error::InternalServerError;
+use crate::requests::dto::paginated_result::PaginatedResult;
+use crate::utils::auth::token::{jwt_auth, Claims};
+
+async fn get_inquiries(params: PaintingInquiriesQuery) -> Result<impl Reply, Rejection> {
+    let client = get_client().await.unwrap();
+    let limit = params.limit.unwrap_or(25).clamp(1, 100);
+    let offset = params.offset.unwrap_or(0);
+
+    let count_query = PaintingInquiry::count_all_query(&params);
+    let rows_query = PaintingInquiry::get_all_query(&params, limit, offset);
+    debug!(target: "db", "inquiries:get_all - PaintingInquiry::get_all_query {}", &rows_query);
+
+    let (count, rows) = tokio::join!(
+        sqlx::query_as::<_, (i64,)>(&count_query).fetch_one(client),
+        sqlx::query_as::<_, PaintingInquiry>(&rows_query).fetch_all(client)
+    );
+
+    match (count, rows) {
+        (Ok((count,)), Ok(rows)) => {
+            let result = PaginatedResult::new(rows, count);
+            Ok(warp::reply::with_status(
+                warp::reply::json(&result),
+                warp::http::StatusCode::OK,
+            ))
+        }
+        (Err(error), _) | (_, Err(error)) => {
+            error!(target: "api", "inquiries:get_all - failed {:?}", error);
+            Ok(InternalServerError::new().response().await)
+        }
+    }
+}
+
+pub fn get() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get()
+        .and(path("api"))
+        .and(path("v1.0"))
+        .and(path("inquiries"))
+        .and(path::end())
+        .and(query::<PaintingInquiriesQuery>())
+        .and(jwt_auth())
+        .and_then(|params: PaintingInquiriesQuery, _claims: Claims| async move {
+            get_inquiries(params).await
+        })
+}
@@ -2,4 +2,6 @@ pub mod create;
 pub mod delete;
 pub mod get;
 pub mod get_all;
+pub mod related;
 pub mod update;
+pub mod update_tags;
@@ -1,21 +1,49 @@
+use sqlx::{Pool, Postgres};
 use warp::{path, Filter, Rejection, Reply};
 
 use crate::database::connection::get_client;
 use crate::database::models::painting::{Painting, PaintingBase, PaintingCreate};
-use crate::errors::api_error::InternalServerError;
+use crate::errors::api_error::{InternalServerError, ValidationError};
 use crate::requests::dto::generic_response::{GenericResponse, Status};
 use crate::utils::auth::token::{jwt_auth, Claims};
+use crate::utils::http_cache::content_changed;
+use crate::utils::money::is_currency_code;
+use crate::utils::slug::assign_slugs;
+
+async fn insert_painting(
+    client: &Pool<Postgres>,
+    query: &str,
+) -> Result<PaintingBase, sqlx::Error> {
+    let mut transaction = client.begin().await?;
+    let painting = sqlx::query_as::<_, PaintingBase>(query)
+        .fetch_one(&mut *transaction)
+        .await?;
+    if let Some(title) = &painting.painting_title {
+        assign_slugs(&mut transaction, painting.id, title).await?;
+    }
+    transaction.commit().await?;
+    Ok(painting)
+}
+
+async fn create_painting(mut data: PaintingCreate) -> Result<impl Reply, Rejection> {
+    data.currency = data.currency.map(|code| code.trim().to_uppercase());
+    if !data.currency.as_deref().is_none_or(is_currency_code) {
+        return Ok(ValidationError::new(Some("invalidCurrency"))
+            .response()
+            .await);
+    }
+
+    if let Err(key) = data.attributes.validate() {
+        return Ok(ValidationError::new(Some(key)).response().await);
+    }
 
-async fn create_painting(data: PaintingCreate) -> Result<impl Reply, Rejection> {
     let client = get_client().await.unwrap().clone();
     debug!(target: "api", "paintings:create - database client aquired");
     debug!(target: "api", "paintings:create - data {:?}", &data);
 
     let query = Painting::create_query(data);
     debug!(target: "db", "paitings:create - Painting::create_query {}", &query);
-    let create_result = sqlx::query_as::<_, PaintingBase>(&query)
-        .fetch_one(&client)
-        .await;
+    let create_result = insert_painting(&client, &query).await;
 
     let painting_base = match create_result {
         Ok(painting) => {
@@ -28,6 +56,7 @@ async fn create_painting(data: PaintingCreate) -> Result<impl Reply, Rejection>
         }
     };
 
+    content_changed();
     let response = GenericResponse::<PaintingBase> {
         status: Status::Success,
         message: "Painting created successfully",
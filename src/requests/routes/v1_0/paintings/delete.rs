@@ -6,6 +6,7 @@ use crate::database::models::painting::{Painting, PaintingDelete};
 use crate::errors::api_error::InternalServerError;
 use crate::requests::dto::generic_response::{GenericResponse, Status};
 use crate::utils::auth::token::{jwt_auth, Claims};
+use crate::utils::http_cache::content_changed;
 
 async fn delete_painting(
     painting_uid: Uuid,
@@ -21,6 +22,7 @@ async fn delete_painting(
 
     match deleted {
         Ok(_) => {
+            content_changed();
             let response = GenericResponse::<PaintingDelete> {
                 status: Status::Success,
                 message: "paintingDeleted",
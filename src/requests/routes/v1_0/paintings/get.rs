@@ -8,6 +8,7 @@ use crate::database::connection::get_client;
 use crate::database::models::image::PaintingImage;
 use crate::database::models::painting::Painting;
 use crate::errors::api_error::InternalServerError;
+use crate::utils::http_cache::{cache_request, CachePolicy, CacheRequest};
 
 #[derive(Serialize)]
 pub struct GetPaintingResult {
@@ -98,5 +99,10 @@ pub fn get() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
             debug!(target: "api", "Route matched with UUID: {}", uuid); // Debug print
             uuid
         })
-        .and_then(get_painting)
+        .and(cache_request())
+        .and_then(|uuid, request: CacheRequest| async move {
+            request
+                .reply(CachePolicy::Revalidate, || get_painting(uuid))
+                .await
+        })
 }
@@ -1,52 +1,145 @@
-use sqlx::{Pool, Postgres};
-use std::sync::Arc;
 use warp::{path, query, Filter, Rejection, Reply};
 
+use crate::client::translations::Language;
 use crate::database::connection::get_client;
+use crate::database::models::facets::Facets;
 use crate::database::models::painting::Painting;
+use crate::errors::api_error::{InternalServerError, ValidationError};
+use crate::requests::dto::cursor::Cursor;
+use crate::requests::dto::faceted_result::FacetedResult;
 use crate::requests::dto::get_paintings_query::GetPaintingsQuery;
 use crate::requests::dto::paginated_result::PaginatedResult;
+use crate::utils::http_cache::{cache_request, CachePolicy, CacheRequest};
 
-async fn get_paintings(query: GetPaintingsQuery) -> Result<impl Reply, Rejection> {
-    let mut result: PaginatedResult<Painting> = PaginatedResult::new();
-    let client: Arc<Pool<Postgres>> = Arc::new(get_client().await.unwrap().clone());
+/// Cuts the extra row `get_keyset_query` selects off and makes cursors of the page edges.
+fn keyset_page(
+    mut rows: Vec<Painting>,
+    limit: usize,
+    query: &GetPaintingsQuery,
+    cursor: Option<&Cursor>,
+) -> PaginatedResult<Painting> {
+    let more = rows.len() > limit;
+    rows.truncate(limit);
+
+    let backward = cursor.is_some_and(|c| c.backward);
+    if backward {
+        rows.reverse();
+    }
+
+    let edge = |row: &Painting, backward: bool| {
+        Cursor {
+            sort: query.sort.clone(),
+            order: query.order.clone(),
+            value: row.sort_value.clone(),
+            id: row.id,
+            backward,
+        }
+        .encode()
+    };
+
+    // going back there is always a page after, going forward a page before unless this is the first one
+    let next_cursor = if more || backward {
+        rows.last().map(|row| edge(row, false))
+    } else {
+        None
+    };
+    let prev_cursor = if (more && backward) || (!backward && cursor.is_some()) {
+        rows.first().map(|row| edge(row, true))
+    } else {
+        None
+    };
+
+    PaginatedResult {
+        rows,
+        count: None,
+        next_cursor,
+        prev_cursor,
+    }
+}
+
+async fn get_paintings(mut query: GetPaintingsQuery) -> Result<warp::reply::Response, Rejection> {
+    let cursor = match query.validate() {
+        Ok(cursor) => cursor,
+        Err(key) => {
+            return Ok(ValidationError::new(Some(key))
+                .response()
+                .await
+                .into_response())
+        }
+    };
+    if let Some(cursor) = &cursor {
+        query.sort = cursor.sort.clone();
+        query.order = cursor.order.clone();
+    }
+
+    let client = get_client().await.unwrap();
     debug!(target: "api", "paintings:get_all - database client aquired");
 
-    let count_client = Arc::clone(&client);
-    let count_task = tokio::spawn(async move {
-        let (count,): (i64,) = sqlx::query_as(&Painting::count_all_query())
-            .fetch_one(&*count_client)
-            .await
-            .expect("Failed to count painting rows");
-        count
-    });
+    let language = query.lang.as_deref().and_then(Language::from_code);
+    let parsed_query = query.safe_parse(language);
+    let offset_mode = query.offset.is_some();
+    // count and facets describe the whole listing, later keyset pages do not repeat them
+    let first_page = cursor.is_none();
 
-    let rows_client = Arc::clone(&client);
-    let rows_task = tokio::spawn(async move {
-        let select_query = Painting::get_all_query(query, None);
-        debug!(target: "db", "paintings:get_all - Painting::get_all_query {}", &select_query);
-        let rows = sqlx::query_as::<_, Painting>(&select_query)
-            .fetch_all(&*rows_client)
-            .await
-            .expect("Failed to select painting rows");
-        rows
-    });
+    let select_query = if offset_mode {
+        Painting::get_all_query(query.clone(), language)
+    } else {
+        Painting::get_keyset_query(&parsed_query, cursor.as_ref())
+    };
+    debug!(target: "db", "paintings:get_all - select query {}", &select_query);
+    let count_query = Painting::count_all_query(&parsed_query);
+    let facets_query = Painting::facets_query(&parsed_query);
+
+    let (rows, count, facets) = tokio::join!(
+        sqlx::query_as::<_, Painting>(&select_query).fetch_all(client),
+        async {
+            if !first_page {
+                return Ok(None);
+            }
+            sqlx::query_as::<_, (i64,)>(&count_query)
+                .fetch_one(client)
+                .await
+                .map(|(count,)| Some(count))
+        },
+        async {
+            if !first_page {
+                return Ok(None);
+            }
+            debug!(target: "db", "paintings:get_all - Painting::facets_query {}", &facets_query);
+            sqlx::query_as::<_, Facets>(&facets_query)
+                .fetch_one(client)
+                .await
+                .map(Some)
+        }
+    );
+
+    let rows = match rows {
+        Ok(rows) => rows,
+        Err(error) => {
+            error!(target: "api", "paintings:get_all - failed to get rows {}", error);
+            return Ok(InternalServerError::new().response().await.into_response());
+        }
+    };
+    debug!(target: "api", "paintings:get_all - {:?}", &rows);
 
-    let (count, rows) = tokio::join!(count_task, rows_task);
+    let mut page = if offset_mode {
+        PaginatedResult::new(rows, 0)
+    } else {
+        keyset_page(rows, parsed_query.limit as usize, &query, cursor.as_ref())
+    };
 
-    result.count = count.unwrap_or_else(|error| {
+    page.count = count.unwrap_or_else(|error| {
         error!(target: "api", "paintings:get_all - failed to count {}", error);
-        0
+        Some(0)
     });
-    debug!(target: "api", "paintings:get_all - {}", &result.count);
+    debug!(target: "api", "paintings:get_all - {:?}", &page.count);
 
-    result.rows = rows.unwrap_or_else(|error| {
-        error!(target: "api", "paintings:get_all - failed to get rows {}", error);
-        Vec::new()
+    let facets = facets.unwrap_or_else(|error| {
+        error!(target: "api", "paintings:get_all - failed to get facets {}", error);
+        Some(Facets::default())
     });
-    debug!(target: "api", "paintings:get_all - {:?}", &result.rows);
 
-    Ok(warp::reply::json(&result))
+    Ok(warp::reply::json(&FacetedResult { page, facets }).into_response())
 }
 
 pub fn get() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
@@ -56,5 +149,10 @@ pub fn get() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
         .and(path("paintings"))
         .and(path::end())
         .and(query::<GetPaintingsQuery>())
-        .and_then(get_paintings)
+        .and(cache_request())
+        .and_then(|query, request: CacheRequest| async move {
+            request
+                .reply(CachePolicy::Revalidate, || get_paintings(query))
+                .await
+        })
 }
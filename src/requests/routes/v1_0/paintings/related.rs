@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use warp::{path, query, Filter, Rejection, Reply};
+
+use crate::client::translations::Language;
+use crate::config::load::{self, ConfigField};
+use crate::database::connection::get_client;
+use crate::database::models::painting::Painting;
+use crate::database::models::related_painting::{
+    RelatedPainting, RELATED_CANDIDATES, RELATED_LIMIT,
+};
+use crate::errors::api_error::{InternalServerError, NotFoundError, ValidationError};
+use crate::requests::dto::generic_response::{GenericResponse, Status};
+use crate::utils::http_cache::{cache_request, CachePolicy, CacheRequest};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RelatedQuery {
+    pub lang: Option<String>,
+}
+
+/// Paintings like the one with `painting_id`, `None` when it does not exist.
+pub async fn get_related(
+    painting_id: Uuid,
+    language: Language,
+    base_static_files_url: &str,
+) -> Result<Option<Vec<RelatedPainting>>, sqlx::Error> {
+    let client = get_client().await?;
+
+    let query = Painting::exists_query();
+    let exists = sqlx::query(&query)
+        .bind(painting_id)
+        .fetch_optional(client)
+        .await?
+        .is_some();
+    if !exists {
+        return Ok(None);
+    }
+
+    let query = Painting::get_related_query(language, base_static_files_url);
+    debug!(target: "db", "paintings:related - Painting::get_related_query {}", &query);
+    let candidates = sqlx::query_as::<_, RelatedPainting>(&query)
+        .bind(painting_id)
+        .bind(RELATED_CANDIDATES)
+        .fetch_all(client)
+        .await?;
+
+    Ok(Some(RelatedPainting::rank(candidates, RELATED_LIMIT)))
+}
+
+async fn get_related_paintings(
+    painting_id: Uuid,
+    query: RelatedQuery,
+) -> Result<warp::reply::Response, Rejection> {
+    let language = match query.lang.as_deref() {
+        Some(code) => match Language::from_code(code) {
+            Some(language) => language,
+            None => {
+                return Ok(ValidationError::new(Some("invalidLanguage"))
+                    .response()
+                    .await
+                    .into_response())
+            }
+        },
+        None => Language::Cs,
+    };
+
+    let static_base_url = match load::get::<String>(ConfigField::StaticFileUrl).await {
+        Ok(value) => value,
+        Err(error) => {
+            error!(target: "api", "paintings:related - failed to get static base url {}", error);
+            return Ok(InternalServerError::new().response().await.into_response());
+        }
+    };
+
+    match get_related(painting_id, language, &static_base_url).await {
+        Ok(Some(related)) => Ok(GenericResponse::send(
+            Status::Success,
+            "relatedPaintings",
+            Some(related),
+            warp::http::StatusCode::OK,
+        )
+        .into_response()),
+        Ok(None) => Ok(NotFoundError::new().response().await.into_response()),
+        Err(error) => {
+            error!(target: "api", "paintings:related - error {:?}", error);
+            Ok(InternalServerError::new().response().await.into_response())
+        }
+    }
+}
+
+pub fn get() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get()
+        .and(path("api"))
+        .and(path("v1.0"))
+        .and(path("paintings"))
+        .and(path::param::<Uuid>())
+        .and(path("related"))
+        .and(path::end())
+        .and(query::<RelatedQuery>())
+        .and(cache_request())
+        .and_then(|id, query, request: CacheRequest| async move {
+            request
+                .reply(CachePolicy::Revalidate, || get_related_paintings(id, query))
+                .await
+        })
+}
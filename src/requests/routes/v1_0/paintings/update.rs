@@ -1,29 +1,60 @@
+use sqlx::{Pool, Postgres};
 use uuid::Uuid;
 use warp::{body, path, Filter, Rejection, Reply};
 
 use crate::database::connection::get_client;
 use crate::database::models::painting::{Painting, PaintingBase, PaintingUpdate};
-use crate::errors::api_error::InternalServerError;
+use crate::errors::api_error::{InternalServerError, ValidationError};
 use crate::requests::dto::generic_response::{GenericResponse, Status};
 use crate::utils::auth::token::{jwt_auth, Claims};
+use crate::utils::http_cache::content_changed;
+use crate::utils::money::is_currency_code;
+use crate::utils::slug::assign_slugs;
+
+async fn save_painting(
+    client: &Pool<Postgres>,
+    query: &str,
+    title_changed: bool,
+) -> Result<PaintingBase, sqlx::Error> {
+    let mut transaction = client.begin().await?;
+    let painting = sqlx::query_as::<_, PaintingBase>(query)
+        .fetch_one(&mut *transaction)
+        .await?;
+    if let Some(title) = painting.painting_title.as_ref().filter(|_| title_changed) {
+        assign_slugs(&mut transaction, painting.id, title).await?;
+    }
+    transaction.commit().await?;
+    Ok(painting)
+}
 
 async fn update_painting(
     painting_uid: Uuid,
-    data: PaintingUpdate,
+    mut data: PaintingUpdate,
 ) -> Result<impl Reply, Rejection> {
+    data.currency = data.currency.map(|code| code.trim().to_uppercase());
+    if !data.currency.as_deref().is_none_or(is_currency_code) {
+        return Ok(ValidationError::new(Some("invalidCurrency"))
+            .response()
+            .await);
+    }
+
+    if let Err(key) = data.attributes.validate() {
+        return Ok(ValidationError::new(Some(key)).response().await);
+    }
+
+    let title_changed = data.title_cs.is_some() || data.title_en.is_some();
     let client = get_client().await.unwrap().clone();
     debug!(target: "api", "paintings:update - database client acquired");
     debug!(target: "api", "paintings:update - data {:?}", &data);
 
     let query = Painting::update_query(painting_uid, data);
     debug!(target: "db", "paintings:update - Painting::update_query {}", &query);
-    let update_result = sqlx::query_as::<_, PaintingBase>(&query)
-        .fetch_one(&client)
-        .await;
+    let update_result = save_painting(&client, &query, title_changed).await;
 
     match update_result {
         Ok(painting) => {
             debug!(target: "api", "paintings:update - result {:?}", &painting);
+            content_changed();
             let response = GenericResponse::<PaintingBase> {
                 status: Status::Success,
                 message: "Painting updated successfully",
@@ -0,0 +1,93 @@
+use uuid::Uuid;
+use warp::{body, path, Filter, Rejection, Reply};
+
+use crate::database::connection::get_client;
+use crate::database::models::painting::Painting;
+use crate::database::models::tag::{PaintingTagsUpdate, Tag};
+use crate::errors::api_error::{InternalServerError, NotFoundError};
+use crate::requests::dto::generic_response::{GenericResponse, Status};
+use crate::utils::auth::token::{jwt_auth, Claims};
+use crate::utils::http_cache::content_changed;
+
+async fn replace_tags(
+    painting_id: Uuid,
+    data: PaintingTagsUpdate,
+) -> Result<Option<Vec<Tag>>, sqlx::Error> {
+    let client = get_client().await?;
+    let mut transaction = client.begin().await?;
+
+    let query = Painting::touch_query();
+    let exists = sqlx::query(&query)
+        .bind(painting_id)
+        .fetch_optional(&mut *transaction)
+        .await?
+        .is_some();
+    if !exists {
+        return Ok(None);
+    }
+
+    let query = Tag::clear_painting_query();
+    debug!(target: "db", "paintings:update_tags - Tag::clear_painting_query {}", &query);
+    sqlx::query(&query)
+        .bind(painting_id)
+        .execute(&mut *transaction)
+        .await?;
+
+    let query = Tag::add_to_painting_query();
+    debug!(target: "db", "paintings:update_tags - Tag::add_to_painting_query {}", &query);
+    sqlx::query(&query)
+        .bind(painting_id)
+        .bind(&data.tag_ids)
+        .execute(&mut *transaction)
+        .await?;
+
+    let query = Tag::get_for_painting_query();
+    let tags = sqlx::query_as::<_, Tag>(&query)
+        .bind(painting_id)
+        .fetch_all(&mut *transaction)
+        .await?;
+
+    transaction.commit().await?;
+    Ok(Some(tags))
+}
+
+async fn update_painting_tags(
+    painting_id: Uuid,
+    data: PaintingTagsUpdate,
+) -> Result<warp::reply::Response, Rejection> {
+    match replace_tags(painting_id, data).await {
+        Ok(Some(tags)) => {
+            content_changed();
+            Ok(GenericResponse::send(
+                Status::Success,
+                "paintingTagsUpdated",
+                Some(tags),
+                warp::http::StatusCode::OK,
+            )
+            .into_response())
+        }
+        Ok(None) => Ok(NotFoundError::new().response().await.into_response()),
+        Err(error) => {
+            error!(target: "api", "paintings:update_tags - error {:?}", error);
+            Ok(InternalServerError::new().response().await.into_response())
+        }
+    }
+}
+
+pub fn update() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::put()
+        .and(path("api"))
+        .and(path("v1.0"))
+        .and(path("paintings"))
+        .and(path::param::<Uuid>())
+        .and(path("tags"))
+        .and(path::end())
+        .and(body::content_length_limit(16 * 1024))
+        .and(body::json::<PaintingTagsUpdate>())
+        .and(jwt_auth())
+        .and_then(
+            |painting_id: Uuid, data: PaintingTagsUpdate, _claims: Claims| async move {
+                update_painting_tags(painting_id, data).await
+            },
+        )
+}
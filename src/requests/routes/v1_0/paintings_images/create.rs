@@ -1,30 +1,38 @@
-use bytes::Buf;
 use futures_util::TryStreamExt;
 use serde::{Deserialize, Serialize};
-use std::path::Path;
-use std::sync::Arc;
-use tokio::sync::Mutex;
+use sqlx::{Pool, Postgres};
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
-use warp::filters::multipart::FormData;
+use warp::filters::multipart::{FormData, Part};
 use warp::{path, query, Filter, Rejection, Reply};
 
 use crate::config::load::{get, ConfigField};
 use crate::database::connection::get_client;
-use crate::database::models::image::{PaintingImage, PaintingImageCreate};
-use crate::errors::api_error::{ImageResizeError, InternalServerError};
+use crate::database::models::image::{ImageMetaPart, PaintingImage, PaintingImageCreate};
+use crate::database::models::painting::Painting;
+use crate::errors::api_error::InternalServerError;
 use crate::requests::dto::generic_response::{GenericResponse, Status};
 use crate::utils::auth::token::{jwt_auth, Claims};
 use crate::utils::file_system::fs_delete::remove_file;
-use crate::utils::file_system::fs_write::append_bytes;
+use crate::utils::file_system::fs_write::write_stream;
+use crate::utils::http_cache::content_changed;
+use crate::utils::images::palette::update_palette;
 use crate::utils::images::resize_to_max::{resize_to_max, ResizeImageJob};
 
-#[derive(Serialize)]
-struct FormDataRecord {
-    pub name: String,
-    pub file_name: String,
-    pub size: usize,
-}
+/// Maximum number of image files accepted in one request
+const MAX_FILES: usize = 8;
+/// Maximum size of a single uploaded image file
+pub const MAX_FILE_SIZE: u64 = 52_428_800;
+/// Maximum size of the whole multipart request
+const MAX_REQUEST_SIZE: u64 = MAX_FILE_SIZE * 4;
+/// Maximum size of a per-file metadata part
+const MAX_META_SIZE: u64 = 16_384;
+/// Widths of the resized variants stored for every image
+pub const IMAGE_SIZES: [u32; 4] = [320, 640, 1024, 1090];
 
+/// Metadata defaults shared by every file in the request.
 #[derive(Debug, Serialize, Deserialize)]
 struct ImageMetaQuery {
     pub preview: Option<bool>,
@@ -35,224 +43,350 @@ struct ImageMetaQuery {
     pub painting_id: Uuid,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct ImageProcessData {
+#[derive(Debug)]
+struct UploadedFile {
+    key: String,
+    file_name: String,
+    file_system_path: PathBuf,
+    size: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImageUploadResult {
+    pub key: String,
     pub file_name: String,
-    pub file_path: String,
-    pub file_system_path: String,
-    pub size: usize,
-    pub preview: bool,
-    pub title_cs: String,
-    pub title_en: String,
-    pub alt_cs: String,
-    pub alt_en: String,
-    pub painting_id: Uuid,
+    pub size: u64,
+    pub status: Status,
+    pub message: String,
+    pub image: Option<PaintingImage>,
 }
 
-async fn create_painting_image(
-    data: FormData,
-    params: ImageMetaQuery,
-) -> Result<impl Reply, Rejection> {
-    let errors: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
-    let errors_clone = Arc::new(&errors);
-    let client = get_client().await.unwrap();
+impl ImageUploadResult {
+    fn failed(key: &str, file_name: &str, size: u64, message: &str) -> Self {
+        Self {
+            key: key.to_string(),
+            file_name: file_name.to_string(),
+            size,
+            status: Status::Error,
+            message: message.to_string(),
+            image: None,
+        }
+    }
+}
 
-    let error_response = GenericResponse::<Vec<FormDataRecord>> {
-        status: Status::Error,
-        message: "internalServerError",
-        data: None,
-    };
+#[derive(Debug, Default)]
+struct ParsedForm {
+    files: Vec<UploadedFile>,
+    metas: HashMap<String, Result<ImageMetaPart, String>>,
+    results: Vec<ImageUploadResult>,
+}
+
+/// Strips the `image`/`meta` prefix from a part name, `image_front` -> `front`.
+pub fn part_key<'a>(name: &'a str, prefix: &str) -> &'a str {
+    name.strip_prefix(prefix)
+        .map(|rest| rest.trim_start_matches(['_', '-', '.']))
+        .unwrap_or(name)
+}
 
-    let processed_data: Arc<Mutex<ImageProcessData>> = Arc::new(Mutex::new(ImageProcessData {
-        file_name: String::from(""),
-        file_path: String::from(""),
-        file_system_path: String::from(""),
-        size: 0,
-        preview: params.preview.unwrap_or(false),
-        title_cs: params.title_cs.unwrap_or(String::from("")),
-        title_en: params.title_en.unwrap_or(String::from("")),
-        alt_cs: params.alt_cs.unwrap_or(String::from("")),
-        alt_en: params.alt_en.unwrap_or(String::from("")),
-        painting_id: params.painting_id,
-    }));
-    let processed_data_clone = Arc::clone(&processed_data);
-
-    let processed = data.try_for_each(|data_field: warp::filters::multipart::Part| {
-		let mut field = data_field;
-		let errors_move_clone = Arc::clone(&errors_clone);
-		let process_data_move_clone = Arc::clone(&processed_data_clone);
-
-		async move {
-			let errors_move_nested_clone = Arc::clone(&errors_move_clone);
-			let mut errors_move_nested_lock = errors_move_nested_clone.lock().await;
-
-			let process_data_move_nested_clone = Arc::clone(&process_data_move_clone);
-			let mut process_data_move_nested_lock = process_data_move_nested_clone.lock().await;
-
-			let file_name: &str = field.filename().unwrap_or("unknown");
-			debug!(target: "api", "images:create - file_name {}", &file_name);
-			let file_path = match get::<String>(ConfigField::StaticFilesDir).await {
-				Ok(value) => {
-					debug!(target: "api", "images:create - config file path {}", &value);
-					Path::new(&value).join(format!("images/{}", &file_name))
-				},
-				Err(error) => {
-					error!(target: "api", "images:create - path creating file path failed {:?}", error);
-					errors_move_nested_lock.push(format!("Creating file path failed {:?}", error));
-					return Ok(())
-				}
-			};
-
-			process_data_move_nested_lock.file_name = file_name.to_string();
-			process_data_move_nested_lock.file_path = String::from(
-				format!("https://static.rosemary-artist.com/static/images/{}", &file_name)
-			);
-
-			while let Some(content) = field.data().await {
-				match content {
-					Ok(value) => {
-						process_data_move_nested_lock.size += value.chunk().len();
-						debug!(target: "api", "images:create path {:?} data chunk processing", &file_path.to_str());
-						let _ = append_bytes(
-							value.chunk(),
-							file_path.to_str().expect("NO filepath specified"),
-							true
-						).await;
-					},
-					Err(error) => {
-						error!(target: "api", "images:create - processing failed to process file chunk {:?}", error);
-						errors_move_nested_lock.push(format!("Failed to process file chunk {:?}", error));
-						return Ok(())
-					}
-				}
-			}
-
-			Ok(())
-		}
-	}).await;
-
-    let errors_main_lock = errors.lock().await;
-    if !errors_main_lock.is_empty() {
-        return Ok(warp::reply::with_status(
-            warp::reply::json(&error_response),
-            warp::http::StatusCode::INTERNAL_SERVER_ERROR,
-        ));
+/// File extension for supported image content types, `image::open` relies on it
+/// to pick the decoder.
+pub fn image_extension(content_type: Option<&str>) -> Option<&'static str> {
+    match content_type {
+        Some("image/jpeg") => Some("jpeg"),
+        Some("image/png") => Some("png"),
+        Some("image/webp") => Some("webp"),
+        Some("image/tiff") => Some("tiff"),
+        _ => None,
     }
+}
+
+async fn read_meta_part(part: Part) -> Result<ImageMetaPart, String> {
+    let mut content: Vec<u8> = Vec::new();
+    let mut stream = Box::pin(part.stream());
 
-    match processed {
-        Ok(_) => {
-            debug!(target: "api", "images:create - processing success")
+    while let Some(chunk) = stream
+        .try_next()
+        .await
+        .map_err(|error| format!("metaReadFailed - {}", error))?
+    {
+        let bytes = bytes::Buf::chunk(&chunk);
+        if (content.len() + bytes.len()) as u64 > MAX_META_SIZE {
+            return Err(String::from("metaTooLarge"));
         }
-        Err(error) => {
-            error!(target: "api", "images:create - processing error {:?}", error);
-            return Ok(warp::reply::with_status(
-                warp::reply::json(&error_response),
-                warp::http::StatusCode::INTERNAL_SERVER_ERROR,
-            ));
+        content.extend_from_slice(bytes);
+    }
+
+    serde_json::from_slice::<ImageMetaPart>(&content)
+        .map_err(|error| format!("metaInvalid - {}", error))
+}
+
+async fn read_form(mut data: FormData, upload_dir: &Path) -> Result<ParsedForm, warp::Error> {
+    let mut form = ParsedForm::default();
+
+    while let Some(part) = data.try_next().await? {
+        let name = part.name().to_string();
+
+        if name.starts_with("meta") {
+            let key = part_key(&name, "meta").to_string();
+            debug!(target: "api", "images:create - reading metadata part {}", &key);
+            let meta = read_meta_part(part).await;
+            form.metas.insert(key, meta);
+            continue;
         }
-    };
 
-    let image_data = processed_data.lock().await;
+        let Some(file_name) = part.filename().map(|value| value.to_string()) else {
+            debug!(target: "api", "images:create - skipping unknown part {}", &name);
+            continue;
+        };
+        let key = part_key(&name, "image").to_string();
 
-    let static_file_dir = match get::<String>(ConfigField::StaticFilesDir).await {
-        Ok(path) => path,
-        Err(error) => {
-            error!(target: "api", "images:create - error getting static dir path {}", error);
-            return Ok(InternalServerError::new().response().await);
+        if form.files.len() >= MAX_FILES {
+            form.results.push(ImageUploadResult::failed(
+                &key,
+                &file_name,
+                0,
+                "tooManyFiles",
+            ));
+            continue;
         }
-    };
 
-    let data = PaintingImageCreate {
-        preview: image_data.preview,
-        alt_cs: image_data.alt_cs.clone(),
-        alt_en: image_data.alt_en.clone(),
-        title_cs: image_data.title_cs.clone(),
-        title_en: image_data.title_en.clone(),
-        painting_id: image_data.painting_id,
-    };
+        let Some(extension) = image_extension(part.content_type()) else {
+            form.results.push(ImageUploadResult::failed(
+                &key,
+                &file_name,
+                0,
+                "unsupportedFileType",
+            ));
+            continue;
+        };
 
-    let query = PaintingImage::create_query(data);
-    debug!(target: "db", "images:create - PaintingImage::create_query - {}", &query);
-    let create_result = sqlx::query_as::<_, PaintingImage>(&query)
-        .fetch_one(&*client)
+        let file_system_path = upload_dir.join(format!("upload_{}.{}", Uuid::new_v4(), extension));
+        debug!(target: "api", "images:create - streaming {} into {:?}", &file_name, &file_system_path);
+        let written = write_stream(
+            Box::pin(part.stream()),
+            &file_system_path.to_string_lossy(),
+            MAX_FILE_SIZE,
+        )
         .await;
 
-    let mut painting = match create_result {
-        Ok(painting_image) => {
-            debug!(target: "api", "images:create - result {:?}", &painting_image);
-            painting_image
+        match written {
+            Ok(size) => form.files.push(UploadedFile {
+                key,
+                file_name,
+                file_system_path,
+                size,
+            }),
+            Err(error) => {
+                error!(target: "api", "images:create - failed to store {} {}", &file_name, error);
+                let message = if error.kind() == io::ErrorKind::InvalidData {
+                    "fileTooLarge"
+                } else {
+                    "fileWriteFailed"
+                };
+                form.results
+                    .push(ImageUploadResult::failed(&key, &file_name, 0, message));
+            }
         }
+    }
+
+    Ok(form)
+}
+
+pub async fn process_image(
+    client: &Pool<Postgres>,
+    data: PaintingImageCreate,
+    file_system_path: &Path,
+    static_file_dir: &str,
+) -> Result<PaintingImage, &'static str> {
+    let query = PaintingImage::create_query(data);
+    debug!(target: "db", "images:create - PaintingImage::create_query - {}", &query);
+    let painting_image = match sqlx::query_as::<_, PaintingImage>(&query)
+        .fetch_one(client)
+        .await
+    {
+        Ok(value) => value,
         Err(error) => {
             error!(target: "api", "images:create - error {:?}", error);
-            return Ok(InternalServerError::new().response().await);
+            return Err("internalServerError");
         }
     };
 
-    let file_system_path =
-        Path::new(&static_file_dir).join(format!("images/{}", &image_data.file_name));
-
-    let sizes = vec![320, 640, 1024, 1090];
     let image_job = ResizeImageJob {
-        max_sizes: sizes.clone(),
+        max_sizes: IMAGE_SIZES.to_vec(),
         path: file_system_path.to_string_lossy().to_string(),
-        new_name: Some(String::from(format!("{}", &painting.id))),
+        new_name: Some(painting_image.id.to_string()),
     };
 
+    // resize_to_max processes the widths from the largest to the smallest
+    let mut sizes = IMAGE_SIZES.to_vec();
+    sizes.sort_by(|a, b| b.cmp(a));
     let resize_result = resize_to_max(image_job).await;
-    let mut index = 0;
-    let mut failed_resize = false;
-    let mut resized_paths: Vec<String> = Vec::new();
 
-    for result in resize_result.into_iter() {
-        if !result {
-            error!(target: "api", "images:create - resize failed {:?}", sizes[index]);
-            failed_resize = true;
-        } else {
-            resized_paths.push(String::from(format!(
-                "images/{}_{}.jpeg",
-                &painting.id, sizes[index]
-            )));
+    let mut resized_paths: Vec<String> = Vec::new();
+    for (size, resized) in sizes.iter().zip(resize_result.iter()) {
+        if !resized {
+            error!(target: "api", "images:create - resize failed {:?}", size);
+            return Err("imageResizeFailed");
         }
-        index += 1;
+    }
+    for size in IMAGE_SIZES.iter() {
+        resized_paths.push(format!("images/{}_{}.jpeg", &painting_image.id, size));
     }
 
-    if failed_resize {
-        return Ok(ImageResizeError::new().response().await);
+    if let Err(error) = update_palette(painting_image.id, static_file_dir, &resized_paths[0]).await
+    {
+        error!(target: "api", "images:create - storing palette failed {}", error);
     }
 
-    if resized_paths.len() == 4 {
-        let update_query = PaintingImage::update_resized_query(
-            painting.id,
-            static_file_dir.clone(),
-            resized_paths,
-        );
-        debug!(target: "api", "images:create - update resized query {}", &update_query);
-        let update_result = sqlx::query_as::<_, PaintingImage>(&update_query)
-            .fetch_one(&*client)
-            .await;
-        match update_result {
-            Ok(value) => {
-                debug!(target: "api", "images:create - updated resized image painting record");
-                painting = value;
+    let update_query = PaintingImage::update_resized_query(
+        painting_image.id,
+        static_file_dir.to_string(),
+        resized_paths,
+    );
+    debug!(target: "api", "images:create - update resized query {}", &update_query);
+    match sqlx::query_as::<_, PaintingImage>(&update_query)
+        .fetch_one(client)
+        .await
+    {
+        Ok(value) => {
+            debug!(target: "api", "images:create - updated resized image painting record");
+            if let Err(error) = sqlx::query(&Painting::touch_query())
+                .bind(value.painting_id)
+                .execute(client)
+                .await
+            {
+                error!(target: "api", "images:create - Painting::touch_query failed {}", error);
             }
-            Err(error) => {
-                error!(target: "api", "images:create - updating resized image record failed {}", error);
-                return Ok(InternalServerError::new().response().await);
+            Ok(value)
+        }
+        Err(error) => {
+            error!(target: "api", "images:create - updating resized image record failed {}", error);
+            Err("internalServerError")
+        }
+    }
+}
+
+async fn create_painting_images(
+    data: FormData,
+    params: ImageMetaQuery,
+) -> Result<impl Reply, Rejection> {
+    let client = get_client().await.unwrap();
+
+    let static_file_dir = match get::<String>(ConfigField::StaticFilesDir).await {
+        Ok(path) => path,
+        Err(error) => {
+            error!(target: "api", "images:create - error getting static dir path {}", error);
+            return Ok(InternalServerError::new().response().await);
+        }
+    };
+    let upload_dir = Path::new(&static_file_dir).join("images");
+
+    let mut form = match read_form(data, &upload_dir).await {
+        Ok(value) => value,
+        Err(error) => {
+            error!(target: "api", "images:create - processing error {:?}", error);
+            return Ok(GenericResponse::<Vec<ImageUploadResult>>::send(
+                Status::Error,
+                "invalidMultipartBody",
+                None,
+                warp::http::StatusCode::BAD_REQUEST,
+            ));
+        }
+    };
+
+    for upload in form.files.iter() {
+        let meta = match form.metas.remove(&upload.key) {
+            Some(Ok(value)) => value,
+            Some(Err(message)) => {
+                form.results.push(ImageUploadResult::failed(
+                    &upload.key,
+                    &upload.file_name,
+                    upload.size,
+                    &message,
+                ));
+                remove_file(&upload.file_system_path.to_string_lossy()).await;
+                continue;
             }
+            None => ImageMetaPart::default(),
+        };
+
+        let image_data = PaintingImageCreate {
+            preview: meta.preview.or(params.preview).unwrap_or(false),
+            alt_cs: meta.alt_cs.or(params.alt_cs.clone()).unwrap_or_default(),
+            alt_en: meta.alt_en.or(params.alt_en.clone()).unwrap_or_default(),
+            title_cs: meta
+                .title_cs
+                .or(params.title_cs.clone())
+                .unwrap_or_default(),
+            title_en: meta
+                .title_en
+                .or(params.title_en.clone())
+                .unwrap_or_default(),
+            painting_id: params.painting_id,
+        };
+
+        let processed = process_image(
+            client,
+            image_data,
+            &upload.file_system_path,
+            &static_file_dir,
+        )
+        .await;
+
+        let removed_original = remove_file(&upload.file_system_path.to_string_lossy()).await;
+        if !removed_original {
+            error!(target: "api", "images:create - failed to remove original uploaded file");
         }
+
+        form.results.push(match processed {
+            Ok(image) => ImageUploadResult {
+                key: upload.key.clone(),
+                file_name: upload.file_name.clone(),
+                size: upload.size,
+                status: Status::Success,
+                message: String::from("paintingImageCreated"),
+                image: Some(image),
+            },
+            Err(message) => {
+                ImageUploadResult::failed(&upload.key, &upload.file_name, upload.size, message)
+            }
+        });
     }
 
-    let removed_original =
-        remove_file(&file_system_path.to_string_lossy().to_string().as_ref()).await;
-    if !removed_original {
-        error!(target: "api", "images:create - failed to remove original uploaded file");
+    let succeeded = form
+        .results
+        .iter()
+        .filter(|result| matches!(result.status, Status::Success))
+        .count();
+    if succeeded > 0 {
+        content_changed();
     }
 
+    let (status, message, status_code) = if succeeded > 0 && succeeded == form.results.len() {
+        (
+            Status::Success,
+            "paintingImagesCreated",
+            warp::http::StatusCode::CREATED,
+        )
+    } else if succeeded > 0 {
+        (
+            Status::Success,
+            "paintingImagesPartiallyCreated",
+            warp::http::StatusCode::MULTI_STATUS,
+        )
+    } else {
+        (
+            Status::Error,
+            "paintingImagesNotCreated",
+            warp::http::StatusCode::BAD_REQUEST,
+        )
+    };
+
     Ok(GenericResponse::send(
-        Status::Success,
-        "paintingImageCreated",
-        Some(painting),
-        warp::http::StatusCode::CREATED,
+        status,
+        message,
+        Some(form.results),
+        status_code,
     ))
 }
 
@@ -263,12 +397,34 @@ pub fn create() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone
         .and(path("images"))
         .and(path::end())
         .and(query::<ImageMetaQuery>())
-        .and(warp::body::content_length_limit(52_428_800))
-        .and(warp::multipart::form().max_length(52_428_800))
+        .and(warp::body::content_length_limit(MAX_REQUEST_SIZE))
+        .and(warp::multipart::form().max_length(MAX_REQUEST_SIZE))
         .and(jwt_auth())
         .and_then(
             |params: ImageMetaQuery, data: FormData, _claims: Claims| async move {
-                create_painting_image(data, params).await
+                create_painting_images(data, params).await
             },
         )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_key() {
+        assert_eq!(part_key("image_front", "image"), "front");
+        assert_eq!(part_key("meta_front", "meta"), "front");
+        assert_eq!(part_key("image", "image"), "");
+        assert_eq!(part_key("meta", "meta"), "");
+        assert_eq!(part_key("photo", "image"), "photo");
+    }
+
+    #[test]
+    fn test_image_extension() {
+        assert_eq!(image_extension(Some("image/jpeg")), Some("jpeg"));
+        assert_eq!(image_extension(Some("image/png")), Some("png"));
+        assert_eq!(image_extension(Some("application/pdf")), None);
+        assert_eq!(image_extension(None), None);
+    }
+}
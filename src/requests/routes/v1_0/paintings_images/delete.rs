@@ -9,6 +9,7 @@ use crate::requests::dto::generic_response::{GenericResponse, Status};
 use crate::utils::auth::token::{jwt_auth, Claims};
 use crate::utils::file_system::fs_delete::remove_file;
 use crate::utils::file_system::fs_read::file_exists;
+use crate::utils::http_cache::content_changed;
 
 async fn delete_painting_image(id: Uuid) -> Result<impl Reply, Rejection> {
     let client = get_client().await.unwrap();
@@ -60,6 +61,7 @@ async fn delete_painting_image(id: Uuid) -> Result<impl Reply, Rejection> {
     match removed_row {
         Ok(_) => {
             debug!(target: "api", "images:delete result OK");
+            content_changed();
         }
         Err(error) => {
             error!(target: "api", "images:delete error {:?}", error);
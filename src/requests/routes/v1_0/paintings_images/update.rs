@@ -6,6 +6,7 @@ use crate::database::models::image::{PaintingImage, PaintingImageUpdate};
 use crate::errors::api_error::InternalServerError;
 use crate::requests::dto::generic_response::{GenericResponse, Status};
 use crate::utils::auth::token::{jwt_auth, Claims};
+use crate::utils::http_cache::content_changed;
 
 async fn update_image(image_uid: Uuid, data: PaintingImageUpdate) -> Result<impl Reply, Rejection> {
     let client = get_client().await.unwrap().clone();
@@ -19,12 +20,15 @@ async fn update_image(image_uid: Uuid, data: PaintingImageUpdate) -> Result<impl
         .await;
 
     match update_result {
-        Ok(image) => Ok(GenericResponse::send(
-            Status::Success,
-            "Painting image updated",
-            Some(image),
-            warp::http::StatusCode::OK,
-        )),
+        Ok(image) => {
+            content_changed();
+            Ok(GenericResponse::send(
+                Status::Success,
+                "Painting image updated",
+                Some(image),
+                warp::http::StatusCode::OK,
+            ))
+        }
         Err(error) => {
             error!(target: "api", "painting_images:update - error {:?}", error);
             Ok(InternalServerError::new().response().await)
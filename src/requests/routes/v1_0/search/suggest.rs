@@ -0,0 +1,75 @@
+use lazy_static::lazy_static;
+use std::time::{Duration, Instant};
+use warp::{path, query, Filter, Rejection, Reply};
+
+use crate::database::connection::get_client;
+use crate::database::models::suggestion::{Suggestion, SUGGESTION_LIMIT};
+use crate::errors::api_error::{InternalServerError, ValidationError};
+use crate::requests::dto::generic_response::{GenericResponse, Status};
+use crate::requests::dto::suggest_query::SuggestQuery;
+use crate::utils::cache::TtlCache;
+
+lazy_static! {
+    /// Suggestions by language and query, edits show up once the entry expires
+    static ref SUGGESTIONS: TtlCache<(String, String), Vec<Suggestion>> =
+        TtlCache::new(1_000, Duration::from_secs(5 * 60));
+}
+
+async fn suggest(query: SuggestQuery) -> Result<warp::reply::Response, Rejection> {
+    let (q, language) = match query.parse() {
+        Ok(parsed) => parsed,
+        Err(key) => {
+            return Ok(ValidationError::new(Some(key))
+                .response()
+                .await
+                .into_response())
+        }
+    };
+
+    let key = (language.to_string(), q);
+    if let Some(suggestions) = SUGGESTIONS.get(&key, Instant::now()) {
+        return Ok(GenericResponse::send(
+            Status::Success,
+            "suggestions",
+            Some(suggestions),
+            warp::http::StatusCode::OK,
+        )
+        .into_response());
+    }
+
+    let client = get_client().await.unwrap();
+    let select_query = Suggestion::suggest_query(language);
+    debug!(target: "db", "search:suggest - Suggestion::suggest_query {}", &select_query);
+    match sqlx::query_as::<_, Suggestion>(&select_query)
+        .bind(&key.1)
+        .bind(SUGGESTION_LIMIT)
+        .fetch_all(client)
+        .await
+    {
+        Ok(suggestions) => {
+            SUGGESTIONS.insert(key, suggestions.clone(), Instant::now());
+            Ok(GenericResponse::send(
+                Status::Success,
+                "suggestions",
+                Some(suggestions),
+                warp::http::StatusCode::OK,
+            )
+            .into_response())
+        }
+        Err(error) => {
+            error!(target: "api", "search:suggest - error {:?}", error);
+            Ok(InternalServerError::new().response().await.into_response())
+        }
+    }
+}
+
+pub fn get() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get()
+        .and(path("api"))
+        .and(path("v1.0"))
+        .and(path("search"))
+        .and(path("suggest"))
+        .and(path::end())
+        .and(query::<SuggestQuery>())
+        .and_then(suggest)
+}
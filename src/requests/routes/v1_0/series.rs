@@ -0,0 +1,6 @@
+pub mod create;
+pub mod delete;
+pub mod get;
+pub mod get_all;
+pub mod update;
+pub mod update_paintings;
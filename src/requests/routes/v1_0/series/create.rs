@@ -0,0 +1,76 @@
+use sqlx::types::Json;
+use warp::{body, path, Filter, Rejection, Reply};
+
+use crate::database::connection::get_client;
+use crate::database::models::generics::translation_patch;
+use crate::database::models::series::{Series, SeriesCreate};
+use crate::errors::api_error::{ConflictError, InternalServerError, ValidationError};
+use crate::requests::dto::generic_response::{GenericResponse, Status};
+use crate::utils::auth::token::{jwt_auth, Claims};
+use crate::utils::http_cache::content_changed;
+
+async fn create_series(data: SeriesCreate) -> Result<warp::reply::Response, Rejection> {
+    if let Err(key) = data.validate() {
+        return Ok(ValidationError::new(Some(key))
+            .response()
+            .await
+            .into_response());
+    }
+
+    let client = get_client().await.unwrap();
+    let query = Series::create_query();
+    debug!(target: "db", "series:create - Series::create_query {}", &query);
+    let result = sqlx::query_as::<_, Series>(&query)
+        .bind(&data.slug)
+        .bind(Json(translation_patch(
+            Some(&data.title_cs),
+            Some(&data.title_en),
+        )))
+        .bind(Json(translation_patch(
+            Some(data.description_cs.as_deref().unwrap_or("")),
+            Some(data.description_en.as_deref().unwrap_or("")),
+        )))
+        .bind(data.cover_image_id)
+        .bind(data.position)
+        .fetch_one(client)
+        .await;
+
+    match result {
+        Ok(series) => {
+            content_changed();
+            Ok(GenericResponse::send(
+                Status::Success,
+                "seriesCreated",
+                Some(series),
+                warp::http::StatusCode::CREATED,
+            )
+            .into_response())
+        }
+        Err(error)
+            if error
+                .as_database_error()
+                .is_some_and(|error| error.is_unique_violation()) =>
+        {
+            Ok(ConflictError::new(Some("slugTaken"))
+                .response()
+                .await
+                .into_response())
+        }
+        Err(error) => {
+            error!(target: "api", "series:create - error {:?}", error);
+            Ok(InternalServerError::new().response().await.into_response())
+        }
+    }
+}
+
+pub fn create() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::post()
+        .and(path("api"))
+        .and(path("v1.0"))
+        .and(path("series"))
+        .and(path::end())
+        .and(body::content_length_limit(64 * 1024))
+        .and(body::json::<SeriesCreate>())
+        .and(jwt_auth())
+        .and_then(|data: SeriesCreate, _claims: Claims| async move { create_series(data).await })
+}
@@ -0,0 +1,47 @@
+use warp::{path, Filter, Rejection, Reply};
+
+use crate::database::connection::get_client;
+use crate::database::models::series::Series;
+use crate::errors::api_error::{InternalServerError, NotFoundError};
+use crate::requests::dto::generic_response::{GenericResponse, Status};
+use crate::utils::http_cache::{cache_request, CachePolicy, CacheRequest};
+
+async fn get_series(slug: String) -> Result<warp::reply::Response, Rejection> {
+    let client = get_client().await.unwrap();
+
+    let query = Series::get_by_slug_query();
+    debug!(target: "db", "series:get - Series::get_by_slug_query {}", &query);
+    match sqlx::query_as::<_, Series>(&query)
+        .bind(&slug)
+        .fetch_optional(client)
+        .await
+    {
+        Ok(Some(series)) => Ok(GenericResponse::send(
+            Status::Success,
+            "series",
+            Some(series),
+            warp::http::StatusCode::OK,
+        )
+        .into_response()),
+        Ok(None) => Ok(NotFoundError::new().response().await.into_response()),
+        Err(error) => {
+            error!(target: "api", "series:get - error {:?}", error);
+            Ok(InternalServerError::new().response().await.into_response())
+        }
+    }
+}
+
+pub fn get() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get()
+        .and(path("api"))
+        .and(path("v1.0"))
+        .and(path("series"))
+        .and(path::param::<String>())
+        .and(path::end())
+        .and(cache_request())
+        .and_then(|slug, request: CacheRequest| async move {
+            request
+                .reply(CachePolicy::Revalidate, || get_series(slug))
+                .await
+        })
+}
@@ -0,0 +1,84 @@
+use sqlx::types::Json;
+use uuid::Uuid;
+use warp::{body, path, Filter, Rejection, Reply};
+
+use crate::database::connection::get_client;
+use crate::database::models::generics::translation_patch;
+use crate::database::models::series::{Series, SeriesUpdate};
+use crate::errors::api_error::{
+    ConflictError, InternalServerError, NotFoundError, ValidationError,
+};
+use crate::requests::dto::generic_response::{GenericResponse, Status};
+use crate::utils::auth::token::{jwt_auth, Claims};
+use crate::utils::http_cache::content_changed;
+
+async fn update_series(id: Uuid, data: SeriesUpdate) -> Result<warp::reply::Response, Rejection> {
+    if let Err(key) = data.validate() {
+        return Ok(ValidationError::new(Some(key))
+            .response()
+            .await
+            .into_response());
+    }
+
+    let client = get_client().await.unwrap();
+    let query = Series::update_query();
+    debug!(target: "db", "series:update - Series::update_query {}", &query);
+    let result = sqlx::query_as::<_, Series>(&query)
+        .bind(id)
+        .bind(&data.slug)
+        .bind(Json(translation_patch(
+            data.title_cs.as_deref(),
+            data.title_en.as_deref(),
+        )))
+        .bind(Json(translation_patch(
+            data.description_cs.as_deref(),
+            data.description_en.as_deref(),
+        )))
+        .bind(data.cover_image_id)
+        .bind(data.position)
+        .fetch_optional(client)
+        .await;
+
+    match result {
+        Ok(Some(series)) => {
+            content_changed();
+            Ok(GenericResponse::send(
+                Status::Success,
+                "seriesUpdated",
+                Some(series),
+                warp::http::StatusCode::OK,
+            )
+            .into_response())
+        }
+        Ok(None) => Ok(NotFoundError::new().response().await.into_response()),
+        Err(error)
+            if error
+                .as_database_error()
+                .is_some_and(|error| error.is_unique_violation()) =>
+        {
+            Ok(ConflictError::new(Some("slugTaken"))
+                .response()
+                .await
+                .into_response())
+        }
+        Err(error) => {
+            error!(target: "api", "series:update - error {:?}", error);
+            Ok(InternalServerError::new().response().await.into_response())
+        }
+    }
+}
+
+pub fn update() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::patch()
+        .and(path("api"))
+        .and(path("v1.0"))
+        .and(path("series"))
+        .and(path::param::<Uuid>())
+        .and(path::end())
+        .and(body::content_length_limit(64 * 1024))
+        .and(body::json::<SeriesUpdate>())
+        .and(jwt_auth())
+        .and_then(|id: Uuid, data: SeriesUpdate, _claims: Claims| async move {
+            update_series(id, data).await
+        })
+}
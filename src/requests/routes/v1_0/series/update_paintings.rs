@@ -0,0 +1,92 @@
+use uuid::Uuid;
+use warp::{body, path, Filter, Rejection, Reply};
+
+use crate::database::connection::get_client;
+use crate::database::models::series::{Series, SeriesPaintingsUpdate};
+use crate::errors::api_error::{InternalServerError, NotFoundError};
+use crate::requests::dto::generic_response::{GenericResponse, Status};
+use crate::utils::auth::token::{jwt_auth, Claims};
+use crate::utils::http_cache::content_changed;
+
+async fn replace_paintings(
+    id: Uuid,
+    data: SeriesPaintingsUpdate,
+) -> Result<Option<Series>, sqlx::Error> {
+    let client = get_client().await?;
+    let mut transaction = client.begin().await?;
+
+    let query = Series::get_by_id_query();
+    let exists = sqlx::query_as::<_, Series>(&query)
+        .bind(id)
+        .fetch_optional(&mut *transaction)
+        .await?
+        .is_some();
+    if !exists {
+        return Ok(None);
+    }
+
+    let query = Series::clear_paintings_query();
+    debug!(target: "db", "series:update_paintings - Series::clear_paintings_query {}", &query);
+    sqlx::query(&query)
+        .bind(id)
+        .execute(&mut *transaction)
+        .await?;
+
+    let query = Series::add_paintings_query();
+    debug!(target: "db", "series:update_paintings - Series::add_paintings_query {}", &query);
+    sqlx::query(&query)
+        .bind(id)
+        .bind(&data.painting_ids)
+        .execute(&mut *transaction)
+        .await?;
+
+    let query = Series::get_by_id_query();
+    let series = sqlx::query_as::<_, Series>(&query)
+        .bind(id)
+        .fetch_optional(&mut *transaction)
+        .await?;
+
+    transaction.commit().await?;
+    Ok(series)
+}
+
+async fn update_series_paintings(
+    id: Uuid,
+    data: SeriesPaintingsUpdate,
+) -> Result<warp::reply::Response, Rejection> {
+    match replace_paintings(id, data).await {
+        Ok(Some(series)) => {
+            content_changed();
+            Ok(GenericResponse::send(
+                Status::Success,
+                "seriesUpdated",
+                Some(series),
+                warp::http::StatusCode::OK,
+            )
+            .into_response())
+        }
+        Ok(None) => Ok(NotFoundError::new().response().await.into_response()),
+        Err(error) => {
+            error!(target: "api", "series:update_paintings - error {:?}", error);
+            Ok(InternalServerError::new().response().await.into_response())
+        }
+    }
+}
+
+pub fn update() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::put()
+        .and(path("api"))
+        .and(path("v1.0"))
+        .and(path("series"))
+        .and(path::param::<Uuid>())
+        .and(path("paintings"))
+        .and(path::end())
+        .and(body::content_length_limit(64 * 1024))
+        .and(body::json::<SeriesPaintingsUpdate>())
+        .and(jwt_auth())
+        .and_then(
+            |id: Uuid, data: SeriesPaintingsUpdate, _claims: Claims| async move {
+                update_series_paintings(id, data).await
+            },
+        )
+}
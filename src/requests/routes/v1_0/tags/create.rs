@@ -0,0 +1,70 @@
+use sqlx::types::Json;
+use warp::{body, path, Filter, Rejection, Reply};
+
+use crate::database::connection::get_client;
+use crate::database::models::generics::translation_patch;
+use crate::database::models::tag::{Tag, TagCreate};
+use crate::errors::api_error::{ConflictError, InternalServerError, ValidationError};
+use crate::requests::dto::generic_response::{GenericResponse, Status};
+use crate::utils::auth::token::{jwt_auth, Claims};
+use crate::utils::http_cache::content_changed;
+
+async fn create_tag(data: TagCreate) -> Result<warp::reply::Response, Rejection> {
+    if let Err(key) = data.validate() {
+        return Ok(ValidationError::new(Some(key))
+            .response()
+            .await
+            .into_response());
+    }
+
+    let client = get_client().await.unwrap();
+    let query = Tag::create_query();
+    debug!(target: "db", "tags:create - Tag::create_query {}", &query);
+    let result = sqlx::query_as::<_, Tag>(&query)
+        .bind(&data.slug)
+        .bind(Json(translation_patch(
+            Some(&data.name_cs),
+            Some(&data.name_en),
+        )))
+        .fetch_one(client)
+        .await;
+
+    match result {
+        Ok(tag) => {
+            content_changed();
+            Ok(GenericResponse::send(
+                Status::Success,
+                "tagCreated",
+                Some(tag),
+                warp::http::StatusCode::CREATED,
+            )
+            .into_response())
+        }
+        Err(error)
+            if error
+                .as_database_error()
+                .is_some_and(|error| error.is_unique_violation()) =>
+        {
+            Ok(ConflictError::new(Some("slugTaken"))
+                .response()
+                .await
+                .into_response())
+        }
+        Err(error) => {
+            error!(target: "api", "tags:create - error {:?}", error);
+            Ok(InternalServerError::new().response().await.into_response())
+        }
+    }
+}
+
+pub fn create() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::post()
+        .and(path("api"))
+        .and(path("v1.0"))
+        .and(path("tags"))
+        .and(path::end())
+        .and(body::content_length_limit(64 * 1024))
+        .and(body::json::<TagCreate>())
+        .and(jwt_auth())
+        .and_then(|data: TagCreate, _claims: Claims| async move { create_tag(data).await })
+}
@@ -0,0 +1,44 @@
+use uuid::Uuid;
+use warp::{path, Filter, Rejection, Reply};
+
+use crate::database::connection::get_client;
+use crate::database::models::tag::Tag;
+use crate::errors::api_error::{InternalServerError, NotFoundError};
+use crate::requests::dto::generic_response::{GenericResponse, Status};
+use crate::utils::auth::token::{jwt_auth, Claims};
+use crate::utils::http_cache::content_changed;
+
+async fn delete_tag(id: Uuid) -> Result<warp::reply::Response, Rejection> {
+    let client = get_client().await.unwrap();
+
+    let query = Tag::delete_query();
+    debug!(target: "db", "tags:delete - Tag::delete_query {}", &query);
+    match sqlx::query(&query).bind(id).fetch_optional(client).await {
+        Ok(Some(_)) => {
+            content_changed();
+            Ok(GenericResponse::<()>::send(
+                Status::Success,
+                "tagDeleted",
+                None,
+                warp::http::StatusCode::OK,
+            )
+            .into_response())
+        }
+        Ok(None) => Ok(NotFoundError::new().response().await.into_response()),
+        Err(error) => {
+            error!(target: "api", "tags:delete - error {:?}", error);
+            Ok(InternalServerError::new().response().await.into_response())
+        }
+    }
+}
+
+pub fn delete() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::delete()
+        .and(path("api"))
+        .and(path("v1.0"))
+        .and(path("tags"))
+        .and(path::param::<Uuid>())
+        .and(path::end())
+        .and(jwt_auth())
+        .and_then(|id: Uuid, _claims: Claims| async move { delete_tag(id).await })
+}
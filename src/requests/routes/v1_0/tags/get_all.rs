@@ -0,0 +1,39 @@
+use warp::{path, Filter, Rejection, Reply};
+
+use crate::database::connection::get_client;
+use crate::database::models::tag::Tag;
+use crate::errors::api_error::InternalServerError;
+use crate::requests::dto::generic_response::{GenericResponse, Status};
+use crate::utils::http_cache::{cache_request, CachePolicy, CacheRequest};
+
+async fn get_all_tags() -> Result<warp::reply::Response, Rejection> {
+    let client = get_client().await.unwrap();
+
+    let query = Tag::get_all_query();
+    debug!(target: "db", "tags:get_all - Tag::get_all_query {}", &query);
+    match sqlx::query_as::<_, Tag>(&query).fetch_all(client).await {
+        Ok(tags) => Ok(GenericResponse::send(
+            Status::Success,
+            "tags",
+            Some(tags),
+            warp::http::StatusCode::OK,
+        )
+        .into_response()),
+        Err(error) => {
+            error!(target: "api", "tags:get_all - error {:?}", error);
+            Ok(InternalServerError::new().response().await.into_response())
+        }
+    }
+}
+
+pub fn get() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get()
+        .and(path("api"))
+        .and(path("v1.0"))
+        .and(path("tags"))
+        .and(path::end())
+        .and(cache_request())
+        .and_then(|request: CacheRequest| async move {
+            request.reply(CachePolicy::Revalidate, get_all_tags).await
+        })
+}
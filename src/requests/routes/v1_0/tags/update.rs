@@ -0,0 +1,78 @@
+use sqlx::types::Json;
+use uuid::Uuid;
+use warp::{body, path, Filter, Rejection, Reply};
+
+use crate::database::connection::get_client;
+use crate::database::models::generics::translation_patch;
+use crate::database::models::tag::{Tag, TagUpdate};
+use crate::errors::api_error::{
+    ConflictError, InternalServerError, NotFoundError, ValidationError,
+};
+use crate::requests::dto::generic_response::{GenericResponse, Status};
+use crate::utils::auth::token::{jwt_auth, Claims};
+use crate::utils::http_cache::content_changed;
+
+async fn update_tag(id: Uuid, data: TagUpdate) -> Result<warp::reply::Response, Rejection> {
+    if let Err(key) = data.validate() {
+        return Ok(ValidationError::new(Some(key))
+            .response()
+            .await
+            .into_response());
+    }
+
+    let client = get_client().await.unwrap();
+    let query = Tag::update_query();
+    debug!(target: "db", "tags:update - Tag::update_query {}", &query);
+    let result = sqlx::query_as::<_, Tag>(&query)
+        .bind(id)
+        .bind(&data.slug)
+        .bind(Json(translation_patch(
+            data.name_cs.as_deref(),
+            data.name_en.as_deref(),
+        )))
+        .fetch_optional(client)
+        .await;
+
+    match result {
+        Ok(Some(tag)) => {
+            content_changed();
+            Ok(GenericResponse::send(
+                Status::Success,
+                "tagUpdated",
+                Some(tag),
+                warp::http::StatusCode::OK,
+            )
+            .into_response())
+        }
+        Ok(None) => Ok(NotFoundError::new().response().await.into_response()),
+        Err(error)
+            if error
+                .as_database_error()
+                .is_some_and(|error| error.is_unique_violation()) =>
+        {
+            Ok(ConflictError::new(Some("slugTaken"))
+                .response()
+                .await
+                .into_response())
+        }
+        Err(error) => {
+            error!(target: "api", "tags:update - error {:?}", error);
+            Ok(InternalServerError::new().response().await.into_response())
+        }
+    }
+}
+
+pub fn update() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::patch()
+        .and(path("api"))
+        .and(path("v1.0"))
+        .and(path("tags"))
+        .and(path::param::<Uuid>())
+        .and(path::end())
+        .and(body::content_length_limit(64 * 1024))
+        .and(body::json::<TagUpdate>())
+        .and(jwt_auth())
+        .and_then(
+            |id: Uuid, data: TagUpdate, _claims: Claims| async move { update_tag(id, data).await },
+        )
+}
@@ -0,0 +1,60 @@
+use warp::{path, Filter, Rejection, Reply};
+
+use crate::client::translations::load_overrides;
+use crate::database::connection::get_client;
+use crate::database::models::translation_override::TranslationOverride;
+use crate::errors::api_error::{InternalServerError, NotFoundError};
+use crate::requests::dto::generic_response::{GenericResponse, Status};
+use crate::utils::auth::token::{jwt_auth, Claims};
+
+/// Removes the override, the compiled message is shown again.
+async fn delete_translation(
+    language: String,
+    key: String,
+) -> Result<warp::reply::Response, Rejection> {
+    let client = get_client().await.unwrap();
+
+    let query = TranslationOverride::delete_query();
+    debug!(target: "db", "translations:delete - TranslationOverride::delete_query {}", &query);
+    match sqlx::query_as::<_, TranslationOverride>(&query)
+        .bind(language.to_lowercase())
+        .bind(&key)
+        .fetch_optional(client)
+        .await
+    {
+        Ok(Some(_)) => {}
+        Ok(None) => return Ok(NotFoundError::new().response().await.into_response()),
+        Err(error) => {
+            error!(target: "api", "translations:delete - error {:?}", error);
+            return Ok(InternalServerError::new().response().await.into_response());
+        }
+    };
+
+    if let Err(error) = load_overrides().await {
+        error!(target: "api", "translations:delete - failed to reload translations {:?}", error);
+    }
+
+    Ok(GenericResponse::<()>::send(
+        Status::Success,
+        "translationDeleted",
+        None,
+        warp::http::StatusCode::OK,
+    )
+    .into_response())
+}
+
+pub fn delete() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::delete()
+        .and(path("api"))
+        .and(path("v1.0"))
+        .and(path("translations"))
+        .and(path::param::<String>())
+        .and(path::param::<String>())
+        .and(path::end())
+        .and(jwt_auth())
+        .and_then(
+            |language: String, key: String, _claims: Claims| async move {
+                delete_translation(language, key).await
+            },
+        )
+}
@@ -0,0 +1,73 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use warp::{path, Filter, Rejection, Reply};
+
+use crate::client::translations::{default_source, languages, TranslationKeys};
+use crate::database::connection::get_client;
+use crate::database::models::translation_override::TranslationOverride;
+use crate::errors::api_error::InternalServerError;
+use crate::utils::auth::token::{jwt_auth, Claims};
+
+/// Message of one language as the administration edits it
+#[derive(Debug, Serialize)]
+struct TranslationEntry {
+    language: &'static str,
+    key: &'static str,
+    /// value in the compiled catalog
+    default: &'static str,
+    /// value from the database that is shown instead
+    value: Option<String>,
+    updated: Option<DateTime<Utc>>,
+}
+
+async fn get_translations() -> Result<impl Reply, Rejection> {
+    let client = get_client().await.unwrap();
+
+    let query = TranslationOverride::get_all_query();
+    debug!(target: "db", "translations:get_all - TranslationOverride::get_all_query {}", &query);
+    let rows = match sqlx::query_as::<_, TranslationOverride>(&query)
+        .fetch_all(client)
+        .await
+    {
+        Ok(rows) => rows,
+        Err(error) => {
+            error!(target: "api", "translations:get_all - error {:?}", error);
+            return Ok(InternalServerError::new().response().await);
+        }
+    };
+
+    let mut overrides: HashMap<(String, String), TranslationOverride> = rows
+        .into_iter()
+        .map(|row| ((row.language.clone(), row.key.clone()), row))
+        .collect();
+
+    let mut entries = Vec::new();
+    for language in languages() {
+        for key in TranslationKeys::ALL {
+            let stored = overrides.remove(&(language.to_string(), key.id().to_string()));
+            entries.push(TranslationEntry {
+                language: language.to_str(),
+                key: key.id(),
+                default: default_source(*key, *language),
+                value: stored.as_ref().map(|row| row.value.clone()),
+                updated: stored.map(|row| row.updated),
+            });
+        }
+    }
+
+    Ok(warp::reply::with_status(
+        warp::reply::json(&entries),
+        warp::http::StatusCode::OK,
+    ))
+}
+
+pub fn get() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get()
+        .and(path("api"))
+        .and(path("v1.0"))
+        .and(path("translations"))
+        .and(path::end())
+        .and(jwt_auth())
+        .and_then(|_claims: Claims| async move { get_translations().await })
+}
@@ -0,0 +1,81 @@
+use warp::{body, path, Filter, Rejection, Reply};
+
+use crate::client::translations::{load_overrides, validate_override, Language, TranslationKeys};
+use crate::database::connection::get_client;
+use crate::database::models::translation_override::{
+    TranslationOverride, TranslationOverrideUpdate,
+};
+use crate::errors::api_error::{InternalServerError, ValidationError};
+use crate::requests::dto::generic_response::{GenericResponse, Status};
+use crate::utils::auth::token::{jwt_auth, Claims};
+
+async fn update_translation(
+    language: String,
+    key: String,
+    data: TranslationOverrideUpdate,
+) -> Result<warp::reply::Response, Rejection> {
+    let (Some(language), Some(key)) = (
+        Language::from_code(&language),
+        TranslationKeys::from_id(&key),
+    ) else {
+        return Ok(ValidationError::new(Some("unknownTranslation"))
+            .response()
+            .await
+            .into_response());
+    };
+
+    if let Err(error) = validate_override(key, language, &data.value) {
+        debug!(target: "api", "translations:update - invalid value {}", error);
+        return Ok(ValidationError::new(Some("invalidTranslation"))
+            .response()
+            .await
+            .into_response());
+    }
+
+    let client = get_client().await.unwrap();
+    let query = TranslationOverride::upsert_query();
+    debug!(target: "db", "translations:update - TranslationOverride::upsert_query {}", &query);
+    let stored = match sqlx::query_as::<_, TranslationOverride>(&query)
+        .bind(language.to_str())
+        .bind(key.id())
+        .bind(&data.value)
+        .fetch_one(client)
+        .await
+    {
+        Ok(value) => value,
+        Err(error) => {
+            error!(target: "api", "translations:update - error {:?}", error);
+            return Ok(InternalServerError::new().response().await.into_response());
+        }
+    };
+
+    if let Err(error) = load_overrides().await {
+        error!(target: "api", "translations:update - failed to reload translations {:?}", error);
+    }
+
+    Ok(GenericResponse::send(
+        Status::Success,
+        "translationUpdated",
+        Some(stored),
+        warp::http::StatusCode::OK,
+    )
+    .into_response())
+}
+
+pub fn update() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::put()
+        .and(path("api"))
+        .and(path("v1.0"))
+        .and(path("translations"))
+        .and(path::param::<String>())
+        .and(path::param::<String>())
+        .and(path::end())
+        .and(body::content_length_limit(64 * 1024))
+        .and(body::json::<TranslationOverrideUpdate>())
+        .and(jwt_auth())
+        .and_then(
+            |language: String, key: String, data: TranslationOverrideUpdate, _claims: Claims| async move {
+                update_translation(language, key, data).await
+            },
+        )
+}
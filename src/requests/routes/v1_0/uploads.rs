@@ -0,0 +1,14 @@
+pub mod create;
+pub mod delete;
+pub mod finalize;
+pub mod head;
+pub mod patch;
+
+/// Version of the tus resumable upload protocol the endpoints follow
+pub const TUS_RESUMABLE: &str = "1.0.0";
+/// Abandoned uploads expire after this many hours without a new chunk
+pub const UPLOAD_EXPIRATION_HOURS: i64 = 24;
+/// Maximum size of a whole resumable upload
+pub const MAX_UPLOAD_SIZE: i64 = 2_147_483_648;
+/// Maximum size of a single PATCH chunk
+pub const MAX_CHUNK_SIZE: u64 = 16_777_216;
@@ -0,0 +1,105 @@
+use std::path::Path;
+use uuid::Uuid;
+use warp::http::header::LOCATION;
+use warp::{body, path, Filter, Rejection, Reply};
+
+use crate::config::load::{get, ConfigField};
+use crate::database::connection::get_client;
+use crate::database::models::image::ImageMetaPart;
+use crate::database::models::upload::{ImageUpload, ImageUploadCreate};
+use crate::errors::api_error::{InternalServerError, ValidationError};
+use crate::requests::dto::generic_response::{GenericResponse, Status};
+use crate::requests::routes::v1_0::paintings_images::create::image_extension;
+use crate::requests::routes::v1_0::uploads::{
+    MAX_UPLOAD_SIZE, TUS_RESUMABLE, UPLOAD_EXPIRATION_HOURS,
+};
+use crate::utils::auth::token::{jwt_auth, Claims};
+
+async fn create_upload(data: ImageUploadCreate) -> Result<warp::reply::Response, Rejection> {
+    let client = get_client().await.unwrap();
+    debug!(target: "api", "uploads:create - data {:?}", &data);
+
+    if data.upload_length <= 0 || data.upload_length > MAX_UPLOAD_SIZE {
+        return Ok(ValidationError::new(Some("invalidUploadLength"))
+            .response()
+            .await
+            .into_response());
+    }
+
+    let Some(extension) = image_extension(Some(&data.content_type)) else {
+        return Ok(ValidationError::new(Some("unsupportedFileType"))
+            .response()
+            .await
+            .into_response());
+    };
+
+    let static_file_dir = match get::<String>(ConfigField::StaticFilesDir).await {
+        Ok(path) => path,
+        Err(error) => {
+            error!(target: "api", "uploads:create - error getting static dir path {}", error);
+            return Ok(InternalServerError::new().response().await.into_response());
+        }
+    };
+
+    let upload_dir = Path::new(&static_file_dir).join("uploads");
+    if let Err(error) = tokio::fs::create_dir_all(&upload_dir).await {
+        error!(target: "api", "uploads:create - failed to create upload dir {}", error);
+        return Ok(InternalServerError::new().response().await.into_response());
+    }
+
+    let id = Uuid::new_v4();
+    let file_path = upload_dir.join(format!("{}.{}", id, extension));
+    let meta = ImageMetaPart {
+        preview: data.preview,
+        title_cs: data.title_cs,
+        title_en: data.title_en,
+        alt_cs: data.alt_cs,
+        alt_en: data.alt_en,
+    };
+
+    let query = ImageUpload::create_query(UPLOAD_EXPIRATION_HOURS);
+    debug!(target: "db", "uploads:create - ImageUpload::create_query {}", &query);
+    let create_result = sqlx::query_as::<_, ImageUpload>(&query)
+        .bind(id)
+        .bind(data.painting_id)
+        .bind(&data.file_name)
+        .bind(&data.content_type)
+        .bind(data.upload_length)
+        .bind(file_path.to_string_lossy().to_string())
+        .bind(sqlx::types::Json(&meta))
+        .fetch_one(client)
+        .await;
+
+    let upload = match create_result {
+        Ok(value) => value,
+        Err(error) => {
+            error!(target: "api", "uploads:create - error {:?}", error);
+            return Ok(InternalServerError::new().response().await.into_response());
+        }
+    };
+
+    let location = format!("/api/v1.0/uploads/{}", upload.id);
+    let response = GenericResponse::send(
+        Status::Success,
+        "uploadCreated",
+        Some(upload),
+        warp::http::StatusCode::CREATED,
+    );
+    let response = warp::reply::with_header(response, LOCATION, location);
+    let response = warp::reply::with_header(response, "Upload-Offset", "0");
+    Ok(warp::reply::with_header(response, "Tus-Resumable", TUS_RESUMABLE).into_response())
+}
+
+pub fn create() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::post()
+        .and(path("api"))
+        .and(path("v1.0"))
+        .and(path("uploads"))
+        .and(path::end())
+        .and(body::content_length_limit(1024 * 1024))
+        .and(body::json::<ImageUploadCreate>())
+        .and(jwt_auth())
+        .and_then(
+            |data: ImageUploadCreate, _claims: Claims| async move { create_upload(data).await },
+        )
+}
@@ -0,0 +1,50 @@
+use uuid::Uuid;
+use warp::{path, Filter, Rejection, Reply};
+
+use crate::database::connection::get_client;
+use crate::database::models::upload::ImageUpload;
+use crate::errors::api_error::{InternalServerError, NotFoundError};
+use crate::requests::dto::generic_response::{GenericResponse, Status};
+use crate::utils::auth::token::{jwt_auth, Claims};
+use crate::utils::file_system::fs_delete::remove_file;
+
+async fn delete_upload(id: Uuid) -> Result<warp::reply::Response, Rejection> {
+    let client = get_client().await.unwrap();
+
+    let query = ImageUpload::delete_query(id);
+    debug!(target: "db", "uploads:delete - ImageUpload::delete_query {}", &query);
+    let upload = match sqlx::query_as::<_, ImageUpload>(&query)
+        .fetch_optional(client)
+        .await
+    {
+        Ok(Some(value)) => value,
+        Ok(None) => return Ok(NotFoundError::new().response().await.into_response()),
+        Err(error) => {
+            error!(target: "api", "uploads:delete - error {:?}", error);
+            return Ok(InternalServerError::new().response().await.into_response());
+        }
+    };
+
+    if upload.upload_offset > 0 && !remove_file(&upload.file_path).await {
+        error!(target: "api", "uploads:delete - failed to remove partial upload file");
+    }
+
+    Ok(GenericResponse::<()>::send(
+        Status::Success,
+        "uploadDeleted",
+        None,
+        warp::http::StatusCode::OK,
+    )
+    .into_response())
+}
+
+pub fn delete() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::delete()
+        .and(path("api"))
+        .and(path("v1.0"))
+        .and(path("uploads"))
+        .and(path::param::<Uuid>())
+        .and(path::end())
+        .and(jwt_auth())
+        .and_then(|id: Uuid, _claims: Claims| async move { delete_upload(id).await })
+}
@@ -0,0 +1,110 @@
+use std::path::Path;
+use uuid::Uuid;
+use warp::{path, Filter, Rejection, Reply};
+
+use crate::config::load::{get, ConfigField};
+use crate::database::connection::get_client;
+use crate::database::models::image::PaintingImageCreate;
+use crate::database::models::upload::ImageUpload;
+use crate::errors::api_error::{ConflictError, InternalServerError, NotFoundError};
+use crate::requests::dto::generic_response::{GenericResponse, Status};
+use crate::requests::routes::v1_0::paintings_images::create::process_image;
+use crate::utils::auth::token::{jwt_auth, Claims};
+use crate::utils::file_system::fs_delete::remove_file;
+use crate::utils::http_cache::content_changed;
+
+async fn finalize_upload(id: Uuid) -> Result<warp::reply::Response, Rejection> {
+    let client = get_client().await.unwrap();
+
+    let query = ImageUpload::get_by_id_query(id);
+    debug!(target: "db", "uploads:finalize - ImageUpload::get_by_id_query {}", &query);
+    let upload = match sqlx::query_as::<_, ImageUpload>(&query)
+        .fetch_optional(client)
+        .await
+    {
+        Ok(Some(value)) => value,
+        Ok(None) => return Ok(NotFoundError::new().response().await.into_response()),
+        Err(error) => {
+            error!(target: "api", "uploads:finalize - error {:?}", error);
+            return Ok(InternalServerError::new().response().await.into_response());
+        }
+    };
+
+    if !upload.is_complete() {
+        return Ok(ConflictError::new(Some("uploadIncomplete"))
+            .response()
+            .await
+            .into_response());
+    }
+
+    let static_file_dir = match get::<String>(ConfigField::StaticFilesDir).await {
+        Ok(path) => path,
+        Err(error) => {
+            error!(target: "api", "uploads:finalize - error getting static dir path {}", error);
+            return Ok(InternalServerError::new().response().await.into_response());
+        }
+    };
+
+    // removing the row first keeps a concurrent finalize from processing the file twice
+    let delete_query = ImageUpload::delete_query(id);
+    debug!(target: "db", "uploads:finalize - ImageUpload::delete_query {}", &delete_query);
+    match sqlx::query_as::<_, ImageUpload>(&delete_query)
+        .fetch_optional(client)
+        .await
+    {
+        Ok(Some(_)) => {}
+        Ok(None) => return Ok(NotFoundError::new().response().await.into_response()),
+        Err(error) => {
+            error!(target: "api", "uploads:finalize - failed to remove upload {:?}", error);
+            return Ok(InternalServerError::new().response().await.into_response());
+        }
+    };
+
+    let image_data = PaintingImageCreate {
+        preview: upload.meta.preview.unwrap_or(false),
+        alt_cs: upload.meta.alt_cs.unwrap_or_default(),
+        alt_en: upload.meta.alt_en.unwrap_or_default(),
+        title_cs: upload.meta.title_cs.unwrap_or_default(),
+        title_en: upload.meta.title_en.unwrap_or_default(),
+        painting_id: upload.painting_id,
+    };
+
+    let file_system_path = Path::new(&upload.file_path);
+    let processed = process_image(client, image_data, file_system_path, &static_file_dir).await;
+
+    if !remove_file(&upload.file_path).await {
+        error!(target: "api", "uploads:finalize - failed to remove uploaded original");
+    }
+
+    match processed {
+        Ok(image) => {
+            content_changed();
+            Ok(GenericResponse::send(
+                Status::Success,
+                "paintingImageCreated",
+                Some(image),
+                warp::http::StatusCode::CREATED,
+            )
+            .into_response())
+        }
+        Err(message) => Ok(GenericResponse::<()>::send(
+            Status::Error,
+            message,
+            None,
+            warp::http::StatusCode::INTERNAL_SERVER_ERROR,
+        )
+        .into_response()),
+    }
+}
+
+pub fn finalize() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::post()
+        .and(path("api"))
+        .and(path("v1.0"))
+        .and(path("uploads"))
+        .and(path::param::<Uuid>())
+        .and(path("finalize"))
+        .and(path::end())
+        .and(jwt_auth())
+        .and_then(|id: Uuid, _claims: Claims| async move { finalize_upload(id).await })
+}
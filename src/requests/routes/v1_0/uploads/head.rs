@@ -0,0 +1,48 @@
+use uuid::Uuid;
+use warp::http::header::CACHE_CONTROL;
+use warp::{path, Filter, Rejection, Reply};
+
+use crate::database::connection::get_client;
+use crate::database::models::upload::ImageUpload;
+use crate::errors::api_error::{InternalServerError, NotFoundError};
+use crate::requests::routes::v1_0::uploads::TUS_RESUMABLE;
+use crate::utils::auth::token::{jwt_auth, Claims};
+
+async fn upload_status(id: Uuid) -> Result<warp::reply::Response, Rejection> {
+    let client = get_client().await.unwrap();
+
+    let query = ImageUpload::get_by_id_query(id);
+    debug!(target: "db", "uploads:head - ImageUpload::get_by_id_query {}", &query);
+    let upload = match sqlx::query_as::<_, ImageUpload>(&query)
+        .fetch_optional(client)
+        .await
+    {
+        Ok(Some(value)) => value,
+        Ok(None) => return Ok(NotFoundError::new().response().await.into_response()),
+        Err(error) => {
+            error!(target: "api", "uploads:head - error {:?}", error);
+            return Ok(InternalServerError::new().response().await.into_response());
+        }
+    };
+
+    let response = warp::reply::with_header(
+        warp::reply(),
+        "Upload-Offset",
+        upload.upload_offset.to_string(),
+    );
+    let response =
+        warp::reply::with_header(response, "Upload-Length", upload.upload_length.to_string());
+    let response = warp::reply::with_header(response, CACHE_CONTROL, "no-store");
+    Ok(warp::reply::with_header(response, "Tus-Resumable", TUS_RESUMABLE).into_response())
+}
+
+pub fn head() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::head()
+        .and(path("api"))
+        .and(path("v1.0"))
+        .and(path("uploads"))
+        .and(path::param::<Uuid>())
+        .and(path::end())
+        .and(jwt_auth())
+        .and_then(|id: Uuid, _claims: Claims| async move { upload_status(id).await })
+}
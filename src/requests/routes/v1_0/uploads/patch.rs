@@ -0,0 +1,160 @@
+use bytes::Buf;
+use futures_util::Stream;
+use uuid::Uuid;
+use warp::http::StatusCode;
+use warp::{body, header, path, Filter, Rejection, Reply};
+
+use crate::database::connection::get_client;
+use crate::database::models::upload::ImageUpload;
+use crate::errors::api_error::{ConflictError, InternalServerError, NotFoundError};
+use crate::requests::dto::generic_response::{GenericResponse, Status};
+use crate::requests::routes::v1_0::uploads::{
+    MAX_CHUNK_SIZE, TUS_RESUMABLE, UPLOAD_EXPIRATION_HOURS,
+};
+use crate::utils::auth::token::{jwt_auth, Claims};
+use crate::utils::file_system::fs_write::{append_stream, truncate_file};
+
+const OFFSET_CONTENT_TYPE: &str = "application/offset+octet-stream";
+
+/// Cuts a written chunk whose offset was not stored, the file has to end at the stored offset.
+async fn discard_chunk(upload: &ImageUpload) {
+    if let Err(error) = truncate_file(&upload.file_path, upload.upload_offset as u64).await {
+        error!(target: "api", "uploads:patch - failed to discard chunk {}", error);
+    }
+}
+
+async fn upload_chunk<S, B>(
+    id: Uuid,
+    offset: i64,
+    content_type: String,
+    data: S,
+) -> Result<warp::reply::Response, Rejection>
+where
+    S: Stream<Item = Result<B, warp::Error>> + Unpin,
+    B: Buf,
+{
+    let client = get_client().await.unwrap();
+
+    if content_type != OFFSET_CONTENT_TYPE {
+        return Ok(GenericResponse::<()>::send(
+            Status::Error,
+            "unsupportedMediaType",
+            None,
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+        )
+        .into_response());
+    }
+
+    // the row stays locked until the new offset is stored, so concurrent chunks of one
+    // upload never write into the file at the same time
+    let mut transaction = match client.begin().await {
+        Ok(transaction) => transaction,
+        Err(error) => {
+            error!(target: "api", "uploads:patch - error {:?}", error);
+            return Ok(InternalServerError::new().response().await.into_response());
+        }
+    };
+
+    let query = ImageUpload::lock_query(id);
+    debug!(target: "db", "uploads:patch - ImageUpload::lock_query {}", &query);
+    let upload = match sqlx::query_as::<_, ImageUpload>(&query)
+        .fetch_optional(&mut *transaction)
+        .await
+    {
+        Ok(Some(value)) => value,
+        Ok(None) => return Ok(NotFoundError::new().response().await.into_response()),
+        Err(error) => {
+            error!(target: "api", "uploads:patch - error {:?}", error);
+            return Ok(InternalServerError::new().response().await.into_response());
+        }
+    };
+
+    if upload.upload_offset != offset {
+        debug!(
+            target: "api",
+            "uploads:patch - offset mismatch {} != {}", upload.upload_offset, offset
+        );
+        return Ok(ConflictError::new(Some("uploadOffsetMismatch"))
+            .response()
+            .await
+            .into_response());
+    }
+
+    let remaining = (upload.upload_length - upload.upload_offset) as u64;
+    let written = append_stream(
+        data,
+        &upload.file_path,
+        upload.upload_offset as u64,
+        remaining.min(MAX_CHUNK_SIZE),
+    )
+    .await;
+    let written = match written {
+        Ok(size) => size as i64,
+        Err(error) => {
+            error!(target: "api", "uploads:patch - failed to append chunk {}", error);
+            return Ok(GenericResponse::<()>::send(
+                Status::Error,
+                "uploadChunkFailed",
+                None,
+                StatusCode::BAD_REQUEST,
+            )
+            .into_response());
+        }
+    };
+
+    let new_offset = upload.upload_offset + written;
+    let update_query = ImageUpload::update_offset_query(
+        id,
+        upload.upload_offset,
+        new_offset,
+        UPLOAD_EXPIRATION_HOURS,
+    );
+    debug!(target: "db", "uploads:patch - ImageUpload::update_offset_query {}", &update_query);
+    match sqlx::query_as::<_, ImageUpload>(&update_query)
+        .fetch_optional(&mut *transaction)
+        .await
+    {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            discard_chunk(&upload).await;
+            return Ok(ConflictError::new(Some("uploadOffsetMismatch"))
+                .response()
+                .await
+                .into_response());
+        }
+        Err(error) => {
+            error!(target: "api", "uploads:patch - failed to update offset {:?}", error);
+            discard_chunk(&upload).await;
+            return Ok(InternalServerError::new().response().await.into_response());
+        }
+    };
+
+    if let Err(error) = transaction.commit().await {
+        error!(target: "api", "uploads:patch - failed to commit offset {:?}", error);
+        discard_chunk(&upload).await;
+        return Ok(InternalServerError::new().response().await.into_response());
+    }
+
+    let response = warp::reply::with_status(warp::reply(), StatusCode::NO_CONTENT);
+    let response = warp::reply::with_header(response, "Upload-Offset", new_offset.to_string());
+    Ok(warp::reply::with_header(response, "Tus-Resumable", TUS_RESUMABLE).into_response())
+}
+
+pub fn patch() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::patch()
+        .and(path("api"))
+        .and(path("v1.0"))
+        .and(path("uploads"))
+        .and(path::param::<Uuid>())
+        .and(path::end())
+        .and(header::<i64>("Upload-Offset"))
+        .and(header::<String>("Content-Type"))
+        .and(body::content_length_limit(MAX_CHUNK_SIZE))
+        .and(body::stream())
+        .and(jwt_auth())
+        .and_then(
+            |id: Uuid, offset: i64, content_type: String, data, _claims: Claims| async move {
+                upload_chunk(id, offset, content_type, Box::pin(data)).await
+            },
+        )
+}
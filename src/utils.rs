@@ -1,4 +1,16 @@
+pub mod assets;
 pub mod auth;
+pub mod cache;
+pub mod compression;
 pub mod cors;
+pub mod feed;
 pub mod file_system;
+pub mod hex;
+pub mod http_cache;
 pub mod images;
+pub mod mail;
+pub mod money;
+pub mod sitemap;
+pub mod slug;
+pub mod spam;
+pub mod uploads;
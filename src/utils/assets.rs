@@ -0,0 +1,138 @@
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::utils::compression::{encode_best, Encoding};
+use crate::utils::hex::to_hex;
+
+include!(concat!(env!("OUT_DIR"), "/assets.rs"));
+
+/// Path the assets are served under
+pub const ASSETS_PATH: &str = "/assets";
+/// Hex digits of the content hash in a fingerprinted file name
+const FINGERPRINT_LENGTH: usize = 10;
+
+/// A stylesheet or script of `static/assets` with its encodings computed once
+pub struct Asset {
+    /// `main.3f2a1b9c0d.css`
+    pub fingerprinted: String,
+    pub content_type: &'static str,
+    pub etag: String,
+    pub body: &'static [u8],
+    pub gzip: Option<Vec<u8>>,
+    pub brotli: Option<Vec<u8>>,
+}
+
+impl Asset {
+    /// Body in `encoding`, the plain one when it could not be compressed.
+    pub fn encoded(&self, encoding: Encoding) -> (&[u8], Encoding) {
+        match (encoding, &self.brotli, &self.gzip) {
+            (Encoding::Brotli, Some(body), _) => (body, Encoding::Brotli),
+            (Encoding::Gzip, _, Some(body)) => (body, Encoding::Gzip),
+            _ => (self.body, Encoding::Identity),
+        }
+    }
+}
+
+struct Assets {
+    by_name: HashMap<&'static str, Asset>,
+    /// fingerprinted name to the plain one
+    names: HashMap<String, &'static str>,
+}
+
+static ASSETS: OnceLock<Assets> = OnceLock::new();
+
+fn content_type(name: &str) -> &'static str {
+    match name.rsplit('.').next() {
+        Some("css") => "text/css; charset=utf-8",
+        Some("js") => "text/javascript; charset=utf-8",
+        _ => "application/octet-stream",
+    }
+}
+
+/// `main.css` with the hash `3f2a1b9c0d` becomes `main.3f2a1b9c0d.css`.
+fn fingerprint(name: &str, hash: &str) -> String {
+    match name.rsplit_once('.') {
+        Some((stem, extension)) => format!("{}.{}.{}", stem, hash, extension),
+        None => format!("{}.{}", name, hash),
+    }
+}
+
+fn compressed(name: &str, body: &[u8], encoding: Encoding) -> Option<Vec<u8>> {
+    match encode_best(body, encoding) {
+        // not worth the Content-Encoding header when it does not shrink
+        Ok(encoded) if encoded.len() < body.len() => Some(encoded),
+        Ok(_) => None,
+        Err(error) => {
+            error!(target: "app", "assets:compressed - {} {:?}", name, error);
+            None
+        }
+    }
+}
+
+fn assets() -> &'static Assets {
+    ASSETS.get_or_init(|| {
+        let mut by_name = HashMap::new();
+        let mut names = HashMap::new();
+        for (name, body) in ASSET_SOURCES {
+            let hash = to_hex(&Sha256::digest(body));
+            let asset = Asset {
+                fingerprinted: fingerprint(name, &hash[..FINGERPRINT_LENGTH]),
+                content_type: content_type(name),
+                etag: format!("\"{}\"", &hash[..FINGERPRINT_LENGTH]),
+                body,
+                gzip: compressed(name, body, Encoding::Gzip),
+                brotli: compressed(name, body, Encoding::Brotli),
+            };
+            names.insert(asset.fingerprinted.clone(), *name);
+            by_name.insert(*name, asset);
+        }
+        Assets { by_name, names }
+    })
+}
+
+/// Hashes and compresses the embedded assets before the first request needs them.
+pub fn init_assets() -> usize {
+    assets().by_name.len()
+}
+
+/// URL of `name` with its content hash, browsers may keep it for as long as they want.
+pub fn asset_url(name: &str) -> String {
+    match assets().by_name.get(name) {
+        Some(asset) => format!("{}/{}", ASSETS_PATH, asset.fingerprinted),
+        None => {
+            error!(target: "template", "assets:asset_url - unknown asset {}", name);
+            format!("{}/{}", ASSETS_PATH, name)
+        }
+    }
+}
+
+/// The asset behind a fingerprinted file name, `true` when the name is the fingerprinted one.
+pub fn find_asset(file_name: &str) -> Option<(&'static Asset, bool)> {
+    let assets = assets();
+    match assets.names.get(file_name) {
+        Some(name) => Some((&assets.by_name[name], true)),
+        None => assets.by_name.get(file_name).map(|asset| (asset, false)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_asset_url() {
+        assert_eq!(fingerprint("main.css", "3f2a1b9c0d"), "main.3f2a1b9c0d.css");
+
+        let url = asset_url("main.css");
+        let file_name = url.strip_prefix("/assets/").unwrap();
+        assert!(file_name.starts_with("main.") && file_name.ends_with(".css"));
+        assert_eq!(file_name.len(), "main..css".len() + FINGERPRINT_LENGTH);
+
+        let (asset, fingerprinted) = find_asset(file_name).unwrap();
+        assert!(fingerprinted);
+        assert_eq!(asset.content_type, "text/css; charset=utf-8");
+        assert!(!find_asset("main.css").unwrap().1);
+        assert!(find_asset("missing.css").is_none());
+    }
+}
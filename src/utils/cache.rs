@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Values kept in memory for a while, good enough for a single instance.
+pub struct TtlCache<K, V> {
+    ttl: Duration,
+    capacity: usize,
+    entries: Mutex<HashMap<K, (Instant, V)>>,
+}
+
+impl<K: Eq + Hash, V: Clone> TtlCache<K, V> {
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            ttl,
+            capacity,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Copy of the value stored under `key` unless it is older than the ttl.
+    pub fn get(&self, key: &K, now: Instant) -> Option<V> {
+        let entries = self
+            .entries
+            .lock()
+            .unwrap_or_else(|error| error.into_inner());
+        entries
+            .get(key)
+            .filter(|(stored, _)| now.duration_since(*stored) < self.ttl)
+            .map(|(_, value)| value.clone())
+    }
+
+    pub fn insert(&self, key: K, value: V, now: Instant) {
+        let mut entries = self
+            .entries
+            .lock()
+            .unwrap_or_else(|error| error.into_inner());
+
+        // drop what expired first, then everything if it is still full
+        if entries.len() >= self.capacity {
+            let ttl = self.ttl;
+            entries.retain(|_, (stored, _)| now.duration_since(*stored) < ttl);
+        }
+        if entries.len() >= self.capacity {
+            entries.clear();
+        }
+
+        entries.insert(key, (now, value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ttl_cache() {
+        let cache = TtlCache::new(2, Duration::from_secs(60));
+        let now = Instant::now();
+
+        cache.insert("a", 1, now);
+        assert_eq!(cache.get(&"a", now), Some(1));
+        assert_eq!(cache.get(&"a", now + Duration::from_secs(61)), None);
+        assert_eq!(cache.get(&"b", now), None);
+
+        cache.insert("b", 2, now);
+        cache.insert("c", 3, now + Duration::from_secs(1));
+        assert_eq!(cache.get(&"c", now), Some(3));
+        assert_eq!(cache.get(&"a", now), None);
+    }
+}
@@ -0,0 +1,236 @@
+use flate2::write::GzEncoder;
+use std::io::{self, Write};
+use warp::http::header::{HeaderValue, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE, ETAG, VARY};
+use warp::http::StatusCode;
+use warp::hyper::body::to_bytes;
+use warp::{Filter, Rejection, Reply};
+
+/// Smaller bodies fit a single packet, encoding them only costs time
+const MIN_COMPRESSED_SIZE: usize = 1024;
+/// Quality of responses compressed per request, the assets use the best one
+const BROTLI_QUALITY: u32 = 5;
+const BROTLI_BEST_QUALITY: u32 = 11;
+const BROTLI_WINDOW: u32 = 22;
+const GZIP_LEVEL: u32 = 6;
+const GZIP_BEST_LEVEL: u32 = 9;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Encoding {
+    Brotli,
+    Gzip,
+    Identity,
+}
+
+impl Encoding {
+    pub fn to_str(self) -> &'static str {
+        match self {
+            Encoding::Brotli => "br",
+            Encoding::Gzip => "gzip",
+            Encoding::Identity => "identity",
+        }
+    }
+}
+
+/// Encoding of an `Accept-Encoding` header, brotli wins over gzip when both are as welcome.
+pub fn negotiate(accept_encoding: Option<&str>) -> Encoding {
+    let Some(header) = accept_encoding else {
+        return Encoding::Identity;
+    };
+
+    let listed = |name: &str| -> Option<f32> {
+        header.split(',').find_map(|coding| {
+            let mut parts = coding.split(';').map(str::trim);
+            if !parts.next()?.eq_ignore_ascii_case(name) {
+                return None;
+            }
+            parts
+                .find_map(|param| param.strip_prefix("q="))
+                .map_or(Some(1.0), |value| value.trim().parse::<f32>().ok())
+        })
+    };
+    // a coding named on its own overrides the wildcard
+    let quality = |name: &str| listed(name).or_else(|| listed("*"));
+
+    [Encoding::Brotli, Encoding::Gzip]
+        .into_iter()
+        .filter_map(|encoding| Some((quality(encoding.to_str())?, encoding)))
+        .filter(|(quality, _)| *quality > 0.0)
+        .fold(
+            None,
+            |best: Option<(f32, Encoding)>, candidate| match best {
+                Some(best) if best.0 >= candidate.0 => Some(best),
+                _ => Some(candidate),
+            },
+        )
+        .map_or(Encoding::Identity, |(_, encoding)| encoding)
+}
+
+/// HTML, JSON, stylesheets, scripts and the XML of feeds and the sitemap, images are
+/// compressed already.
+pub fn is_compressible(content_type: &str) -> bool {
+    let mime = content_type.split(';').next().unwrap_or("").trim();
+    mime.starts_with("text/")
+        || matches!(
+            mime,
+            "application/json"
+                | "application/javascript"
+                | "application/xml"
+                | "application/atom+xml"
+                | "application/rss+xml"
+                | "image/svg+xml"
+        )
+}
+
+fn encode(body: &[u8], encoding: Encoding, best: bool) -> io::Result<Vec<u8>> {
+    match encoding {
+        Encoding::Brotli => {
+            let quality = if best {
+                BROTLI_BEST_QUALITY
+            } else {
+                BROTLI_QUALITY
+            };
+            let mut writer =
+                brotli::CompressorWriter::new(Vec::new(), 4096, quality, BROTLI_WINDOW);
+            writer.write_all(body)?;
+            Ok(writer.into_inner())
+        }
+        Encoding::Gzip => {
+            let level = if best { GZIP_BEST_LEVEL } else { GZIP_LEVEL };
+            let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::new(level));
+            encoder.write_all(body)?;
+            encoder.finish()
+        }
+        Encoding::Identity => Ok(body.to_vec()),
+    }
+}
+
+/// Compressed once with the best quality, for content that is served many times.
+pub fn encode_best(body: &[u8], encoding: Encoding) -> io::Result<Vec<u8>> {
+    encode(body, encoding, true)
+}
+
+fn add_vary(response: &mut warp::reply::Response) {
+    let headers = response.headers_mut();
+    let vary = match headers.get(VARY).and_then(|value| value.to_str().ok()) {
+        Some(vary) if vary.to_ascii_lowercase().contains("accept-encoding") => return,
+        Some(vary) => format!("{}, Accept-Encoding", vary),
+        None => String::from("Accept-Encoding"),
+    };
+    if let Ok(value) = HeaderValue::from_str(&vary) {
+        headers.insert(VARY, value);
+    }
+}
+
+/// The compressed body is another representation, a strong ETag of the plain one would lie.
+fn weaken_etag(response: &mut warp::reply::Response) {
+    let headers = response.headers_mut();
+    let weak = match headers.get(ETAG).and_then(|value| value.to_str().ok()) {
+        Some(etag) if !etag.starts_with("W/") => format!("W/{}", etag),
+        _ => return,
+    };
+    if let Ok(value) = HeaderValue::from_str(&weak) {
+        headers.insert(ETAG, value);
+    }
+}
+
+/// Encodes a compressible response in the best encoding the client accepts.
+pub async fn compress(reply: impl Reply, accept_encoding: Option<String>) -> warp::reply::Response {
+    let mut response = reply.into_response();
+    let compressible = response.status() != StatusCode::NO_CONTENT
+        && response.status() != StatusCode::NOT_MODIFIED
+        && !response.headers().contains_key(CONTENT_ENCODING)
+        && response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(is_compressible);
+    if !compressible {
+        return response;
+    }
+
+    add_vary(&mut response);
+    let encoding = negotiate(accept_encoding.as_deref());
+    if encoding == Encoding::Identity {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let body = match to_bytes(body).await {
+        Ok(body) => body,
+        Err(error) => {
+            error!(target: "api", "compression:compress - failed to read the body {:?}", error);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+    if body.len() < MIN_COMPRESSED_SIZE {
+        return warp::reply::Response::from_parts(parts, body.into());
+    }
+
+    match encode(&body, encoding, false) {
+        Ok(encoded) => {
+            parts.headers.remove(CONTENT_LENGTH);
+            parts.headers.insert(
+                CONTENT_ENCODING,
+                HeaderValue::from_static(encoding.to_str()),
+            );
+            let mut response = warp::reply::Response::from_parts(parts, encoded.into());
+            weaken_etag(&mut response);
+            response
+        }
+        Err(error) => {
+            error!(target: "api", "compression:compress - {:?}", error);
+            warp::reply::Response::from_parts(parts, body.into())
+        }
+    }
+}
+
+/// `.with(warp::wrap_fn(compression))` compresses every response of the wrapped routes.
+pub fn compression<F, R>(
+    filter: F,
+) -> impl Filter<Extract = (warp::reply::Response,), Error = Rejection> + Clone + Send + Sync + 'static
+where
+    F: Filter<Extract = (R,), Error = Rejection> + Clone + Send + Sync + 'static,
+    R: Reply + Send,
+{
+    warp::header::optional::<String>("accept-encoding")
+        .and(filter)
+        .then(|accept_encoding: Option<String>, reply: R| compress(reply, accept_encoding))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn test_negotiate() {
+        assert_eq!(negotiate(Some("gzip, deflate, br, zstd")), Encoding::Brotli);
+        assert_eq!(negotiate(Some("br;q=0.5, gzip")), Encoding::Gzip);
+        assert_eq!(negotiate(Some("gzip;q=0, br;q=0")), Encoding::Identity);
+        assert_eq!(negotiate(Some("*")), Encoding::Brotli);
+        assert_eq!(negotiate(Some("*, br;q=0")), Encoding::Gzip);
+        assert_eq!(negotiate(Some("deflate")), Encoding::Identity);
+        assert_eq!(negotiate(None), Encoding::Identity);
+    }
+
+    #[tokio::test]
+    async fn test_compress() {
+        let html = "<p>painting</p>".repeat(200);
+        let reply = warp::reply::with_header(warp::reply::html(html.clone()), ETAG, "\"1a\"");
+
+        let response = compress(reply, Some(String::from("gzip"))).await;
+        assert_eq!(response.headers()[CONTENT_ENCODING], "gzip");
+        assert_eq!(response.headers()[VARY], "Accept-Encoding");
+        assert_eq!(response.headers()[ETAG], "W/\"1a\"");
+        let body = to_bytes(response.into_body()).await.unwrap();
+        let mut decoded = String::new();
+        flate2::read::GzDecoder::new(&body[..])
+            .read_to_string(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, html);
+
+        let small = compress(warp::reply::json(&"small"), Some(String::from("br"))).await;
+        assert!(!small.headers().contains_key(CONTENT_ENCODING));
+        assert_eq!(small.headers()[VARY], "Accept-Encoding");
+    }
+}
@@ -1,7 +1,7 @@
 pub fn settings() -> warp::cors::Builder {
     let cors = warp::cors()
         .allow_any_origin()
-        .allow_methods(vec!["GET", "POST", "PATCH", "PUT", "DELETE"])
+        .allow_methods(vec!["GET", "HEAD", "POST", "PATCH", "PUT", "DELETE"])
         .allow_credentials(true);
     // .allow_headers("*");
     // .allow_headers(vec![
@@ -0,0 +1,265 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use uuid::Uuid;
+
+use crate::client::translations::Language;
+use crate::utils::sitemap::escape_xml;
+
+/// Painting in a feed, every text already in the language of the feed
+#[derive(Debug)]
+pub struct FeedEntry {
+    pub id: Uuid,
+    pub title: String,
+    /// absolute URL of the detail page
+    pub url: String,
+    pub summary: String,
+    /// formatted price, "12 500 Kč"
+    pub price: String,
+    /// absolute URL of the preview, sent as the enclosure
+    pub image: Option<String>,
+    pub image_alt: String,
+    pub published: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl FeedEntry {
+    fn guid(&self) -> String {
+        format!("urn:uuid:{}", self.id)
+    }
+
+    /// HTML body of the entry, escaped once here and once more by the feed.
+    fn content(&self) -> String {
+        let mut html = String::new();
+        if let Some(image) = &self.image {
+            html.push_str(&format!(
+                "<p><img src=\"{}\" alt=\"{}\"></p>",
+                escape_xml(image),
+                escape_xml(&self.image_alt)
+            ));
+        }
+        html.push_str(&format!("<p>{}</p>", escape_xml(&self.summary)));
+        html.push_str(&format!(
+            "<p><strong>{}</strong></p>",
+            escape_xml(&self.price)
+        ));
+        html
+    }
+}
+
+#[derive(Debug)]
+pub struct Feed {
+    pub title: String,
+    pub description: String,
+    pub author: String,
+    pub language: Language,
+    /// absolute URL of the gallery the feed follows
+    pub page_url: String,
+    /// absolute URL of the feed itself
+    pub self_url: String,
+    pub entries: Vec<FeedEntry>,
+}
+
+impl Feed {
+    /// Last change of any entry, the epoch for an empty feed so that the output stays stable.
+    fn updated(&self) -> DateTime<Utc> {
+        self.entries
+            .iter()
+            .map(|entry| entry.updated)
+            .max()
+            .unwrap_or(DateTime::UNIX_EPOCH)
+    }
+}
+
+fn atom_date(date: DateTime<Utc>) -> String {
+    date.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Atom 1.0 document of the feed.
+pub fn render_atom(feed: &Feed) -> String {
+    let mut xml = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<feed xmlns=\"http://www.w3.org/2005/Atom\" xml:lang=\"{}\">\n",
+        feed.language.to_str()
+    );
+    xml.push_str(&format!("\t<title>{}</title>\n", escape_xml(&feed.title)));
+    xml.push_str(&format!(
+        "\t<subtitle>{}</subtitle>\n",
+        escape_xml(&feed.description)
+    ));
+    xml.push_str(&format!("\t<id>{}</id>\n", escape_xml(&feed.self_url)));
+    xml.push_str(&format!(
+        "\t<link rel=\"self\" type=\"application/atom+xml\" href=\"{}\"/>\n",
+        escape_xml(&feed.self_url)
+    ));
+    xml.push_str(&format!(
+        "\t<link rel=\"alternate\" type=\"text/html\" href=\"{}\"/>\n",
+        escape_xml(&feed.page_url)
+    ));
+    xml.push_str(&format!(
+        "\t<updated>{}</updated>\n",
+        atom_date(feed.updated())
+    ));
+    xml.push_str(&format!(
+        "\t<author>\n\t\t<name>{}</name>\n\t</author>\n",
+        escape_xml(&feed.author)
+    ));
+
+    for entry in feed.entries.iter() {
+        xml.push_str("\t<entry>\n");
+        xml.push_str(&format!(
+            "\t\t<title>{}</title>\n",
+            escape_xml(&entry.title)
+        ));
+        xml.push_str(&format!("\t\t<id>{}</id>\n", entry.guid()));
+        xml.push_str(&format!(
+            "\t\t<link rel=\"alternate\" type=\"text/html\" href=\"{}\"/>\n",
+            escape_xml(&entry.url)
+        ));
+        if let Some(image) = &entry.image {
+            xml.push_str(&format!(
+                "\t\t<link rel=\"enclosure\" type=\"image/jpeg\" href=\"{}\"/>\n",
+                escape_xml(image)
+            ));
+        }
+        xml.push_str(&format!(
+            "\t\t<published>{}</published>\n",
+            atom_date(entry.published)
+        ));
+        xml.push_str(&format!(
+            "\t\t<updated>{}</updated>\n",
+            atom_date(entry.updated)
+        ));
+        xml.push_str(&format!(
+            "\t\t<summary>{}</summary>\n",
+            escape_xml(&entry.summary)
+        ));
+        xml.push_str(&format!(
+            "\t\t<content type=\"html\">{}</content>\n",
+            escape_xml(&entry.content())
+        ));
+        xml.push_str("\t</entry>\n");
+    }
+
+    xml.push_str("</feed>\n");
+    xml
+}
+
+/// RSS 2.0 document of the feed.
+pub fn render_rss(feed: &Feed) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\">\n\t<channel>\n",
+    );
+    xml.push_str(&format!("\t\t<title>{}</title>\n", escape_xml(&feed.title)));
+    xml.push_str(&format!(
+        "\t\t<link>{}</link>\n",
+        escape_xml(&feed.page_url)
+    ));
+    xml.push_str(&format!(
+        "\t\t<description>{}</description>\n",
+        escape_xml(&feed.description)
+    ));
+    xml.push_str(&format!(
+        "\t\t<language>{}</language>\n",
+        feed.language.to_str()
+    ));
+    xml.push_str(&format!(
+        "\t\t<lastBuildDate>{}</lastBuildDate>\n",
+        feed.updated().to_rfc2822()
+    ));
+    xml.push_str(&format!(
+        "\t\t<atom:link href=\"{}\" rel=\"self\" type=\"application/rss+xml\"/>\n",
+        escape_xml(&feed.self_url)
+    ));
+
+    for entry in feed.entries.iter() {
+        xml.push_str("\t\t<item>\n");
+        xml.push_str(&format!(
+            "\t\t\t<title>{}</title>\n",
+            escape_xml(&entry.title)
+        ));
+        xml.push_str(&format!("\t\t\t<link>{}</link>\n", escape_xml(&entry.url)));
+        xml.push_str(&format!(
+            "\t\t\t<guid isPermaLink=\"false\">{}</guid>\n",
+            entry.guid()
+        ));
+        xml.push_str(&format!(
+            "\t\t\t<pubDate>{}</pubDate>\n",
+            entry.published.to_rfc2822()
+        ));
+        xml.push_str(&format!(
+            "\t\t\t<description>{}</description>\n",
+            escape_xml(&entry.content())
+        ));
+        if let Some(image) = &entry.image {
+            xml.push_str(&format!(
+                "\t\t\t<enclosure url=\"{}\" length=\"0\" type=\"image/jpeg\"/>\n",
+                escape_xml(image)
+            ));
+        }
+        xml.push_str("\t\t</item>\n");
+    }
+
+    xml.push_str("\t</channel>\n</rss>\n");
+    xml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn feed() -> Feed {
+        let created = Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap();
+        Feed {
+            title: String::from("Rosemary - new paintings"),
+            description: String::from("The newest oil paintings"),
+            author: String::from("Michaela Halásová"),
+            language: Language::En,
+            page_url: String::from("https://example.com/en/gallery"),
+            self_url: String::from("https://example.com/en/feed.xml"),
+            entries: vec![FeedEntry {
+                id: Uuid::nil(),
+                title: String::from("Salt & pepper"),
+                url: String::from("https://example.com/en/gallery/salt-pepper"),
+                summary: String::from("Two <small> dots"),
+                price: String::from("€1,250"),
+                image: Some(String::from("https://static.example.com/a_1900.jpeg")),
+                image_alt: String::from("Painting"),
+                published: created,
+                updated: created + chrono::Duration::days(2),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_render_atom() {
+        let xml = render_atom(&feed());
+        assert!(xml.contains("<feed xmlns=\"http://www.w3.org/2005/Atom\" xml:lang=\"en\">"));
+        assert!(xml.contains("<updated>2024-03-03T12:00:00Z</updated>\n\t<author>"));
+        assert!(xml.contains("<title>Salt &amp; pepper</title>"));
+        assert!(xml.contains("<id>urn:uuid:00000000-0000-0000-0000-000000000000</id>"));
+        assert!(xml.contains(
+            "<link rel=\"enclosure\" type=\"image/jpeg\" href=\"https://static.example.com/a_1900.jpeg\"/>"
+        ));
+        assert!(xml.contains("<published>2024-03-01T12:00:00Z</published>"));
+        assert!(xml.contains("&lt;p&gt;Two &amp;lt;small&amp;gt; dots&lt;/p&gt;"));
+        assert!(xml.contains("&lt;strong&gt;€1,250&lt;/strong&gt;"));
+    }
+
+    #[test]
+    fn test_render_rss() {
+        let xml = render_rss(&feed());
+        assert!(xml.contains("<language>en</language>"));
+        assert!(xml.contains("<lastBuildDate>Sun, 3 Mar 2024 12:00:00 +0000</lastBuildDate>"));
+        assert!(xml.contains("<pubDate>Fri, 1 Mar 2024 12:00:00 +0000</pubDate>"));
+        assert!(xml.contains(
+            "<enclosure url=\"https://static.example.com/a_1900.jpeg\" length=\"0\" type=\"image/jpeg\"/>"
+        ));
+        assert_eq!(xml.matches("<item>").count(), 1);
+
+        let empty = Feed {
+            entries: Vec::new(),
+            ..feed()
+        };
+        assert!(render_rss(&empty)
+            .contains("<lastBuildDate>Thu, 1 Jan 1970 00:00:00 +0000</lastBuildDate>"));
+    }
+}
@@ -1,8 +1,11 @@
 #![allow(dead_code)]
-use std::io;
+use bytes::Buf;
+use futures_util::{Stream, TryStreamExt};
+use std::fmt::Display;
+use std::io::{self, SeekFrom};
 use tokio::fs::File;
 use tokio::fs::OpenOptions;
-use tokio::io::AsyncWriteExt;
+use tokio::io::{AsyncSeekExt, AsyncWriteExt};
 
 pub async fn write_bytes(data: &[u8], file_path: &str) -> io::Result<()> {
     let mut file = File::create(file_path).await?;
@@ -39,6 +42,114 @@ pub async fn append_bytes(data: &[u8], file_path: &str, create_file: bool) -> io
     }
 }
 
+/// Writes chunks from `stream` into a newly created file through a single file handle.
+/// Fails with `InvalidData` once more than `max_size` bytes arrive; the partial file
+/// is removed on every error.
+pub async fn write_stream<S, B, E>(stream: S, file_path: &str, max_size: u64) -> io::Result<u64>
+where
+    S: Stream<Item = Result<B, E>> + Unpin,
+    B: Buf,
+    E: Display,
+{
+    debug!(target: "app", "fs:write write_stream file {}", &file_path);
+    let mut file = File::create(file_path).await?;
+
+    let written = write_chunks(stream, &mut file, max_size).await;
+    let result = match written {
+        Ok(size) => file.flush().await.map(|_| size),
+        Err(error) => Err(error),
+    };
+
+    if let Err(error) = &result {
+        error!(target: "app", "fs:write write_stream failed {}", error);
+        drop(file);
+        if let Err(remove_error) = tokio::fs::remove_file(file_path).await {
+            error!(target: "app", "fs:write write_stream cleanup failed {}", remove_error);
+        }
+    }
+
+    result
+}
+
+/// Writes chunks from `stream` into the file from `offset` on, creating it when missing.
+/// Bytes past `offset` are dropped first, and on error the file is truncated back to
+/// `offset` so a retried chunk starts clean.
+pub async fn append_stream<S, B, E>(
+    stream: S,
+    file_path: &str,
+    offset: u64,
+    max_size: u64,
+) -> io::Result<u64>
+where
+    S: Stream<Item = Result<B, E>> + Unpin,
+    B: Buf,
+    E: Display,
+{
+    debug!(target: "app", "fs:write append_stream file {} at {}", &file_path, offset);
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(file_path)
+        .await?;
+    file.set_len(offset).await?;
+    file.seek(SeekFrom::Start(offset)).await?;
+
+    let written = write_chunks(stream, &mut file, max_size).await;
+    let result = match written {
+        Ok(size) => file.flush().await.map(|_| size),
+        Err(error) => Err(error),
+    };
+
+    if let Err(error) = &result {
+        error!(target: "app", "fs:write append_stream failed {}", error);
+        if let Err(truncate_error) = file.set_len(offset).await {
+            error!(target: "app", "fs:write append_stream truncate failed {}", truncate_error);
+        }
+    }
+
+    result
+}
+
+/// Cuts the file back to `length` bytes.
+pub async fn truncate_file(file_path: &str, length: u64) -> io::Result<()> {
+    debug!(target: "app", "fs:write truncate_file file {} to {}", &file_path, length);
+    let file = OpenOptions::new().write(true).open(file_path).await?;
+    file.set_len(length).await
+}
+
+async fn write_chunks<S, B, E>(mut stream: S, file: &mut File, max_size: u64) -> io::Result<u64>
+where
+    S: Stream<Item = Result<B, E>> + Unpin,
+    B: Buf,
+    E: Display,
+{
+    let mut size: u64 = 0;
+
+    while let Some(mut chunk) = stream
+        .try_next()
+        .await
+        .map_err(|error| io::Error::other(error.to_string()))?
+    {
+        size += chunk.remaining() as u64;
+        if size > max_size {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("file size limit of {} bytes exceeded", max_size),
+            ));
+        }
+
+        while chunk.has_remaining() {
+            let bytes = chunk.chunk();
+            let length = bytes.len();
+            file.write_all(bytes).await?;
+            chunk.advance(length);
+        }
+    }
+
+    Ok(size)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -73,4 +184,60 @@ mod tests {
         };
         assert_eq!(removed, true);
     }
+
+    #[tokio::test]
+    async fn test_write_stream_limit() {
+        let file_path = String::from("testStreamFile.txt");
+        let chunks: Vec<Result<&[u8], io::Error>> = vec![Ok(&b"Hello "[..]), Ok(&b"World!"[..])];
+
+        let size = write_stream(futures_util::stream::iter(chunks), &file_path, 12)
+            .await
+            .unwrap();
+        assert_eq!(size, 12);
+        assert_eq!(
+            fs_read::read_file_to_string(&file_path).await,
+            "Hello World!"
+        );
+
+        let chunks: Vec<Result<&[u8], io::Error>> = vec![Ok(&b"Hello "[..]), Ok(&b"World!"[..])];
+        let error = write_stream(futures_util::stream::iter(chunks), &file_path, 8)
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(!fs_read::file_exists(&file_path).await);
+    }
+
+    #[tokio::test]
+    async fn test_append_stream_rollback() {
+        let file_path = String::from("testAppendStreamFile.txt");
+
+        let chunks: Vec<Result<&[u8], io::Error>> = vec![Ok(&b"Hello "[..])];
+        let size = append_stream(futures_util::stream::iter(chunks), &file_path, 0, 6)
+            .await
+            .unwrap();
+        assert_eq!(size, 6);
+
+        let chunks: Vec<Result<&[u8], io::Error>> = vec![Ok(&b"World!"[..]), Ok(&b"!!"[..])];
+        let error = append_stream(futures_util::stream::iter(chunks), &file_path, 6, 6)
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(fs_read::get_file_size(&file_path).await, 6);
+
+        // bytes of a chunk whose offset was never stored are dropped
+        append_bytes(b"stray", &file_path, false).await.unwrap();
+        let chunks: Vec<Result<&[u8], io::Error>> = vec![Ok(&b"World!"[..])];
+        append_stream(futures_util::stream::iter(chunks), &file_path, 6, 6)
+            .await
+            .unwrap();
+        assert_eq!(
+            fs_read::read_file_to_string(&file_path).await,
+            "Hello World!"
+        );
+
+        truncate_file(&file_path, 5).await.unwrap();
+        assert_eq!(fs_read::read_file_to_string(&file_path).await, "Hello");
+
+        fs::remove_file(&file_path).await.unwrap();
+    }
 }
@@ -0,0 +1,310 @@
+use chrono::DateTime;
+use lazy_static::lazy_static;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use tokio::time::{interval, Duration};
+use warp::http::header::{HeaderMap, HeaderValue, CACHE_CONTROL, ETAG, LAST_MODIFIED};
+use warp::http::StatusCode;
+use warp::hyper::body::{to_bytes, Bytes};
+use warp::path::FullPath;
+use warp::{Filter, Rejection, Reply};
+
+use crate::database::connection::get_client;
+use crate::database::models::painting::Painting;
+use crate::errors::api_error::InternalServerError;
+use crate::utils::cache::TtlCache;
+
+/// Responses kept rendered at once
+const RENDER_CACHE_CAPACITY: usize = 512;
+/// Rendered responses are dropped after this even when nothing changed
+const RENDER_CACHE_TTL: Duration = Duration::from_secs(30 * 60);
+/// How often reservations that ran out are looked for
+const RESERVATION_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Unix milliseconds of the last change of anything the public responses show. It starts
+/// at the start of the process so that a deploy with new templates changes every ETag.
+static CONTENT_VERSION: AtomicU64 = AtomicU64::new(0);
+
+lazy_static! {
+    static ref RENDERED: TtlCache<String, CachedResponse> =
+        TtlCache::new(RENDER_CACHE_CAPACITY, RENDER_CACHE_TTL);
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CachePolicy {
+    /// pages and public API responses, revalidated on every use
+    Revalidate,
+    /// feeds and the sitemap, readers poll them on their own schedule
+    Feed,
+}
+
+impl CachePolicy {
+    fn cache_control(self) -> &'static str {
+        match self {
+            CachePolicy::Revalidate => "public, no-cache",
+            CachePolicy::Feed => "public, max-age=3600",
+        }
+    }
+}
+
+#[derive(Clone)]
+struct CachedResponse {
+    version: u64,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+impl CachedResponse {
+    fn response(self) -> warp::reply::Response {
+        let mut response = warp::reply::Response::new(self.body.into());
+        *response.headers_mut() = self.headers;
+        response
+    }
+}
+
+fn unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Failed to get current unix time")
+        .as_millis() as u64
+}
+
+pub fn content_version() -> u64 {
+    let version = CONTENT_VERSION.load(Ordering::Acquire);
+    if version != 0 {
+        return version;
+    }
+    let now = unix_millis();
+    match CONTENT_VERSION.compare_exchange(0, now, Ordering::AcqRel, Ordering::Acquire) {
+        Ok(_) => now,
+        Err(current) => current,
+    }
+}
+
+/// Called after every change of paintings, images, series, tags, rates or translations, the
+/// rendered responses and the ETags the clients hold stop matching.
+pub fn content_changed() {
+    let next = unix_millis().max(content_version() + 1);
+    CONTENT_VERSION.fetch_max(next, Ordering::AcqRel);
+}
+
+fn etag(version: u64) -> String {
+    format!("\"{:x}\"", version)
+}
+
+/// Weak comparison of an `If-None-Match` list with the current ETag.
+pub fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    if_none_match.split(',').map(str::trim).any(|candidate| {
+        candidate == "*" || candidate.strip_prefix("W/").unwrap_or(candidate) == etag
+    })
+}
+
+fn http_date(version: u64) -> String {
+    DateTime::from_timestamp_millis(version as i64)
+        .unwrap_or(DateTime::UNIX_EPOCH)
+        .format("%a, %d %b %Y %H:%M:%S GMT")
+        .to_string()
+}
+
+fn with_cache_headers(
+    mut response: warp::reply::Response,
+    policy: CachePolicy,
+    version: u64,
+) -> warp::reply::Response {
+    let headers = response.headers_mut();
+    headers.insert(
+        CACHE_CONTROL,
+        HeaderValue::from_static(policy.cache_control()),
+    );
+    if let Ok(value) = HeaderValue::from_str(&etag(version)) {
+        headers.insert(ETAG, value);
+    }
+    if let Ok(value) = HeaderValue::from_str(&http_date(version)) {
+        headers.insert(LAST_MODIFIED, value);
+    }
+    response
+}
+
+/// Pages with a guarded form carry a single-use token, browsers must not keep them.
+pub fn no_store(reply: impl Reply) -> warp::reply::Response {
+    let mut response = reply.into_response();
+    response
+        .headers_mut()
+        .insert(CACHE_CONTROL, HeaderValue::from_static("no-store"));
+    response
+}
+
+/// What a cached route needs to know about the request
+#[derive(Debug)]
+pub struct CacheRequest {
+    /// path and query of the request
+    key: String,
+    if_none_match: Option<String>,
+}
+
+pub fn cache_request() -> impl Filter<Extract = (CacheRequest,), Error = Rejection> + Clone {
+    warp::path::full()
+        .and(warp::query::raw().or(warp::any().map(String::new)).unify())
+        .and(warp::header::optional::<String>("if-none-match"))
+        .map(
+            |path: FullPath, query: String, if_none_match: Option<String>| CacheRequest {
+                key: if query.is_empty() {
+                    path.as_str().to_string()
+                } else {
+                    format!("{}?{}", path.as_str(), query)
+                },
+                if_none_match,
+            },
+        )
+}
+
+impl CacheRequest {
+    /// 304 when the client has the current version, the kept response when there is one,
+    /// otherwise `render` runs and a successful response is kept.
+    pub async fn reply<F, Fut, R>(
+        self,
+        policy: CachePolicy,
+        render: F,
+    ) -> Result<warp::reply::Response, Rejection>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<R, Rejection>>,
+        R: Reply,
+    {
+        let version = content_version();
+        if self
+            .if_none_match
+            .as_deref()
+            .is_some_and(|value| etag_matches(value, &etag(version)))
+        {
+            return Ok(with_cache_headers(
+                StatusCode::NOT_MODIFIED.into_response(),
+                policy,
+                version,
+            ));
+        }
+
+        let now = Instant::now();
+        if let Some(cached) = RENDERED
+            .get(&self.key, now)
+            .filter(|cached| cached.version == version)
+        {
+            return Ok(with_cache_headers(cached.response(), policy, version));
+        }
+
+        let response = render().await?.into_response();
+        if response.status() != StatusCode::OK {
+            return Ok(response);
+        }
+
+        let (parts, body) = response.into_parts();
+        let body = to_bytes(body).await.map_err(|error| {
+            error!(target: "api", "http_cache:reply - failed to read the body {:?}", error);
+            warp::reject::custom(InternalServerError::new())
+        })?;
+        let cached = CachedResponse {
+            version,
+            headers: parts.headers,
+            body,
+        };
+        RENDERED.insert(self.key, cached.clone(), now);
+        Ok(with_cache_headers(cached.response(), policy, version))
+    }
+}
+
+/// Reservations run out without any request changing the painting, the responses showing
+/// them as reserved are dropped once one has.
+pub fn spawn_reservation_task() {
+    tokio::spawn(async {
+        let mut ticker = interval(RESERVATION_CHECK_INTERVAL);
+        let mut checked = chrono::Utc::now();
+        loop {
+            ticker.tick().await;
+            let now = chrono::Utc::now();
+            let client = match get_client().await {
+                Ok(client) => client,
+                Err(error) => {
+                    error!(target: "app", "http_cache:reservations - {}", error);
+                    continue;
+                }
+            };
+
+            let query = Painting::count_expired_reservations_query();
+            match sqlx::query_as::<_, (i64,)>(&query)
+                .bind(checked)
+                .bind(now)
+                .fetch_one(client)
+                .await
+            {
+                Ok((count,)) => {
+                    if count > 0 {
+                        debug!(target: "app", "http_cache:reservations - {} reservations ran out", count);
+                        content_changed();
+                    }
+                    checked = now;
+                }
+                Err(error) => error!(target: "app", "http_cache:reservations - {}", error),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use warp::http::header::CONTENT_TYPE;
+
+    #[test]
+    fn test_etag_matches() {
+        let etag = etag(0x18f2a);
+        assert_eq!(etag, "\"18f2a\"");
+        assert!(etag_matches("\"18f2a\"", &etag));
+        assert!(etag_matches("\"1\", W/\"18f2a\"", &etag));
+        assert!(etag_matches("*", &etag));
+        assert!(!etag_matches("\"18f2b\"", &etag));
+        assert_eq!(http_date(0), "Thu, 01 Jan 1970 00:00:00 GMT");
+    }
+
+    #[tokio::test]
+    async fn test_reply() {
+        let request = |if_none_match: Option<String>| CacheRequest {
+            key: String::from("/en/test-reply"),
+            if_none_match,
+        };
+        let render = || async { Ok::<_, Rejection>(warp::reply::html("<p>page</p>")) };
+
+        let first = request(None)
+            .reply(CachePolicy::Revalidate, render)
+            .await
+            .unwrap();
+        assert_eq!(first.status(), StatusCode::OK);
+        assert_eq!(first.headers()[CACHE_CONTROL], "public, no-cache");
+        let etag = first.headers()[ETAG].to_str().unwrap().to_string();
+
+        let not_modified = request(Some(etag.clone()))
+            .reply(CachePolicy::Revalidate, || async {
+                Err::<warp::reply::Html<&str>, _>(warp::reject::not_found())
+            })
+            .await
+            .unwrap();
+        assert_eq!(not_modified.status(), StatusCode::NOT_MODIFIED);
+
+        let cached = request(None)
+            .reply(CachePolicy::Revalidate, || async {
+                Err::<warp::reply::Html<&str>, _>(warp::reject::not_found())
+            })
+            .await
+            .unwrap();
+        assert_eq!(cached.headers()[CONTENT_TYPE], "text/html; charset=utf-8");
+        assert_eq!(to_bytes(cached.into_body()).await.unwrap(), "<p>page</p>");
+
+        content_changed();
+        let changed = request(Some(etag.clone()))
+            .reply(CachePolicy::Revalidate, render)
+            .await
+            .unwrap();
+        assert_eq!(changed.status(), StatusCode::OK);
+        assert_ne!(changed.headers()[ETAG], etag.as_str());
+    }
+}
@@ -1 +1,2 @@
+pub mod palette;
 pub mod resize_to_max;
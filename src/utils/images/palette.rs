@@ -0,0 +1,200 @@
+use image::{DynamicImage, RgbImage};
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::task;
+use uuid::Uuid;
+
+use crate::config::load::{get, ConfigField};
+use crate::database::connection::get_client;
+use crate::database::models::image::PaintingImage;
+
+/// Colours kept per image
+pub const PALETTE_SIZE: usize = 5;
+/// Side of the thumbnail the colours are counted on
+const SAMPLE_SIZE: u32 = 64;
+/// Low bits dropped from every channel so that close shades count as one colour
+const CHANNEL_SHIFT: u8 = 4;
+/// Distance between black and white
+const MAX_DISTANCE: f32 = 441.67294;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PaletteColor {
+    pub rgb: [u8; 3],
+    /// part of the image covered by the colour, the shares of a palette add up to at most 1
+    pub share: f32,
+}
+
+impl PaletteColor {
+    fn distance(&self, other: &PaletteColor) -> f32 {
+        self.rgb
+            .iter()
+            .zip(other.rgb.iter())
+            .map(|(a, b)| (f32::from(*a) - f32::from(*b)).powi(2))
+            .sum::<f32>()
+            .sqrt()
+    }
+}
+
+/// Most common colours of the pixels, each one the average of the shades grouped into it.
+fn palette_of_pixels(pixels: &RgbImage) -> Vec<PaletteColor> {
+    let mut buckets: HashMap<[u8; 3], (u32, [u32; 3])> = HashMap::new();
+    for pixel in pixels.pixels() {
+        let key = pixel.0.map(|channel| channel >> CHANNEL_SHIFT);
+        let (count, sums) = buckets.entry(key).or_insert((0, [0; 3]));
+        *count += 1;
+        for (sum, channel) in sums.iter_mut().zip(pixel.0) {
+            *sum += u32::from(channel);
+        }
+    }
+
+    let total = pixels.pixels().len().max(1) as f32;
+    let mut colors: Vec<(u32, [u32; 3])> = buckets.into_values().collect();
+    colors.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    colors
+        .into_iter()
+        .take(PALETTE_SIZE)
+        .map(|(count, sums)| PaletteColor {
+            rgb: sums.map(|sum| (sum / count) as u8),
+            share: count as f32 / total,
+        })
+        .collect()
+}
+
+pub fn extract_palette(image: &DynamicImage) -> Vec<PaletteColor> {
+    palette_of_pixels(&image.thumbnail(SAMPLE_SIZE, SAMPLE_SIZE).to_rgb8())
+}
+
+/// Share weighted distance from every colour of `from` to the closest colour of `to`.
+fn palette_distance(from: &[PaletteColor], to: &[PaletteColor]) -> f32 {
+    let weight: f32 = from.iter().map(|color| color.share).sum();
+    if weight <= 0.0 {
+        return MAX_DISTANCE;
+    }
+
+    from.iter()
+        .map(|color| {
+            let closest = to
+                .iter()
+                .map(|other| color.distance(other))
+                .fold(MAX_DISTANCE, f32::min);
+            closest * color.share
+        })
+        .sum::<f32>()
+        / weight
+}
+
+/// 1 for palettes of the same colours down to 0 for black against white.
+pub fn palette_similarity(a: &[PaletteColor], b: &[PaletteColor]) -> f32 {
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let distance = (palette_distance(a, b) + palette_distance(b, a)) / 2.0;
+    (1.0 - distance / MAX_DISTANCE).clamp(0.0, 1.0)
+}
+
+/// Palette of an image file, `None` when it can not be decoded.
+pub async fn palette_of_file(path: PathBuf) -> Option<Vec<PaletteColor>> {
+    let opened =
+        task::spawn_blocking(move || image::open(path).map(|image| extract_palette(&image))).await;
+
+    match opened {
+        Ok(Ok(palette)) => Some(palette),
+        Ok(Err(error)) => {
+            error!(target: "app", "images:palette - failed to open image {}", error);
+            None
+        }
+        Err(join_error) => {
+            error!(target: "app", "images:palette - failed to read palette {}", join_error);
+            None
+        }
+    }
+}
+
+/// Stores the palette of the image, read from `file` relative to the static file dir.
+pub async fn update_palette(
+    id: Uuid,
+    static_file_dir: &str,
+    file: &str,
+) -> Result<(), sqlx::Error> {
+    let Some(palette) = palette_of_file(Path::new(static_file_dir).join(file)).await else {
+        return Ok(());
+    };
+
+    let client = get_client().await?;
+    let query = PaintingImage::update_palette_query();
+    debug!(target: "db", "images:palette - PaintingImage::update_palette_query {}", &query);
+    sqlx::query(&query)
+        .bind(id)
+        .bind(sqlx::types::Json(palette))
+        .execute(client)
+        .await?;
+    Ok(())
+}
+
+/// Reads palettes of images that were processed before palettes existed.
+pub async fn init_palettes() -> Result<(), sqlx::Error> {
+    let static_file_dir = match get::<String>(ConfigField::StaticFilesDir).await {
+        Ok(value) => value,
+        Err(error) => {
+            error!(target: "app", "images:palette - failed to get static file dir {}", error);
+            return Ok(());
+        }
+    };
+
+    let client = get_client().await?;
+    let query = PaintingImage::get_missing_palette_query();
+    debug!(target: "db", "images:palette - PaintingImage::get_missing_palette_query {}", &query);
+    let rows = sqlx::query(&query).fetch_all(client).await?;
+
+    for row in rows.iter() {
+        let id: Uuid = row.try_get("id")?;
+        let file: String = row.try_get("file")?;
+        update_palette(id, &static_file_dir, &file).await?;
+    }
+
+    debug!(target: "app", "images:palette - checked palettes of {} images", rows.len());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgb;
+
+    fn color(rgb: [u8; 3], share: f32) -> PaletteColor {
+        PaletteColor { rgb, share }
+    }
+
+    #[test]
+    fn test_palette_of_pixels() {
+        let pixels = RgbImage::from_fn(10, 10, |x, _| {
+            if x < 7 {
+                Rgb([200, 30, 30])
+            } else {
+                Rgb([20, 20, 200])
+            }
+        });
+
+        let palette = palette_of_pixels(&pixels);
+        assert_eq!(palette.len(), 2);
+        assert_eq!(palette[0], color([200, 30, 30], 0.7));
+        assert_eq!(palette[1].rgb, [20, 20, 200]);
+    }
+
+    #[test]
+    fn test_palette_similarity() {
+        let red = vec![color([200, 30, 30], 0.8), color([240, 240, 240], 0.2)];
+        let dark_red = vec![color([180, 20, 20], 0.9), color([230, 230, 230], 0.1)];
+        let blue = vec![color([20, 20, 200], 1.0)];
+
+        assert_eq!(palette_similarity(&red, &red), 1.0);
+        assert!(palette_similarity(&red, &dark_red) > palette_similarity(&red, &blue));
+        assert_eq!(
+            palette_similarity(&red, &blue),
+            palette_similarity(&blue, &red)
+        );
+        assert_eq!(palette_similarity(&red, &[]), 0.0);
+    }
+}
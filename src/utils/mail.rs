@@ -0,0 +1,98 @@
+pub mod file_mailer;
+pub mod smtp_mailer;
+
+use lazy_static::lazy_static;
+use lettre::message::header::ContentType;
+use lettre::Message;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::sync::OnceCell;
+
+use crate::config::load::{get, ConfigField};
+use crate::utils::mail::file_mailer::FileMailer;
+use crate::utils::mail::smtp_mailer::SmtpMailer;
+
+lazy_static! {
+    pub static ref MAILER: OnceCell<Arc<dyn Mailer>> = OnceCell::new();
+}
+
+#[derive(Debug, Clone)]
+pub struct Mail {
+    pub to: String,
+    pub reply_to: Option<String>,
+    pub subject: String,
+    pub body: String,
+}
+
+#[derive(Debug)]
+pub struct MailError(pub String);
+
+impl fmt::Display for MailError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "mail delivery failed - {}", self.0)
+    }
+}
+
+impl std::error::Error for MailError {}
+
+pub type MailFuture<'a> = Pin<Box<dyn Future<Output = Result<(), MailError>> + Send + 'a>>;
+
+/// Delivery backend for outgoing notification emails.
+pub trait Mailer: Send + Sync {
+    fn send<'a>(&'a self, mail: &'a Mail) -> MailFuture<'a>;
+}
+
+pub fn build_message(from: &str, mail: &Mail) -> Result<Message, MailError> {
+    let parse_error = |error: lettre::address::AddressError| MailError(error.to_string());
+
+    let mut builder = Message::builder()
+        .from(from.parse().map_err(parse_error)?)
+        .to(mail.to.parse().map_err(parse_error)?)
+        .subject(mail.subject.clone())
+        .header(ContentType::TEXT_PLAIN);
+
+    if let Some(reply_to) = &mail.reply_to {
+        builder = builder.reply_to(reply_to.parse().map_err(parse_error)?);
+    }
+
+    builder
+        .body(mail.body.clone())
+        .map_err(|error| MailError(error.to_string()))
+}
+
+pub async fn init_mailer() -> Result<(), std::io::Error> {
+    let transport = get::<String>(ConfigField::MailTransport).await?;
+    let from = get::<String>(ConfigField::MailFrom).await?;
+
+    let mailer: Arc<dyn Mailer> = match transport.as_str() {
+        "smtp" => {
+            let host = get::<String>(ConfigField::SmtpHost).await?;
+            let port = get::<u16>(ConfigField::SmtpPort).await?;
+            let username = get::<String>(ConfigField::SmtpUsername).await?;
+            let password = get::<String>(ConfigField::SmtpPassword).await?;
+            let tls = get::<bool>(ConfigField::SmtpTls).await?;
+            let mailer = SmtpMailer::new(&host, port, &username, &password, tls, &from)
+                .map_err(|error| std::io::Error::other(error.to_string()))?;
+            debug!(target: "mail", "smtp mailer created for {}:{}", &host, port);
+            Arc::new(mailer)
+        }
+        _ => {
+            let dir = get::<String>(ConfigField::MailFileDir).await?;
+            debug!(target: "mail", "file mailer created in {}", &dir);
+            Arc::new(FileMailer::new(&dir, &from))
+        }
+    };
+
+    MAILER
+        .set(mailer)
+        .map_err(|_| std::io::Error::other("Mailer is already initialized"))
+}
+
+pub async fn get_mailer() -> Result<Arc<dyn Mailer>, std::io::Error> {
+    MAILER
+        .get()
+        .cloned()
+        .ok_or_else(|| std::io::Error::other("Mailer does not exist"))
+}
@@ -0,0 +1,75 @@
+use chrono::Utc;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+use crate::utils::file_system::fs_write::write_bytes;
+use crate::utils::mail::{build_message, Mail, MailError, MailFuture, Mailer};
+
+/// Writes every mail as an `.eml` file, meant for development and for
+/// deployments without an SMTP relay.
+pub struct FileMailer {
+    dir: PathBuf,
+    from: String,
+}
+
+impl FileMailer {
+    pub fn new(dir: &str, from: &str) -> Self {
+        Self {
+            dir: PathBuf::from(dir),
+            from: from.to_string(),
+        }
+    }
+
+    async fn write(&self, mail: &Mail) -> Result<(), MailError> {
+        let message = build_message(&self.from, mail)?;
+
+        tokio::fs::create_dir_all(&self.dir)
+            .await
+            .map_err(|error| MailError(error.to_string()))?;
+
+        let file_path = self.dir.join(format!(
+            "{}_{}.eml",
+            Utc::now().format("%Y%m%dT%H%M%S"),
+            Uuid::new_v4()
+        ));
+        debug!(target: "mail", "file_mailer - writing {:?}", &file_path);
+
+        write_bytes(&message.formatted(), &file_path.to_string_lossy())
+            .await
+            .map_err(|error| MailError(error.to_string()))
+    }
+}
+
+impl Mailer for FileMailer {
+    fn send<'a>(&'a self, mail: &'a Mail) -> MailFuture<'a> {
+        Box::pin(self.write(mail))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_file_mailer() {
+        let dir = std::env::temp_dir().join(format!("rosemary_mail_{}", Uuid::new_v4()));
+        let mailer = FileMailer::new(&dir.to_string_lossy(), "noreply@rosemary-artist.com");
+        let mail = Mail {
+            to: String::from("artist@rosemary-artist.com"),
+            reply_to: Some(String::from("visitor@example.com")),
+            subject: String::from("New message"),
+            body: String::from("Hello from the contact form"),
+        };
+
+        mailer.send(&mail).await.unwrap();
+
+        let mut entries = tokio::fs::read_dir(&dir).await.unwrap();
+        let entry = entries.next_entry().await.unwrap().unwrap();
+        let content = tokio::fs::read_to_string(entry.path()).await.unwrap();
+        assert!(content.contains("Subject: New message"));
+        assert!(content.contains("Reply-To: visitor@example.com"));
+        assert!(content.contains("Hello from the contact form"));
+
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+}
@@ -0,0 +1,134 @@
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Tokio1Executor};
+
+use crate::utils::mail::{build_message, Mail, MailError, MailFuture, Mailer};
+
+pub struct SmtpMailer {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: String,
+}
+
+impl SmtpMailer {
+    pub fn new(
+        host: &str,
+        port: u16,
+        username: &str,
+        password: &str,
+        tls: bool,
+        from: &str,
+    ) -> Result<Self, MailError> {
+        let mut builder = if tls {
+            AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host)
+                .map_err(|error| MailError(error.to_string()))?
+        } else {
+            AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(host)
+        };
+        builder = builder.port(port);
+
+        if !username.is_empty() {
+            builder =
+                builder.credentials(Credentials::new(username.to_string(), password.to_string()));
+        }
+
+        Ok(Self {
+            transport: builder.build(),
+            from: from.to_string(),
+        })
+    }
+
+    async fn deliver(&self, mail: &Mail) -> Result<(), MailError> {
+        let message = build_message(&self.from, mail)?;
+        self.transport
+            .send(message)
+            .await
+            .map(|response| {
+                debug!(target: "mail", "smtp_mailer - relay response {:?}", response.code());
+            })
+            .map_err(|error| MailError(error.to_string()))
+    }
+}
+
+impl Mailer for SmtpMailer {
+    fn send<'a>(&'a self, mail: &'a Mail) -> MailFuture<'a> {
+        Box::pin(self.deliver(mail))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+    use tokio::sync::oneshot;
+
+    /// Minimal SMTP stand-in accepting one message and handing its DATA back.
+    async fn smtp_stand_in(listener: TcpListener, sender: oneshot::Sender<String>) {
+        let (socket, _) = listener.accept().await.unwrap();
+        let (reader, mut writer) = socket.into_split();
+        let mut lines = BufReader::new(reader).lines();
+        let mut data = String::new();
+        let mut in_data = false;
+
+        writer.write_all(b"220 localhost ESMTP\r\n").await.unwrap();
+        while let Some(line) = lines.next_line().await.unwrap() {
+            if in_data {
+                if line == "." {
+                    in_data = false;
+                    writer.write_all(b"250 queued\r\n").await.unwrap();
+                } else {
+                    data.push_str(&line);
+                    data.push('\n');
+                }
+                continue;
+            }
+
+            let command = line.to_uppercase();
+            let reply: &[u8] = if command.starts_with("EHLO") || command.starts_with("HELO") {
+                b"250 localhost\r\n"
+            } else if command.starts_with("DATA") {
+                in_data = true;
+                b"354 end data with <CR><LF>.<CR><LF>\r\n"
+            } else if command.starts_with("QUIT") {
+                writer.write_all(b"221 bye\r\n").await.unwrap();
+                break;
+            } else {
+                b"250 OK\r\n"
+            };
+            writer.write_all(reply).await.unwrap();
+        }
+
+        let _ = sender.send(data);
+    }
+
+    #[tokio::test]
+    async fn test_smtp_mailer() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (sender, receiver) = oneshot::channel();
+        tokio::spawn(smtp_stand_in(listener, sender));
+
+        let mailer = SmtpMailer::new(
+            "127.0.0.1",
+            port,
+            "",
+            "",
+            false,
+            "noreply@rosemary-artist.com",
+        )
+        .unwrap();
+        let mail = Mail {
+            to: String::from("artist@rosemary-artist.com"),
+            reply_to: None,
+            subject: String::from("New message"),
+            body: String::from("Hello over SMTP"),
+        };
+
+        mailer.send(&mail).await.unwrap();
+        drop(mailer);
+
+        let data = receiver.await.unwrap();
+        assert!(data.contains("Subject: New message"));
+        assert!(data.contains("To: artist@rosemary-artist.com"));
+        assert!(data.contains("Hello over SMTP"));
+    }
+}
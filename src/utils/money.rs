@@ -0,0 +1,141 @@
+pub mod cnb;
+
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use crate::client::formatting::format_money;
+use crate::client::translations::Language;
+use crate::config::load::{get_sync, ConfigField};
+use crate::database::connection::get_client;
+use crate::database::models::exchange_rate::ExchangeRate;
+use crate::utils::file_system::fs_read::{file_exists, read_file_to_string};
+use crate::utils::http_cache::content_changed;
+
+/// Currency every rate is quoted against
+pub const BASE_CURRENCY: &str = "CZK";
+
+lazy_static! {
+    /// CZK for one unit of each currency, templates read it without touching the database
+    static ref RATES: RwLock<HashMap<String, f64>> = RwLock::new(HashMap::new());
+}
+
+pub fn is_currency_code(code: &str) -> bool {
+    code.len() == 3 && code.chars().all(|c| c.is_ascii_uppercase())
+}
+
+pub fn set_rates(rates: &[ExchangeRate]) {
+    let mut cache = RATES.write().unwrap_or_else(|error| error.into_inner());
+    cache.clear();
+    for rate in rates {
+        cache.insert(rate.currency.clone(), rate.unit_rate());
+    }
+}
+
+/// Reloads the rate cache from the database.
+pub async fn load_rates() -> Result<usize, sqlx::Error> {
+    let client = get_client().await?;
+    let query = ExchangeRate::get_all_query();
+    debug!(target: "db", "money:load_rates - ExchangeRate::get_all_query {}", &query);
+    let rates = sqlx::query_as::<_, ExchangeRate>(&query)
+        .fetch_all(client)
+        .await?;
+    set_rates(&rates);
+    content_changed();
+    Ok(rates.len())
+}
+
+/// Stores parsed rates and refreshes the cache.
+pub async fn store_rates(rates: &[cnb::CnbRate]) -> Result<Vec<ExchangeRate>, sqlx::Error> {
+    let client = get_client().await?;
+    let query = ExchangeRate::upsert_query();
+    let mut transaction = client.begin().await?;
+    let mut stored = Vec::with_capacity(rates.len());
+
+    for rate in rates {
+        let row = sqlx::query_as::<_, ExchangeRate>(&query)
+            .bind(&rate.currency)
+            .bind(rate.amount)
+            .bind(rate.rate)
+            .fetch_one(&mut *transaction)
+            .await?;
+        stored.push(row);
+    }
+
+    transaction.commit().await?;
+    load_rates().await?;
+    Ok(stored)
+}
+
+/// Imports the CNB file from config when there is one, then fills the cache.
+pub async fn init_rates() -> Result<(), Box<dyn std::error::Error>> {
+    let file_path = get_sync::<String>(ConfigField::ExchangeRatesFile)?;
+
+    if !file_path.is_empty() {
+        if file_exists(&file_path).await {
+            let rates = cnb::parse(&read_file_to_string(&file_path).await)?;
+            let stored = store_rates(&rates).await?;
+            debug!(target: "app", "money:init - imported {} rates from {}", stored.len(), file_path);
+        } else {
+            error!(target: "app", "money:init - rates file {} does not exist", file_path);
+        }
+    }
+
+    let count = load_rates().await?;
+    debug!(target: "app", "money:init - {} exchange rates loaded", count);
+    Ok(())
+}
+
+fn unit_rate(currency: &str) -> Option<f64> {
+    if currency == BASE_CURRENCY {
+        return Some(1.0);
+    }
+    let cache = RATES.read().unwrap_or_else(|error| error.into_inner());
+    cache.get(currency).copied()
+}
+
+pub fn convert(amount: i64, from: &str, to: &str) -> Option<f64> {
+    if from == to {
+        return Some(amount as f64);
+    }
+    Some(amount as f64 * unit_rate(from)? / unit_rate(to)?)
+}
+
+pub fn display_currency(language: Language) -> String {
+    let field = if language == Language::Cs {
+        ConfigField::DisplayCurrencyCs
+    } else {
+        ConfigField::DisplayCurrencyEn
+    };
+    get_sync::<String>(field).unwrap_or_else(|_| String::from(BASE_CURRENCY))
+}
+
+/// Price in the display currency of the language, falls back to the stored
+/// currency when there is no rate to convert with.
+pub fn display_price(amount: i64, currency: &str, language: Language) -> String {
+    let target = display_currency(language);
+    match convert(amount, currency, &target) {
+        Some(value) => format_money(value.round() as i64, &target, language),
+        None => format_money(amount, currency, language),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert() {
+        set_rates(&[ExchangeRate {
+            currency: String::from("HUF"),
+            amount: 100,
+            rate: 6.5,
+            updated: chrono::Utc::now(),
+        }]);
+
+        assert_eq!(convert(1000, "CZK", "CZK"), Some(1000.0));
+        assert_eq!(convert(1000, "HUF", "CZK"), Some(65.0));
+        assert_eq!(convert(65, "CZK", "HUF").map(f64::round), Some(1000.0));
+        assert_eq!(convert(65, "CZK", "XXX"), None);
+    }
+}
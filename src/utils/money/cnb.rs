@@ -0,0 +1,105 @@
+use std::fmt;
+
+use crate::utils::money::is_currency_code;
+
+/// One line of the CNB daily rates file, `country|currency|amount|code|rate`
+#[derive(Debug, Clone, PartialEq)]
+pub struct CnbRate {
+    pub currency: String,
+    pub amount: i32,
+    pub rate: f64,
+}
+
+#[derive(Debug)]
+pub struct CnbError(pub String);
+
+impl fmt::Display for CnbError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid CNB rates file - {}", self.0)
+    }
+}
+
+impl std::error::Error for CnbError {}
+
+/// Parses the text published at cnb.cz, the first line carries the date and
+/// the second one the column names.
+pub fn parse(text: &str) -> Result<Vec<CnbRate>, CnbError> {
+    let mut rates = Vec::new();
+
+    for (index, line) in text.lines().enumerate().skip(2) {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let columns: Vec<&str> = line.split('|').collect();
+        if columns.len() != 5 {
+            return Err(CnbError(format!(
+                "line {} has {} columns",
+                index + 1,
+                columns.len()
+            )));
+        }
+
+        let currency = columns[3].trim().to_uppercase();
+        if !is_currency_code(&currency) {
+            return Err(CnbError(format!(
+                "line {} has invalid code {}",
+                index + 1,
+                columns[3]
+            )));
+        }
+
+        let amount = columns[2]
+            .trim()
+            .parse::<i32>()
+            .ok()
+            .filter(|amount| *amount > 0)
+            .ok_or_else(|| CnbError(format!("line {} has invalid amount", index + 1)))?;
+
+        let rate = columns[4]
+            .trim()
+            .replace(',', ".")
+            .parse::<f64>()
+            .ok()
+            .filter(|rate| rate.is_finite() && *rate > 0.0)
+            .ok_or_else(|| CnbError(format!("line {} has invalid rate", index + 1)))?;
+
+        rates.push(CnbRate {
+            currency,
+            amount,
+            rate,
+        });
+    }
+
+    if rates.is_empty() {
+        return Err(CnbError(String::from("no rates found")));
+    }
+
+    Ok(rates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let text = "17.10.2026 #201\nzemě|měna|množství|kód|kurz\nEMU|euro|1|EUR|24,315\nMaďarsko|forint|100|HUF|6,512\n";
+        let rates = parse(text).unwrap();
+
+        assert_eq!(rates.len(), 2);
+        assert_eq!(
+            rates[0],
+            CnbRate {
+                currency: String::from("EUR"),
+                amount: 1,
+                rate: 24.315
+            }
+        );
+        assert_eq!(rates[1].amount, 100);
+
+        assert!(parse("17.10.2026 #201\nzemě|měna|množství|kód|kurz\n").is_err());
+        assert!(parse("17.10.2026 #201\nhead\nEMU|euro|1|EURO|24,315").is_err());
+    }
+}
@@ -0,0 +1,139 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+
+use crate::client::routes::Route;
+use crate::client::translations::{languages, Language};
+
+/// Page of the sitemap in every language it exists in
+#[derive(Debug)]
+pub struct SitemapPage {
+    /// absolute URL by language
+    pub urls: Vec<(Language, String)>,
+    pub lastmod: Option<DateTime<Utc>>,
+    /// absolute URLs of the photos on the page
+    pub images: Vec<String>,
+}
+
+impl SitemapPage {
+    /// `route` in every one of `languages()`.
+    pub fn new(route: &Route, site_url: &str) -> Self {
+        Self {
+            urls: languages()
+                .iter()
+                .map(|language| (*language, route.url(site_url, *language)))
+                .collect(),
+            lastmod: None,
+            images: Vec::new(),
+        }
+    }
+}
+
+pub fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Sitemap with one `<url>` per page and language, each linking its other languages
+/// and the first language as `x-default`.
+pub fn render_sitemap(pages: &[SitemapPage]) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\" xmlns:xhtml=\"http://www.w3.org/1999/xhtml\" xmlns:image=\"http://www.google.com/schemas/sitemap-image/1.1\">\n",
+    );
+
+    for page in pages {
+        let mut alternates = String::new();
+        for (language, url) in page.urls.iter() {
+            alternates.push_str(&format!(
+                "\t\t<xhtml:link rel=\"alternate\" hreflang=\"{}\" href=\"{}\"/>\n",
+                language.to_str(),
+                escape_xml(url)
+            ));
+        }
+        if let Some((_, url)) = page.urls.first() {
+            alternates.push_str(&format!(
+                "\t\t<xhtml:link rel=\"alternate\" hreflang=\"x-default\" href=\"{}\"/>\n",
+                escape_xml(url)
+            ));
+        }
+
+        for (_, url) in page.urls.iter() {
+            xml.push_str(&format!("\t<url>\n\t\t<loc>{}</loc>\n", escape_xml(url)));
+            if let Some(lastmod) = page.lastmod {
+                xml.push_str(&format!(
+                    "\t\t<lastmod>{}</lastmod>\n",
+                    lastmod.to_rfc3339_opts(SecondsFormat::Secs, true)
+                ));
+            }
+            xml.push_str(&alternates);
+            for image in page.images.iter() {
+                xml.push_str(&format!(
+                    "\t\t<image:image>\n\t\t\t<image:loc>{}</image:loc>\n\t\t</image:image>\n",
+                    escape_xml(image)
+                ));
+            }
+            xml.push_str("\t</url>\n");
+        }
+    }
+
+    xml.push_str("</urlset>\n");
+    xml
+}
+
+/// robots.txt for all crawlers, `index` off keeps them out of the whole site.
+pub fn render_robots(index: bool, disallow: &str, sitemap_url: &str) -> String {
+    let mut robots = String::from("User-agent: *\n");
+    if index {
+        for path in disallow
+            .split(',')
+            .map(str::trim)
+            .filter(|path| !path.is_empty())
+        {
+            robots.push_str(&format!("Disallow: {}\n", path));
+        }
+        robots.push_str(&format!("\nSitemap: {}\n", sitemap_url));
+    } else {
+        robots.push_str("Disallow: /\n");
+    }
+    robots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_render_sitemap() {
+        let mut page = SitemapPage::new(
+            &Route::Painting(String::from("zima")),
+            "https://example.com",
+        );
+        page.lastmod = Some(Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap());
+        page.images = vec![String::from("https://static.example.com/a.jpeg?v=1&w=2")];
+
+        let xml = render_sitemap(&[page]);
+        assert_eq!(xml.matches("<url>").count(), languages().len());
+        assert!(xml.contains("<loc>https://example.com/en/gallery/zima</loc>"));
+        assert!(xml.contains("<lastmod>2024-03-01T12:00:00Z</lastmod>"));
+        assert!(xml.contains("hreflang=\"x-default\" href=\"https://example.com/cs/gallery/zima\""));
+        assert!(
+            xml.contains("<image:loc>https://static.example.com/a.jpeg?v=1&amp;w=2</image:loc>")
+        );
+    }
+
+    #[test]
+    fn test_render_robots() {
+        let robots = render_robots(true, "/api/, /admin/", "https://example.com/sitemap.xml");
+        assert_eq!(
+            robots,
+            "User-agent: *\nDisallow: /api/\nDisallow: /admin/\n\nSitemap: https://example.com/sitemap.xml\n"
+        );
+        assert_eq!(
+            render_robots(false, "/api/", "https://example.com/sitemap.xml"),
+            "User-agent: *\nDisallow: /\n"
+        );
+    }
+}
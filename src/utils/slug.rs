@@ -0,0 +1,239 @@
+use sqlx::types::JsonValue;
+use sqlx::{Acquire, Postgres, Row, Transaction};
+use uuid::Uuid;
+
+use crate::client::translations::Language;
+use crate::database::connection::get_client;
+use crate::database::models::generics::Translation;
+use crate::database::models::painting_slug::PaintingSlug;
+
+/// Longest slug `slugify` produces, counted before the uniqueness suffix
+const MAX_SLUG_LENGTH: usize = 80;
+
+/// Suffixes tried when concurrent saves of the same title keep taking the free one
+const MAX_SLUG_ATTEMPTS: usize = 5;
+
+/// Slug used when nothing is left of the title
+const FALLBACK_SLUG: &str = "painting";
+
+/// Lowercase ASCII words joined by single dashes, safe to put into a query or a path.
+pub fn is_slug(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= 120
+        && value.split('-').all(|word| {
+            !word.is_empty()
+                && word
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        })
+}
+
+/// ASCII spelling of Czech and other central European letters.
+fn transliterate(c: char) -> Option<&'static str> {
+    let ascii = match c {
+        'á' | 'ä' | 'à' | 'â' | 'ą' => "a",
+        'č' | 'ć' | 'ç' => "c",
+        'ď' => "d",
+        'é' | 'ě' | 'ë' | 'è' | 'ê' | 'ę' => "e",
+        'í' | 'ï' | 'ì' | 'î' => "i",
+        'ľ' | 'ĺ' | 'ł' => "l",
+        'ň' | 'ń' | 'ñ' => "n",
+        'ó' | 'ô' | 'ò' | 'õ' | 'ö' | 'ő' | 'ø' => "o",
+        'ř' | 'ŕ' => "r",
+        'š' | 'ś' => "s",
+        'ß' => "ss",
+        'ť' => "t",
+        'ú' | 'ů' | 'ù' | 'û' | 'ü' | 'ű' => "u",
+        'ý' | 'ÿ' => "y",
+        'ž' | 'ź' | 'ż' => "z",
+        'æ' => "ae",
+        'œ' => "oe",
+        _ => return None,
+    };
+    Some(ascii)
+}
+
+/// URL slug of a title, "Zimní ráno č. 2" becomes "zimni-rano-c-2".
+pub fn slugify(title: &str) -> String {
+    let mut slug = String::with_capacity(title.len());
+
+    for c in title.chars().flat_map(char::to_lowercase) {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+        } else if let Some(ascii) = transliterate(c) {
+            slug.push_str(ascii);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+
+    if slug.len() > MAX_SLUG_LENGTH {
+        let cut = slug[..MAX_SLUG_LENGTH]
+            .rfind('-')
+            .unwrap_or(MAX_SLUG_LENGTH);
+        slug.truncate(cut);
+    }
+
+    let slug = slug.trim_matches('-');
+    if slug.is_empty() {
+        String::from(FALLBACK_SLUG)
+    } else {
+        slug.to_string()
+    }
+}
+
+/// First of `base`, `base-2`, `base-3`... not in `taken`.
+pub fn unique_slug(base: &str, taken: &[String]) -> String {
+    if !taken.iter().any(|slug| slug == base) {
+        return base.to_string();
+    }
+
+    (2..)
+        .map(|suffix| format!("{}-{}", base, suffix))
+        .find(|candidate| !taken.contains(candidate))
+        .expect("Ran out of slug suffixes")
+}
+
+/// Makes `slug` the current one of the painting in a savepoint, `false` when a concurrent
+/// save claimed it after `get_taken_query` had run.
+async fn claim_slug(
+    transaction: &mut Transaction<'_, Postgres>,
+    painting_id: Uuid,
+    language: Language,
+    slug: &str,
+) -> Result<bool, sqlx::Error> {
+    let mut savepoint = Acquire::begin(&mut *transaction).await?;
+
+    let claimed = async {
+        sqlx::query(&PaintingSlug::retire_query())
+            .bind(painting_id)
+            .bind(language.to_str())
+            .bind(slug)
+            .execute(&mut *savepoint)
+            .await?;
+
+        sqlx::query_scalar::<_, String>(&PaintingSlug::upsert_query())
+            .bind(painting_id)
+            .bind(language.to_str())
+            .bind(slug)
+            .fetch_optional(&mut *savepoint)
+            .await
+    }
+    .await;
+
+    match claimed {
+        Ok(Some(_)) => {
+            savepoint.commit().await?;
+            Ok(true)
+        }
+        Ok(None) => {
+            savepoint.rollback().await?;
+            Ok(false)
+        }
+        Err(error)
+            if error
+                .as_database_error()
+                .is_some_and(|error| error.is_unique_violation()) =>
+        {
+            savepoint.rollback().await?;
+            Ok(false)
+        }
+        Err(error) => Err(error),
+    }
+}
+
+/// Gives the painting a current slug from its title in every language, the
+/// previous slug stays behind so old links can be redirected.
+pub async fn assign_slugs(
+    transaction: &mut Transaction<'_, Postgres>,
+    painting_id: Uuid,
+    title: &Translation,
+) -> Result<(), sqlx::Error> {
+    'languages: for (language, value) in [(Language::Cs, &title.cs), (Language::En, &title.en)] {
+        let base = slugify(value);
+        let mut lost: Vec<String> = Vec::new();
+
+        for _ in 0..MAX_SLUG_ATTEMPTS {
+            let mut taken: Vec<String> = sqlx::query_scalar(&PaintingSlug::get_taken_query())
+                .bind(painting_id)
+                .bind(language.to_str())
+                .bind(&base)
+                .fetch_all(&mut **transaction)
+                .await?;
+            taken.extend(lost.iter().cloned());
+            let slug = unique_slug(&base, &taken);
+
+            if claim_slug(transaction, painting_id, language, &slug).await? {
+                continue 'languages;
+            }
+            debug!(target: "db", "slug:assign - {} was taken concurrently", &slug);
+            lost.push(slug);
+        }
+
+        error!(target: "db", "slug:assign - no free slug for {} after {} attempts", &base, MAX_SLUG_ATTEMPTS);
+        return Err(sqlx::Error::RowNotFound);
+    }
+
+    Ok(())
+}
+
+/// Same as `assign_slugs` in its own transaction.
+pub async fn update_slugs(painting_id: Uuid, title: &Translation) -> Result<(), sqlx::Error> {
+    let client = get_client().await?;
+    let mut transaction = client.begin().await?;
+    assign_slugs(&mut transaction, painting_id, title).await?;
+    transaction.commit().await
+}
+
+/// Creates slugs for paintings that were added before slugs existed.
+pub async fn init_slugs() -> Result<(), sqlx::Error> {
+    let client = get_client().await?;
+    let query = PaintingSlug::get_missing_query();
+    debug!(target: "db", "slug:init - PaintingSlug::get_missing_query {}", &query);
+    let rows = sqlx::query(&query).fetch_all(client).await?;
+
+    for row in rows.iter() {
+        let painting_id: Uuid = row.try_get("id")?;
+        let title_json: JsonValue = row.try_get("painting_title")?;
+        let title: Translation = serde_json::from_value(title_json).unwrap_or_default();
+        update_slugs(painting_id, &title).await?;
+    }
+
+    debug!(target: "app", "slug:init - created slugs for {} paintings", rows.len());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_slug() {
+        assert!(is_slug("winter-2021"));
+        assert!(!is_slug("Winter"));
+        assert!(!is_slug("winter--2021"));
+        assert!(!is_slug("-winter"));
+        assert!(!is_slug("zima'; DROP"));
+        assert!(!is_slug(""));
+    }
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("Zimní ráno č. 2"), "zimni-rano-c-2");
+        assert_eq!(
+            slugify("Žluťoučký kůň úpěl ďábelské ódy"),
+            "zlutoucky-kun-upel-dabelske-ody"
+        );
+        assert_eq!(slugify("  Řeka -- v noci!  "), "reka-v-noci");
+        assert_eq!(slugify("???"), FALLBACK_SLUG);
+        assert!(slugify(&"dlouhý název ".repeat(20)).len() <= MAX_SLUG_LENGTH);
+        assert!(is_slug(&slugify(&"dlouhý název ".repeat(20))));
+    }
+
+    #[test]
+    fn test_unique_slug() {
+        let taken = vec![String::from("rano"), String::from("rano-2")];
+        assert_eq!(unique_slug("rano", &taken), "rano-3");
+        assert_eq!(unique_slug("vecer", &taken), "vecer");
+    }
+}
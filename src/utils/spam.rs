@@ -0,0 +1,127 @@
+pub mod form_token;
+pub mod proof_of_work;
+pub mod rate_limit;
+
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, SocketAddr};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use warp::{Filter, Rejection};
+
+use crate::config::load::{get_sync, ConfigField};
+use crate::utils::spam::rate_limit::RateLimiter;
+
+/// Forms protected by the guard, the name is part of the signed token
+pub const GUARDED_FORMS: [&str; 2] = ["contact", "inquiry"];
+
+lazy_static! {
+    pub static ref FORM_LIMITER: RateLimiter = RateLimiter::new(5, Duration::from_secs(10 * 60));
+}
+
+/// Anti-spam fields every public form sends along with its own data.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FormGuard {
+    pub form_token: Option<String>,
+    /// honeypot, hidden from people so only bots fill it in
+    pub website: Option<String>,
+    pub pow_nonce: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpamRejection {
+    /// honeypot was filled in, the submission should be dropped silently
+    Honeypot,
+    RateLimited,
+    Invalid(&'static str),
+}
+
+/// Values the formGuard.html component needs.
+#[derive(Debug)]
+pub struct FormGuardProps {
+    pub token: String,
+    pub difficulty: u8,
+}
+
+impl FormGuardProps {
+    pub fn new(form: &str) -> Self {
+        Self {
+            token: issue_token(form),
+            difficulty: difficulty(),
+        }
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Failed to get current unix time")
+        .as_secs()
+}
+
+fn secret() -> Vec<u8> {
+    match get_sync::<String>(ConfigField::JwtSecret) {
+        Ok(value) => value.into_bytes(),
+        Err(error) => {
+            error!(target: "app", "spam:secret - failed to get secret {}", error);
+            Vec::new()
+        }
+    }
+}
+
+pub fn difficulty() -> u8 {
+    get_sync::<u8>(ConfigField::ProofOfWorkDifficulty)
+        .unwrap_or(0)
+        .min(proof_of_work::MAX_DIFFICULTY)
+}
+
+pub fn issue_token(form: &str) -> String {
+    form_token::issue(&secret(), form, unix_now())
+}
+
+/// Runs every check in order of cost, the token is burnt only once all others passed.
+pub fn verify(form: &str, guard: &FormGuard, ip: Option<IpAddr>) -> Result<(), SpamRejection> {
+    if guard
+        .website
+        .as_deref()
+        .is_some_and(|value| !value.trim().is_empty())
+    {
+        return Err(SpamRejection::Honeypot);
+    }
+
+    if let Some(ip) = ip {
+        if !FORM_LIMITER.check(form, ip, Instant::now()) {
+            return Err(SpamRejection::RateLimited);
+        }
+    }
+
+    let token = guard
+        .form_token
+        .as_deref()
+        .ok_or(SpamRejection::Invalid("missingFormToken"))?;
+    let now = unix_now();
+    form_token::check(&secret(), form, token, now).map_err(SpamRejection::Invalid)?;
+
+    let nonce = guard.pow_nonce.as_deref().unwrap_or("");
+    if !proof_of_work::verify(token, nonce, difficulty()) {
+        return Err(SpamRejection::Invalid("invalidProofOfWork"));
+    }
+
+    if !form_token::consume(token, now) {
+        return Err(SpamRejection::Invalid("formTokenUsed"));
+    }
+
+    Ok(())
+}
+
+/// Address of the client, `X-Forwarded-For` is honoured only behind a trusted proxy.
+pub fn client_ip() -> impl Filter<Extract = (Option<IpAddr>,), Error = Rejection> + Clone {
+    warp::addr::remote()
+        .and(warp::header::optional::<String>("x-forwarded-for"))
+        .map(|remote: Option<SocketAddr>, forwarded: Option<String>| {
+            let trust_proxy = get_sync::<bool>(ConfigField::TrustProxyHeaders).unwrap_or(false);
+            let forwarded_ip = forwarded
+                .filter(|_| trust_proxy)
+                .and_then(|value| value.split(',').next()?.trim().parse::<IpAddr>().ok());
+            forwarded_ip.or(remote.map(|address| address.ip()))
+        })
+}
@@ -0,0 +1,102 @@
+use hmac::{Hmac, Mac};
+use lazy_static::lazy_static;
+use rand::Rng;
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::utils::hex::{from_hex, to_hex};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Humans need at least a few seconds to fill in a form
+pub const MIN_SUBMIT_SECONDS: u64 = 3;
+/// Tokens of pages left open longer than this are refused
+pub const MAX_TOKEN_AGE_SECONDS: u64 = 4 * 60 * 60;
+
+lazy_static! {
+    /// Signatures of tokens already used, kept until the token would expire anyway
+    static ref USED_TOKENS: Mutex<HashMap<String, u64>> = Mutex::new(HashMap::new());
+}
+
+fn mac(secret: &[u8], form: &str, payload: &str) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(format!("form:{}:{}", form, payload).as_bytes());
+    mac
+}
+
+/// Token has the shape `<issued unix seconds>.<random nonce>.<hmac>` and is bound to one form.
+pub fn issue(secret: &[u8], form: &str, now: u64) -> String {
+    let nonce: u64 = rand::thread_rng().gen();
+    let payload = format!("{}.{:016x}", now, nonce);
+    let signature = to_hex(&mac(secret, form, &payload).finalize().into_bytes());
+    format!("{}.{}", payload, signature)
+}
+
+/// Checks signature and age of the token, returns the message key of the problem.
+pub fn check(secret: &[u8], form: &str, token: &str, now: u64) -> Result<(), &'static str> {
+    let (payload, signature) = token.rsplit_once('.').ok_or("invalidFormToken")?;
+    let (issued, _nonce) = payload.split_once('.').ok_or("invalidFormToken")?;
+    let issued = issued.parse::<u64>().map_err(|_| "invalidFormToken")?;
+
+    let signature = from_hex(signature).ok_or("invalidFormToken")?;
+    if mac(secret, form, payload).verify_slice(&signature).is_err() {
+        return Err("invalidFormToken");
+    }
+
+    if now < issued + MIN_SUBMIT_SECONDS {
+        return Err("formSubmittedTooFast");
+    }
+
+    if now > issued + MAX_TOKEN_AGE_SECONDS {
+        return Err("formTokenExpired");
+    }
+
+    Ok(())
+}
+
+/// Marks the token as used, returns false when it was used before.
+pub fn consume(token: &str, now: u64) -> bool {
+    let mut used = USED_TOKENS
+        .lock()
+        .unwrap_or_else(|error| error.into_inner());
+    used.retain(|_, expires| *expires > now);
+    used.insert(token.to_string(), now + MAX_TOKEN_AGE_SECONDS)
+        .is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_form_token() {
+        let secret = b"form token secret";
+        let token = issue(secret, "contact", 1_000);
+
+        assert_eq!(
+            check(secret, "contact", &token, 1_001),
+            Err("formSubmittedTooFast")
+        );
+        assert_eq!(check(secret, "contact", &token, 1_010), Ok(()));
+        assert_eq!(
+            check(secret, "contact", &token, 1_000 + MAX_TOKEN_AGE_SECONDS + 1),
+            Err("formTokenExpired")
+        );
+        assert_eq!(
+            check(secret, "inquiry", &token, 1_010),
+            Err("invalidFormToken")
+        );
+        assert_eq!(
+            check(b"other secret", "contact", &token, 1_010),
+            Err("invalidFormToken")
+        );
+        assert_eq!(
+            check(secret, "contact", "1000.abc", 1_010),
+            Err("invalidFormToken")
+        );
+
+        assert!(consume(&token, 1_010));
+        assert!(!consume(&token, 1_011));
+    }
+}
@@ -0,0 +1,52 @@
+use sha2::{Digest, Sha256};
+
+/// Anything above this would keep phones busy for minutes
+pub const MAX_DIFFICULTY: u8 = 24;
+
+pub fn leading_zero_bits(hash: &[u8]) -> u32 {
+    let mut bits = 0;
+    for byte in hash {
+        if *byte == 0 {
+            bits += 8;
+        } else {
+            bits += byte.leading_zeros();
+            break;
+        }
+    }
+    bits
+}
+
+/// The client has to find a nonce so that `sha256("<token>:<nonce>")` starts
+/// with `difficulty` zero bits, see static/assets/formGuard.js.
+pub fn verify(token: &str, nonce: &str, difficulty: u8) -> bool {
+    if difficulty == 0 {
+        return true;
+    }
+    if nonce.is_empty() || nonce.len() > 32 {
+        return false;
+    }
+
+    let hash = Sha256::digest(format!("{}:{}", token, nonce).as_bytes());
+    leading_zero_bits(&hash) >= u32::from(difficulty.min(MAX_DIFFICULTY))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proof_of_work() {
+        assert_eq!(leading_zero_bits(&[0, 0, 0b0001_0000]), 19);
+        assert_eq!(leading_zero_bits(&[0xff]), 0);
+
+        let token = "1700000000.00000000000000ff.abc";
+        let nonce = (0u32..)
+            .map(|value| value.to_string())
+            .find(|nonce| verify(token, nonce, 8))
+            .unwrap();
+
+        assert!(verify(token, &nonce, 8));
+        assert!(verify(token, "", 0));
+        assert!(!verify(token, "", 8));
+    }
+}
@@ -0,0 +1,71 @@
+use std::collections::{HashMap, VecDeque};
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Sliding window limiter kept in memory, good enough for a single instance.
+pub struct RateLimiter {
+    limit: usize,
+    window: Duration,
+    hits: Mutex<HashMap<(String, IpAddr), VecDeque<Instant>>>,
+}
+
+impl RateLimiter {
+    pub fn new(limit: usize, window: Duration) -> Self {
+        Self {
+            limit,
+            window,
+            hits: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Records the attempt, returns false when the address already used up its limit.
+    pub fn check(&self, key: &str, ip: IpAddr, now: Instant) -> bool {
+        let mut hits = self.hits.lock().unwrap_or_else(|error| error.into_inner());
+
+        // forget addresses which have been quiet for a whole window
+        if hits.len() > 10_000 {
+            let window = self.window;
+            hits.retain(|_, attempts| {
+                attempts
+                    .back()
+                    .is_some_and(|last| now.duration_since(*last) < window)
+            });
+        }
+
+        let attempts = hits.entry((key.to_string(), ip)).or_default();
+        while attempts
+            .front()
+            .is_some_and(|first| now.duration_since(*first) >= self.window)
+        {
+            attempts.pop_front();
+        }
+
+        if attempts.len() >= self.limit {
+            return false;
+        }
+
+        attempts.push_back(now);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limiter() {
+        let limiter = RateLimiter::new(2, Duration::from_secs(60));
+        let ip: IpAddr = "192.0.2.1".parse().unwrap();
+        let other_ip: IpAddr = "192.0.2.2".parse().unwrap();
+        let now = Instant::now();
+
+        assert!(limiter.check("contact", ip, now));
+        assert!(limiter.check("contact", ip, now));
+        assert!(!limiter.check("contact", ip, now));
+        assert!(limiter.check("inquiry", ip, now));
+        assert!(limiter.check("contact", other_ip, now));
+        assert!(limiter.check("contact", ip, now + Duration::from_secs(61)));
+    }
+}
@@ -0,0 +1,41 @@
+use tokio::time::{interval, Duration};
+
+use crate::database::connection::get_client;
+use crate::database::models::upload::ImageUpload;
+use crate::utils::file_system::fs_delete::remove_file;
+use crate::utils::file_system::fs_read::file_exists;
+
+/// How often abandoned resumable uploads are looked for
+const EXPIRY_CHECK_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// Removes expired upload rows together with their partial files.
+pub async fn expire_abandoned() -> Result<usize, sqlx::Error> {
+    let client = get_client().await?;
+
+    let query = ImageUpload::delete_expired_query();
+    debug!(target: "db", "uploads:expire - ImageUpload::delete_expired_query {}", &query);
+    let expired = sqlx::query_as::<_, ImageUpload>(&query)
+        .fetch_all(client)
+        .await?;
+
+    for upload in expired.iter() {
+        if file_exists(&upload.file_path).await && !remove_file(&upload.file_path).await {
+            error!(target: "app", "uploads:expire - failed to remove {}", &upload.file_path);
+        }
+    }
+
+    Ok(expired.len())
+}
+
+pub fn spawn_expiry_task() {
+    tokio::spawn(async {
+        let mut ticker = interval(EXPIRY_CHECK_INTERVAL);
+        loop {
+            ticker.tick().await;
+            match expire_abandoned().await {
+                Ok(count) => debug!(target: "app", "uploads:expire - removed {} uploads", count),
+                Err(error) => error!(target: "app", "uploads:expire - failed {}", error),
+            }
+        }
+    });
+}
@@ -0,0 +1,64 @@
+// @ts-check
+
+/**
+ * @param {ArrayBuffer} buffer -
+ * @return {number} leading zero bits of the hash
+ */
+const leadingZeroBits = (buffer) => {
+	const bytes = new Uint8Array(buffer);
+	let bits = 0;
+	for (const byte of bytes) {
+		if (byte === 0) {
+			bits += 8;
+			continue;
+		}
+		bits += Math.clz32(byte) - 24;
+		break;
+	}
+	return bits;
+};
+
+/**
+ * Finds nonce so that sha256(`${token}:${nonce}`) starts with difficulty zero bits.
+ * @param {string} token -
+ * @param {number} difficulty -
+ * @return {Promise<string>} nonce -
+ */
+const solveChallenge = async (token, difficulty) => {
+	const encoder = new TextEncoder();
+	for (let nonce = 0; ; nonce++) {
+		const hash = await crypto.subtle.digest(
+			'SHA-256',
+			encoder.encode(`${token}:${nonce}`),
+		);
+		if (leadingZeroBits(hash) >= difficulty) return nonce.toString();
+	}
+};
+
+const addFormGuards = () => {
+	/** @type {NodeListOf<HTMLFormElement>} */
+	const forms = document.querySelectorAll('form[data-pow-difficulty]:not(#buyDialogForm)');
+
+	forms.forEach((form) => {
+		const difficulty = Number(form.dataset.powDifficulty || 0);
+		if (!difficulty) return;
+
+		form.addEventListener('submit', async (e) => {
+			/** @type {HTMLInputElement | null} */
+			const nonceInput = form.querySelector('input[name="pow_nonce"]');
+			/** @type {HTMLInputElement | null} */
+			const tokenInput = form.querySelector('input[name="form_token"]');
+			if (!nonceInput || !tokenInput || nonceInput.value) return;
+
+			e.preventDefault();
+			nonceInput.value = await solveChallenge(tokenInput.value, difficulty);
+			form.requestSubmit();
+		});
+	});
+};
+
+window.formGuard = { solveChallenge };
+
+document.addEventListener('DOMContentLoaded', () => {
+	addFormGuards();
+});
@@ -1,83 +1,72 @@
-const pagePath = 'gallery.html';
-const dirty = false;
-const getDefaultQuery = () => {
-	return {
-		sort: 'created',
-		order: 'desc',
-		limit: '25',
-		offset: '0',
-	};
-};
-let filters = new URLSearchParams(getDefaultQuery());
+// @ts-check
+const { debounce } = window.utils;
 
-const parseUrlQuery = () => {
-	const params = new URLSearchParams(document.location.search);
-	const sort = params.get('sort');
-	const order = params.get('order');
-	const limit = parseInt(params.get('limit'));
-	const offset = parseInt(params.get('offset'));
-	const search = params.get('search');
+const suggestUrl = '/api/v1.0/search/suggest';
+const minQueryLength = 2;
 
-	if (sort && ['created', 'sold', 'title', 'price', 'width', 'height'].includes(sort)) {
-		filters.set('sort', sort);
-	}
-	if (order && (order === 'asc' || order === 'desc')) {
-		filters.set('order', order);
-	}
-	if (!isNaN(limit)) {
-		filters.set('limit', limit.toString());
-	}
-	if (!isNaN(offset)) {
-		filters.set('offset', offset.toString());
-	}
-	if (search && search.trim().length) {
-		filters.set('search', search);
-	}
+/**
+ * @param {string} q -
+ * @param {string} lang -
+ * @return {Promise<Array<{kind: string, label: string, url: string}>>} suggestions -
+ */
+const fetchSuggestions = async (q, lang) => {
+	const params = new URLSearchParams({ q, lang });
+	const response = await fetch(`${suggestUrl}?${params.toString()}`);
+	if (!response.ok) return [];
+	const body = await response.json();
+	return body.data || [];
 };
 
-const redirectWithFilters = () => {
-	window.location.href = `/${pagePath}?${filters.toString()}`;
-}
-
-const resetFilters = () => {
-	filters = new URLSearchParams(getDefaultQuery());
-	redirectWithFilters();
+/**
+ * @param {HTMLUListElement} list -
+ * @param {Array<{kind: string, label: string, url: string}>} suggestions -
+ * @return {void}
+ */
+const renderSuggestions = (list, suggestions) => {
+	list.replaceChildren(...suggestions.map((suggestion) => {
+		const item = document.createElement('li');
+		const link = document.createElement('a');
+		link.href = suggestion.url;
+		link.textContent = suggestion.label;
+		link.className = `gallerySuggestion gallerySuggestion-${suggestion.kind}`;
+		item.appendChild(link);
+		return item;
+	}));
+	list.hidden = suggestions.length === 0;
 };
 
-const handleFilterEvent = (event) => {
-	event.stopPropagation();
-	filters.set(event.target.name, event.target.value);
-	redirectWithFilters();
-};
+const addSuggestions = () => {
+	/** @type {HTMLInputElement | null} */
+	const searchInput = document.querySelector('.galleryFilters input[name="search"]');
+	/** @type {HTMLUListElement | null} */
+	const list = document.querySelector('#gallerySuggestions');
+	if (!searchInput || !list) return;
+	const lang = searchInput.dataset.lang || 'cs';
+	let latest = '';
 
-const addInputEventListeners = () => {
-	const sortInput = document.querySelector('.galleryFilters > select:nth-child(1)');
-	if (sortInput) {
-		sortInput.addEventListener('change', event => handleFilterEvent(event));
-		sortInput.value = filters.get('sort');
-	}
+	searchInput.addEventListener('input', debounce(async () => {
+		const q = searchInput.value.trim();
+		latest = q;
+		if (q.length < minQueryLength) {
+			renderSuggestions(list, []);
+			return;
+		}
+		const suggestions = await fetchSuggestions(q, lang).catch(() => []);
+		// answers can arrive out of order, only the last query counts
+		if (q === latest) renderSuggestions(list, suggestions);
+	}, 200));
 
-	const orderInput = document.querySelector('.galleryFilters > select:nth-child(2)');
-	if (orderInput) {
-		orderInput.addEventListener('change', event => handleFilterEvent(event));
-		orderInput.value = filters.get('order');
-	}
-
-	const searchInput = document.querySelector('.galleryFilters > input');
-	if (searchInput) {
-		searchInput.addEventListener(
-			'input',
-			window.utils.debounce((event) => {handleFilterEvent(event)}, 300)
-		);
-		searchInput.value = filters.get('search') || '';
-	}
-};
+	searchInput.addEventListener('keydown', (event) => {
+		if (event.key === 'Escape') renderSuggestions(list, []);
+	});
 
-const onLoad = () => {
-	parseUrlQuery();
-	addInputEventListeners();
+	document.addEventListener('click', (event) => {
+		if (event.target !== searchInput && !list.contains(/** @type {Node} */ (event.target))) {
+			renderSuggestions(list, []);
+		}
+	});
 };
 
 document.addEventListener('DOMContentLoaded', () => {
-	onLoad();
-});
\ No newline at end of file
+	addSuggestions();
+});
@@ -27,6 +27,28 @@ const updateDialogImage = (tempIndex) => {
 	dialogImage.src=`${baseUrl}_${dialogImage.getAttribute('maxWidth')}.jpg`;
 };
 
+const formTokenUrl = '/api/v1.0/form-tokens/inquiry';
+
+/**
+ * A submitted token is consumed whatever the response, the next submission needs a new one.
+ * @param {HTMLFormElement} form -
+ * @return {Promise<void>}
+ */
+const refreshFormToken = async (form) => {
+	/** @type {HTMLInputElement | null} */
+	const nonceInput = form.querySelector('input[name="pow_nonce"]');
+	/** @type {HTMLInputElement | null} */
+	const tokenInput = form.querySelector('input[name="form_token"]');
+	if (nonceInput) nonceInput.value = '';
+
+	const response = await fetch(formTokenUrl, { cache: 'no-store' }).catch(() => null);
+	if (!response || !response.ok || !tokenInput) return;
+	const body = await response.json().catch(() => null);
+	if (!body || !body.data) return;
+	tokenInput.value = body.data.token;
+	form.dataset.powDifficulty = String(body.data.difficulty);
+};
+
 const addOrderEvents = () => {
 	/** @type {HTMLButtonElement | null} */
 	const buyButton = document.querySelector('#buyButton');
@@ -43,6 +65,43 @@ const addOrderEvents = () => {
 		e.stopPropagation();
 		buyDialog.close();
 	});
+
+	/** @type {HTMLFormElement | null} */
+	const buyForm = document.querySelector('#buyDialogForm');
+	/** @type {HTMLParagraphElement | null} */
+	const result = document.querySelector('#buyDialogResult');
+	if (!buyForm) return;
+
+	buyForm.addEventListener('click', (e) => {
+		e.stopPropagation();
+	});
+
+	buyForm.addEventListener('submit', async (e) => {
+		e.preventDefault();
+		/** @type {HTMLInputElement | null} */
+		const nonceInput = buyForm.querySelector('input[name="pow_nonce"]');
+		/** @type {HTMLInputElement | null} */
+		const tokenInput = buyForm.querySelector('input[name="form_token"]');
+		const difficulty = Number(buyForm.dataset.powDifficulty || 0);
+		if (difficulty && nonceInput && tokenInput && !nonceInput.value) {
+			nonceInput.value = await window.formGuard.solveChallenge(tokenInput.value, difficulty);
+		}
+
+		const body = Object.fromEntries(new FormData(buyForm).entries());
+		const response = await fetch(buyForm.action, {
+			method: 'POST',
+			headers: { 'Content-Type': 'application/json' },
+			body: JSON.stringify(body),
+		}).catch(() => null);
+
+		if (result) {
+			result.textContent = (response && response.ok)
+				? buyForm.dataset.sentMessage || ''
+				: buyForm.dataset.failedMessage || '';
+		}
+		if (response && response.ok) buyForm.reset();
+		await refreshFormToken(buyForm);
+	});
 };
 
 const addPhotoEvents = () => {
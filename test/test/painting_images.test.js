@@ -76,4 +76,38 @@ describe('painting images test', function () {
 		console.log('response', response);
 		console.log('data', data);
 	});
-})
\ No newline at end of file
+
+	it('create multiple images with per-file metadata', async function () {
+		const formData = new FormData();
+		formData.append('image_front', createReadStream('../images/preview.jpg'));
+		formData.append('meta_front', JSON.stringify({
+			preview: true,
+			title_cs: 'predni strana',
+			title_en: 'front side',
+		}));
+		formData.append('image_detail', createReadStream('../images/painting_image.jpg'));
+		formData.append('meta_detail', JSON.stringify({
+			preview: false,
+			alt_cs: 'detail obrazu',
+			alt_en: 'painting detail',
+		}));
+
+		const query = new URLSearchParams();
+		query.append('painting_id', paintingId);
+
+		const request = new Request(`${serviceUrl}/images?${query.toString()}`, {
+			method: 'POST',
+			body: formData,
+			headers: {
+				...formData.getHeaders(),
+				'Authorization': `Bearer ${token}`
+			}
+		});
+
+		const response = await fetch(request);
+		const data = await response.json();
+		response.status.should.equal(201);
+		data.data.length.should.equal(2);
+		data.data.map(result => result.key).should.have.members(['front', 'detail']);
+	});
+})